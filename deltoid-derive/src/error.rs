@@ -36,6 +36,7 @@ pub type DeriveResult<T> = Result<T, DeriveError>;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum DeriveError {
+    BorrowedField { field: String },
     BugDetected {
         file: &'static str,
         line: u32,
@@ -49,6 +50,39 @@ pub enum DeriveError {
         line: u32,
         column: u32
     },
+    InvalidMarkerValue { marker: &'static str },
+    MisplacedMarker {
+        marker: String,
+        allowed_on: &'static str,
+        #[serde(skip, default = "proc_macro2::Span::call_site")]
+        span: proc_macro2::Span,
+    },
+    UnknownMarker { marker: String },
+    UnsupportedMarker { marker: &'static str },
 
     // Add more error variants here
 }
+
+impl DeriveError {
+    /// Return a `compile_error!` invocation that reports `self` at
+    /// the site of the derive, or at the offending tokens if known.
+    pub fn to_compile_error(&self) -> proc_macro2::TokenStream {
+        let msg: String = match self {
+            Self::UnknownMarker { marker } =>
+                format!("unknown #[delta(...)] attribute key `{}`", marker),
+            Self::MisplacedMarker { marker, allowed_on, .. } =>
+                format!("#[delta({})] can only be used on {}", marker, allowed_on),
+            Self::BorrowedField { field } => format!(
+                "field `{}` borrows its value, which can't be delta'd; \
+                 mark it with #[delta(ignore_field)]",
+                field
+            ),
+            _ => format!("failed to derive Delta: {:?}", self),
+        };
+        match self {
+            Self::MisplacedMarker { span, .. } =>
+                quote::quote_spanned! { *span => compile_error!(#msg); },
+            _ => quote::quote! { compile_error!(#msg); },
+        }
+    }
+}
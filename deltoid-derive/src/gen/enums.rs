@@ -12,6 +12,7 @@ use quote::{format_ident, quote};
 
 pub(crate) fn define_delta_enum(input: &InputType) -> DeriveResult<TokenStream2> {
     if !input.is_enum() { return bug_detected!() }
+    let unchanged: Ident2 = input.unchanged_variant_name()?;
     let delta_type_name: &Ident2 = input.delta_type_name()?;
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
@@ -36,6 +37,18 @@ pub(crate) fn define_delta_enum(input: &InputType) -> DeriveResult<TokenStream2>
         })
         .collect();
     let where_clause = quote! { /*where*/ };
+    let serde_repr: &[NestedMeta] = input.serde_repr()?;
+    let serde_attr: TokenStream2 = if serde_repr.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[serde( #(#serde_repr),* )] }
+    };
+    let delta_derives: &[Path] = input.delta_derives()?;
+    let derive_attr: TokenStream2 = if delta_derives.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[derive( #(#delta_derives),* )] }
+    };
     let enum_body: TokenStream2 = enum_variants.iter()
         .map(|enum_variant: &EnumVariant| -> DeriveResult<_> {
             let variant_name = &enum_variant.name;
@@ -67,21 +80,26 @@ pub(crate) fn define_delta_enum(input: &InputType) -> DeriveResult<TokenStream2>
     Ok(quote! {
         #[derive(Clone, PartialEq)]
         #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+        #derive_attr
+        #serde_attr
         pub enum #delta_type_name<#(#type_param_decls),*>
             #where_clause
         {
             #enum_body
+            /// The value is unchanged.
+            #unchanged,
         }
     })
 }
 
 pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2> {
     if !input.is_enum() { return bug_detected!() }
+    let unchanged: Ident2 = input.unchanged_variant_name()?;
     let delta_type_name: &Ident2 = input.delta_type_name()?;
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -229,6 +247,13 @@ pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2>
             #(
                 #field_patterns => #match_bodies,
             )*
+            Self::#unchanged => {
+                let type_name = String::new()
+                    + stringify!(#delta_type_name)
+                    + "::"
+                    + stringify!(#unchanged);
+                f.debug_struct(&type_name).finish()
+            },
         }
     };
     Ok(quote! {
@@ -245,13 +270,57 @@ pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2>
     })
 }
 
+pub(crate) fn define_Default_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_enum() { return bug_detected!() }
+    if !input.default_delta()? { return Ok(TokenStream2::new()) }
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let unchanged: Ident2 = input.unchanged_variant_name()?;
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => {
+                let T: &Ident2 = &type_param.ident;
+                // NOTE: `bounds` defines trait bounds on the corresponding
+                // type parameter `T` in `InputType::Struct#type_param`:
+                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
+                    .map(|trait_bound| quote! { #trait_bound })
+                    .collect();
+                quote! {
+                    #T: deltoid::Core
+                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
+                }
+            },
+        })
+        .collect();
+    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .collect();
+    let where_clause = quote! { where #(#predicates),* };
+    // NOTE: The default delta leaves a value of any variant unchanged:
+    Ok(quote! {
+        impl<#(#type_param_decls),*> std::default::Default
+            for #delta_type_name<#type_params>
+            #where_clause
+        {
+            fn default() -> Self {
+                Self::#unchanged
+            }
+        }
+    })
+}
+
 pub(crate) fn define_Core_impl(input: &InputType) -> DeriveResult<TokenStream2> {
     if !input.is_enum() { return bug_detected!() }
     let type_name: &Ident2 = input.type_name()?;
     let delta_type_name: &Ident2 = input.delta_type_name()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -292,11 +361,12 @@ pub(crate) fn define_Core_impl(input: &InputType) -> DeriveResult<TokenStream2>
 
 pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2> {
     if !input.is_enum() { return bug_detected!() }
+    let unchanged: Ident2 = input.unchanged_variant_name()?;
     let type_name: &Ident2 = input.type_name()?;
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -348,9 +418,13 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                 .map(|((f, lhs_name), delta_name)| if f.ignore_field() {
                     quote! { #lhs_name.clone() }
                 } else {
+                    let value = f.apply_tokens(
+                        quote! { #lhs_name },
+                        quote! { delta.clone(/*TODO*/) },
+                    );
                     quote! {
                         if let Some(delta) = #delta_name {
-                            #lhs_name.apply(delta.clone(/*TODO*/))?
+                            #value
                         } else {
                             #lhs_name.clone()
                         }
@@ -400,9 +474,13 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                 .map(|((f, lhs_name), delta_name)| if f.ignore_field() {
                     quote! { #lhs_name.clone() }
                 } else {
+                    let value = f.apply_tokens(
+                        quote! { #lhs_name },
+                        quote! { delta.clone(/*TODO*/) },
+                    );
                     quote! {
                         if let Some(delta) = #delta_name {
-                            #lhs_name.apply(delta.clone(/*TODO*/))?
+                            #value
                         } else {
                             #lhs_name.clone()
                         }
@@ -451,6 +529,7 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
             #[allow(unused)]
             fn apply(&self, delta: Self::Delta) -> deltoid::DeltaResult<Self> {
                 match (self, &delta/*TODO*/) {
+                    (_, Self::Delta::#unchanged) => Ok(self.clone()),
                     #(
                         (#lhs_patterns, #delta_patterns) => {
                             #match_bodies
@@ -464,11 +543,12 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
 
 pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
     if !input.is_enum() { return bug_detected!() }
+    let unchanged: Ident2 = input.unchanged_variant_name()?;
     let type_name: &Ident2 = input.type_name()?;
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -520,11 +600,15 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
                 .map(|((f, lhs_name), rhs_name)| if f.ignore_field() {
                     quote! { std::marker::PhantomData }
                 } else {
+                    let delta = f.delta_tokens(
+                        quote! { #lhs_name },
+                        quote! { #rhs_name },
+                    );
                     quote! {
                         if #lhs_name == #rhs_name {
                             None
                         } else {
-                            Some(#lhs_name.delta(#rhs_name)?)
+                            Some(#delta)
                         }
                     }
                 })
@@ -536,10 +620,14 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
             rhs_patterns.push(quote! {
                 Self::#variant_name { #(#field_names: #rhs_names),* }
             });
-            match_bodies.push(quote! {
-                Ok(Self::Delta::#variant_name {
-                    #(#field_names: #field_values),*
-                })
+            match_bodies.push(if variant_fields.iter().all(FieldDesc::ignore_field) {
+                quote! { Ok(Self::Delta::#unchanged) }
+            } else {
+                quote! {
+                    Ok(Self::Delta::#variant_name {
+                        #(#field_names: #field_values),*
+                    })
+                }
             });
             // NOTE: then, push the pairwise-unequal patterns:
             lhs_patterns.push(quote! { _ });
@@ -565,11 +653,15 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
                 .map(|(f, (lhs_name, rhs_name))| if f.ignore_field() {
                     quote! { std::marker::PhantomData }
                 } else {
+                    let delta = f.delta_tokens(
+                        quote! { #lhs_name },
+                        quote! { #rhs_name },
+                    );
                     quote! {
                         if #lhs_name == #rhs_name {
                             None
                         } else {
-                            Some(#lhs_name.delta(#rhs_name)?)
+                            Some(#delta)
                         }
                     }
                 })
@@ -581,8 +673,10 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
             rhs_patterns.push(quote! {
                 Self::#variant_name( #(#rhs_names),* )
             });
-            match_bodies.push(quote! {
-                Ok(Self::Delta::#variant_name( #(#field_values),* ))
+            match_bodies.push(if variant_fields.iter().all(FieldDesc::ignore_field) {
+                quote! { Ok(Self::Delta::#unchanged) }
+            } else {
+                quote! { Ok(Self::Delta::#variant_name( #(#field_values),* )) }
             });
             // NOTE: then, push the pairwise-unequal patterns:
             lhs_patterns.push(quote! { _ });
@@ -596,7 +690,7 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
             // NOTE: first, push the pairwise-equal patterns:
             lhs_patterns.push(quote! { Self::#variant_name });
             rhs_patterns.push(quote! { Self::#variant_name });
-            match_bodies.push(quote! { Ok(Self::Delta::#variant_name) });
+            match_bodies.push(quote! { Ok(Self::Delta::#unchanged) });
             // NOTE: then, push the pairwise-unequal patterns:
             lhs_patterns.push(quote! { _ });
             rhs_patterns.push(quote! { rhs @ Self::#variant_name });
@@ -626,12 +720,13 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
 
 pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
     if !input.is_enum() { return bug_detected!() }
+    let unchanged: Ident2 = input.unchanged_variant_name()?;
     let type_name: &Ident2 = input.type_name()?;
     let delta_type_name: &Ident2 = input.delta_type_name()?;
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -676,16 +771,15 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                         Ok(if field.ignore_field() {
                             quote! { #fname: Default::default() }
                         } else {
-                            quote! {
-                                #fname: <#ftype>::from_delta(
-                                    #fname.ok_or_else(|| DeltaError::ExpectedValue {
-                                        type_name: stringify!(#ftype).to_string(),
-                                        file: file!().to_string(),
-                                        line: line!(),
-                                        column: column!(),
-                                    })?
-                                )?
-                            }
+                            let value = field.field_from_delta_tokens(quote! {
+                                #fname.ok_or_else(|| DeltaError::ExpectedValue {
+                                    type_name: stringify!(#ftype).to_string(),
+                                    file: file!().to_string(),
+                                    line: line!(),
+                                    column: column!(),
+                                })?
+                            });
+                            quote! { #fname: #value }
                         })
                     })
                     .collect::<DeriveResult<_>>()?;
@@ -711,16 +805,14 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                         Ok(if field.ignore_field() {
                             quote! { Default::default() }
                         } else {
-                            quote! {
-                                <#ftype>::from_delta(
-                                    #fname.ok_or_else(|| DeltaError::ExpectedValue {
-                                        type_name: stringify!(#ftype).to_string(),
-                                        file: file!().to_string(),
-                                        line: line!(),
-                                        column: column!(),
-                                    })?
-                                )?
-                            }
+                            field.field_from_delta_tokens(quote! {
+                                #fname.ok_or_else(|| DeltaError::ExpectedValue {
+                                    type_name: stringify!(#ftype).to_string(),
+                                    file: file!().to_string(),
+                                    line: line!(),
+                                    column: column!(),
+                                })?
+                            })
                         })
                     })
                     .collect::<DeriveResult<_>>()?;
@@ -747,6 +839,12 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                 #[allow(unused)] use deltoid::{DeltaError, FromDelta};
                 Ok(match delta {
                     #match_body
+                    #delta_type_name::#unchanged => return Err(DeltaError::ExpectedValue {
+                        type_name: stringify!(#type_name).to_string(),
+                        file: file!().to_string(),
+                        line: line!(),
+                        column: column!(),
+                    }),
                 })
             }
         }
@@ -760,7 +858,7 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -805,7 +903,8 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                         Ok(if field.ignore_field() {
                             quote! { #fname: std::marker::PhantomData }
                         } else {
-                            quote! { #fname: Some(#fname.into_delta()?) }
+                            let value = field.field_into_delta_tokens(quote! { #fname });
+                            quote! { #fname: Some(#value) }
                         })
                     })
                     .collect::<DeriveResult<_>>()?;
@@ -829,7 +928,8 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                         Ok(if field.ignore_field() {
                             quote! { std::marker::PhantomData }
                         } else {
-                            quote! { Some(#fname.into_delta()?) }
+                            let value = field.field_into_delta_tokens(quote! { #fname });
+                            quote! { Some(#value) }
                         })
                     })
                     .collect::<DeriveResult<_>>()?;
@@ -863,3 +963,384 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
         }
     })
 }
+
+pub(crate) fn define_DeltaOps_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_enum() { return bug_detected!() }
+    let unchanged: Ident2 = input.unchanged_variant_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let enum_variants: &[EnumVariant] = input.enum_variants()?;
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => {
+                let T: &Ident2 = &type_param.ident;
+                // NOTE: `bounds` defines trait bounds on the corresponding
+                // type parameter `T` in `InputType::Struct#type_param`:
+                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
+                    .map(|trait_bound| quote! { #trait_bound })
+                    .collect();
+                quote! {
+                    #T: deltoid::Core
+                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
+                }
+            },
+        })
+        .collect();
+    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .collect();
+    let where_clause = quote! { where #(#predicates),* };
+    let mut match_body = TokenStream2::new();
+    for enum_variant in enum_variants.iter() {
+        let variant_name = &enum_variant.name;
+        let field_names: Vec<Ident> = enum_variant.fields()
+            .enumerate()
+            .map(|(fidx, field): (usize, &FieldDesc)| match field.name_ref() {
+                Ok(fname) => fname.clone(),
+                Err(_) => format_ident!("field_{}", fidx),
+            })
+            .collect();
+        let changed_names: Vec<&Ident> = enum_variant.fields()
+            .zip(field_names.iter())
+            .filter(|(field, _)| !field.ignore_field())
+            .map(|(_, fname)| fname)
+            .collect();
+        // NOTE: A delta between 2 values of a variant without any
+        //       (non-ignored) fields is `Unchanged`, so a delta to such a
+        //       variant always changes the variant of a value.  A delta to
+        //       a variant whose field deltas are all `None` is only ever
+        //       applied to a value of the same variant, which it leaves as is.
+        let is_empty: TokenStream2 = if changed_names.is_empty() {
+            quote! { false }
+        } else {
+            quote! { true #( && #changed_names.is_none() )* }
+        };
+        match_body.extend(match enum_variant.struct_variant {
+            StructVariant::NamedStruct => quote! {
+                #delta_type_name::#variant_name { #(#field_names),* } => #is_empty,
+            },
+            StructVariant::TupleStruct => quote! {
+                #delta_type_name::#variant_name( #(#field_names),* ) => #is_empty,
+            },
+            StructVariant::UnitStruct => quote! {
+                #delta_type_name::#variant_name => #is_empty,
+            },
+        });
+    }
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::DeltaOps
+            for #delta_type_name<#type_params>
+            #where_clause
+        {
+            #[allow(unused)]
+            fn is_empty(&self) -> bool {
+                match self {
+                    #match_body
+                    #delta_type_name::#unchanged => true,
+                }
+            }
+        }
+    })
+}
+
+pub(crate) fn define_Compose_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_enum() { return bug_detected!() }
+    let type_name: &Ident2 = input.type_name()?;
+    let unchanged: Ident2 = input.unchanged_variant_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let enum_variants: &[EnumVariant] = input.enum_variants()?;
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => {
+                let T: &Ident2 = &type_param.ident;
+                // NOTE: `bounds` defines trait bounds on the corresponding
+                // type parameter `T` in `InputType::Struct#type_param`:
+                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
+                    .map(|trait_bound| quote! { #trait_bound })
+                    .collect();
+                quote! {
+                    #T: deltoid::Core
+                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
+                }
+            },
+        })
+        .collect();
+    // NOTE: The delta type only implements `Compose` if the delta types
+    //       of all fields of all its variants do.  Those bounds are
+    //       higher-ranked so that they aren't rejected as trivially false
+    //       for non-generic types, and see `FieldDesc::compose_bound()`
+    //       for their form:
+    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .chain(enum_variants.iter()
+            .flat_map(|enum_variant: &EnumVariant| enum_variant.fields())
+            .filter(|field: &&FieldDesc| !field.ignore_field())
+            .filter_map(|field: &FieldDesc| field.compose_bound(type_name)))
+        .collect();
+    let where_clause = quote! { where #(#predicates),* };
+    let mut compose_body = TokenStream2::new();
+    let mut switch_body = TokenStream2::new();
+    let mut clone_body = TokenStream2::new();
+    for enum_variant in enum_variants.iter() {
+        let variant_name = &enum_variant.name;
+        let fields: Vec<&FieldDesc> = enum_variant.fields().collect();
+        let field_names: Vec<Ident> = fields.iter()
+            .enumerate()
+            .map(|(fidx, field): (usize, &&FieldDesc)| match field.name_ref() {
+                Ok(fname) => fname.clone(),
+                Err(_) => format_ident!("field_{}", fidx),
+            })
+            .collect();
+        let lhs_names: Vec<Ident> = field_names.iter()
+            .map(|fname| format_ident!("lhs_{}", fname))
+            .collect();
+        let rhs_names: Vec<Ident> = field_names.iter()
+            .map(|fname| format_ident!("rhs_{}", fname))
+            .collect();
+        let field_values: Vec<TokenStream2> = fields.iter()
+            .zip(lhs_names.iter().zip(rhs_names.iter()))
+            .map(|(field, (lhs_name, rhs_name))| if field.ignore_field() {
+                quote! { std::marker::PhantomData }
+            } else {
+                quote! {
+                    match (#lhs_name, #rhs_name) {
+                        (lhs, None) => lhs.clone(),
+                        (None, Some(rhs)) => Some(rhs.clone()),
+                        (Some(lhs), Some(rhs)) =>
+                            Some(deltoid::ComposeRef::compose_ref(lhs, rhs)?),
+                    }
+                }
+            })
+            .collect();
+        let changed_names: Vec<&Ident> = fields.iter()
+            .zip(rhs_names.iter())
+            .filter(|(field, _)| !field.ignore_field())
+            .map(|(_, rhs_name)| rhs_name)
+            .collect();
+        let cloned_values: Vec<TokenStream2> = fields.iter()
+            .zip(rhs_names.iter())
+            .map(|(field, rhs_name)| if field.ignore_field() {
+                quote! { std::marker::PhantomData }
+            } else {
+                quote! { #rhs_name.clone() }
+            })
+            .collect();
+        // NOTE: A delta that changes the variant of a value must hold
+        //       the value of every field:
+        let switch = |pattern: TokenStream2, value: TokenStream2| quote! {
+            (_, #pattern) => if true #( && #changed_names.is_some() )* {
+                Ok(#value)
+            } else {
+                Err(deltoid::DeltaError::FailedToApplyDelta {
+                    reason: format!(
+                        "{}::{} can't change the variant of a value \
+                         without the value of every field",
+                        stringify!(#delta_type_name), stringify!(#variant_name),
+                    ),
+                })
+            },
+        };
+        match enum_variant.struct_variant {
+            StructVariant::NamedStruct => {
+                compose_body.extend(quote! {
+                    (
+                        Self::#variant_name { #( #field_names: #lhs_names ),* },
+                        Self::#variant_name { #( #field_names: #rhs_names ),* },
+                    ) => Ok(Self::#variant_name {
+                        #( #field_names: #field_values, )*
+                    }),
+                });
+                switch_body.extend(switch(
+                    quote! { Self::#variant_name { #( #field_names: #rhs_names ),* } },
+                    quote! { Self::#variant_name { #( #field_names: #cloned_values, )* } },
+                ));
+                clone_body.extend(quote! {
+                    Self::#variant_name { #( #field_names: #rhs_names ),* } =>
+                        Self::#variant_name { #( #field_names: #cloned_values, )* },
+                });
+            },
+            StructVariant::TupleStruct => {
+                compose_body.extend(quote! {
+                    (
+                        Self::#variant_name( #( #lhs_names ),* ),
+                        Self::#variant_name( #( #rhs_names ),* ),
+                    ) => Ok(Self::#variant_name( #( #field_values ),* )),
+                });
+                switch_body.extend(switch(
+                    quote! { Self::#variant_name( #( #rhs_names ),* ) },
+                    quote! { Self::#variant_name( #( #cloned_values ),* ) },
+                ));
+                clone_body.extend(quote! {
+                    Self::#variant_name( #( #rhs_names ),* ) =>
+                        Self::#variant_name( #( #cloned_values ),* ),
+                });
+            },
+            StructVariant::UnitStruct => {
+                compose_body.extend(quote! {
+                    (Self::#variant_name, Self::#variant_name) =>
+                        Ok(Self::#variant_name),
+                });
+                switch_body.extend(switch(
+                    quote! { Self::#variant_name },
+                    quote! { Self::#variant_name },
+                ));
+                clone_body.extend(quote! {
+                    Self::#variant_name => Self::#variant_name,
+                });
+            },
+        }
+    }
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Compose
+            for #delta_type_name<#type_params>
+            #where_clause
+        {
+            #[allow(unused, unreachable_patterns)]
+            fn compose(&self, next: &Self) -> deltoid::DeltaResult<Self> {
+                match (self, next) {
+                    // NOTE: `Self` isn't necessarily `Clone` here, but
+                    //       the deltas of its fields are:
+                    (rhs, Self::#unchanged) | (Self::#unchanged, rhs) => Ok(match rhs {
+                        #clone_body
+                        Self::#unchanged => Self::#unchanged,
+                    }),
+                    #compose_body
+                    #switch_body
+                }
+            }
+        }
+    })
+}
+
+pub(crate) fn define_Invert_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_enum() { return bug_detected!() }
+    let unchanged: Ident2 = input.unchanged_variant_name()?;
+    let type_name: &Ident2 = input.type_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let enum_variants: &[EnumVariant] = input.enum_variants()?;
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => {
+                let T: &Ident2 = &type_param.ident;
+                // NOTE: `bounds` defines trait bounds on the corresponding
+                // type parameter `T` in `InputType::Struct#type_param`:
+                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
+                    .map(|trait_bound| quote! { #trait_bound })
+                    .collect();
+                quote! {
+                    #T: deltoid::Core
+                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
+                }
+            },
+        })
+        .collect();
+    // NOTE: The delta type only implements `Invert` if the delta types
+    //       of all fields of all its variants do, and if `base` can be
+    //       converted into a delta to undo a change of variant.  As with
+    //       `Compose`, those bounds are higher-ranked:
+    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .chain(enum_variants.iter()
+            .flat_map(|enum_variant: &EnumVariant| enum_variant.fields())
+            .filter(|field: &&FieldDesc| !field.ignore_field())
+            .map(|field: &FieldDesc| {
+                let field_type: &Type = field.type_ref();
+                let delta_type: TokenStream2 = field.delta_type_tokens();
+                quote! { for<'__a> #delta_type: deltoid::Invert<#field_type> }
+            }))
+        .chain(std::iter::once(quote! {
+            for<'__a> #type_name<#type_params>:
+                std::clone::Clone + deltoid::IntoDelta<Delta = Self>
+        }))
+        .collect();
+    let where_clause = quote! { where #(#predicates),* };
+    let mut invert_body = TokenStream2::new();
+    for enum_variant in enum_variants.iter() {
+        let variant_name = &enum_variant.name;
+        let fields: Vec<&FieldDesc> = enum_variant.fields().collect();
+        let field_names: Vec<Ident> = fields.iter()
+            .enumerate()
+            .map(|(fidx, field): (usize, &&FieldDesc)| match field.name_ref() {
+                Ok(fname) => fname.clone(),
+                Err(_) => format_ident!("field_{}", fidx),
+            })
+            .collect();
+        let delta_names: Vec<Ident> = field_names.iter()
+            .map(|fname| format_ident!("delta_{}", fname))
+            .collect();
+        let base_names: Vec<Ident> = field_names.iter()
+            .map(|fname| format_ident!("base_{}", fname))
+            .collect();
+        let field_values: Vec<TokenStream2> = fields.iter()
+            .zip(delta_names.iter().zip(base_names.iter()))
+            .map(|(field, (delta_name, base_name))| if field.ignore_field() {
+                quote! { std::marker::PhantomData }
+            } else {
+                quote! {
+                    match #delta_name {
+                        None => None,
+                        Some(delta) =>
+                            Some(deltoid::Invert::invert(delta, #base_name)?),
+                    }
+                }
+            })
+            .collect();
+        match enum_variant.struct_variant {
+            StructVariant::NamedStruct => invert_body.extend(quote! {
+                (
+                    Self::#variant_name { #( #field_names: #delta_names ),* },
+                    #type_name::#variant_name { #( #field_names: #base_names ),* },
+                ) => Ok(Self::#variant_name {
+                    #( #field_names: #field_values, )*
+                }),
+            }),
+            StructVariant::TupleStruct => invert_body.extend(quote! {
+                (
+                    Self::#variant_name( #( #delta_names ),* ),
+                    #type_name::#variant_name( #( #base_names ),* ),
+                ) => Ok(Self::#variant_name( #( #field_values ),* )),
+            }),
+            StructVariant::UnitStruct => invert_body.extend(quote! {
+                (Self::#variant_name, #type_name::#variant_name) =>
+                    Ok(Self::#variant_name),
+            }),
+        }
+    }
+    // NOTE: A delta that changed the variant of `base` is undone by
+    //       a delta that holds the value of every field of `base`:
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Invert<#type_name<#type_params>>
+            for #delta_type_name<#type_params>
+            #where_clause
+        {
+            #[allow(unused, unreachable_patterns)]
+            fn invert(
+                &self,
+                base: &#type_name<#type_params>,
+            ) -> deltoid::DeltaResult<Self> {
+                match (self, base) {
+                    (Self::#unchanged, _) => Ok(Self::#unchanged),
+                    #invert_body
+                    _ => deltoid::IntoDelta::into_delta(base.clone()),
+                }
+            }
+        }
+    })
+}
@@ -1,33 +1,258 @@
 //!
 
-use proc_macro2::{
-    Delimiter as Delimiter2,
-    TokenTree as TokenTree2
-};
+use crate::{DeriveError, DeriveResult};
 use syn::*;
+use syn::spanned::Spanned;
 
 
-/// A `field` in the input struct or input enum variant
-/// is marked with #[delta(ignore_field)].
+/// A `field` in the input struct or input enum variant is marked with
+/// #[delta(ignore_field)], or equivalently its alias #[delta(skip)].
+/// Such a field is never diffed, and on apply it is carried over
+/// unchanged from the value the delta is applied to.
 pub(crate) fn ignore_field(field: &Field) -> bool {
-    let mut ignore = false;
-    for attr in field.attrs.iter() {
-        let attr_segments: Vec<String> = attr.path.segments.iter()
-            .map(|path_segment| format!("{}", path_segment.ident))
-            .collect();
-        let is_delta_attr = attr_segments == &["delta"];
-        let arg_tokens_iter = attr.tokens.clone().into_iter().next();
-        const DELIM: Delimiter2 = Delimiter2::Parenthesis;
-        let arg_is_ignore_field = match arg_tokens_iter {
-            Some(TokenTree2::Group(g)) if g.delimiter() == DELIM => {
-                let tokens: Vec<String> = g.stream().clone().into_iter()
-                    .map(|tt| format!("{}", tt))
-                    .collect();
-                tokens == &["ignore_field"]
-            },
+    has_marker(&field.attrs, "ignore_field") || has_marker(&field.attrs, "skip")
+}
+
+/// An input struct or input enum is marked with #[delta(default)].
+pub(crate) fn default_delta(attrs: &[Attribute]) -> bool {
+    has_marker(attrs, "default")
+}
+
+/// An input struct is marked with #[repr(transparent)].
+pub(crate) fn repr_transparent(attrs: &[Attribute]) -> bool {
+    attrs.iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested.into_iter()),
+            _ => None,
+        })
+        .flatten()
+        .any(|arg| match arg {
+            NestedMeta::Meta(Meta::Path(path)) => path.is_ident("transparent"),
             _ => false,
+        })
+}
+
+/// An input struct is marked with #[delta(patch)], i.e. a separate patch
+/// type should be generated for it, along with an `apply_patch()` method.
+pub(crate) fn patch(attrs: &[Attribute]) -> bool {
+    has_marker(attrs, "patch")
+}
+
+/// A `field` in the input struct or input enum variant is marked with
+/// #[delta(sort_keys_by = "path")], where `path` names a comparator
+/// `fn(&K, &K) -> std::cmp::Ordering` used to order the field's map delta.
+pub(crate) fn sort_keys_by(field: &Field) -> DeriveResult<Option<Path>> {
+    for arg in delta_args(&field.attrs) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = arg {
+            if !nv.path.is_ident("sort_keys_by") { continue }
+            return match nv.lit {
+                Lit::Str(lit) => lit.parse::<Path>()
+                    .map(Some)
+                    .map_err(|_| DeriveError::InvalidMarkerValue {
+                        marker: "sort_keys_by"
+                    }),
+                _ => Err(DeriveError::InvalidMarkerValue {
+                    marker: "sort_keys_by"
+                }),
+            };
+        }
+    }
+    Ok(None)
+}
+
+/// Return the name of the delta type generated for the input type `ident`.
+/// This is `{ident}Delta` unless the input type is marked with
+/// #[delta(delta_name = "Name")], in which case `Name` must be a legal
+/// identifier.
+pub(crate) fn delta_name(attrs: &[Attribute], ident: &Ident) -> DeriveResult<Ident> {
+    for arg in delta_args(attrs) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = arg {
+            if !nv.path.is_ident("delta_name") { continue }
+            return match nv.lit {
+                Lit::Str(lit) => lit.parse::<Ident>()
+                    .map_err(|_| DeriveError::InvalidMarkerValue {
+                        marker: "delta_name"
+                    }),
+                _ => Err(DeriveError::InvalidMarkerValue {
+                    marker: "delta_name"
+                }),
+            };
+        }
+    }
+    Ok(quote::format_ident!("{}Delta", ident))
+}
+
+/// A `field` in the input struct or input enum variant is marked with
+/// #[delta(delta_type = "path::to::D")], where `D: deltoid::FieldDelta<T>`
+/// replaces `<T as deltoid::Core>::Delta` as the delta type of the field.
+pub(crate) fn delta_type(field: &Field) -> DeriveResult<Option<Type>> {
+    for arg in delta_args(&field.attrs) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = arg {
+            if !nv.path.is_ident("delta_type") { continue }
+            return match nv.lit {
+                Lit::Str(lit) => lit.parse::<Type>()
+                    .map(Some)
+                    .map_err(|_| DeriveError::InvalidMarkerValue {
+                        marker: "delta_type"
+                    }),
+                _ => Err(DeriveError::InvalidMarkerValue {
+                    marker: "delta_type"
+                }),
+            };
+        }
+    }
+    Ok(None)
+}
+
+/// Return the arguments that determine the serde representation of an
+/// input enum i.e. `tag = "..."`, `content = "..."` and `untagged`.
+/// If the input enum is marked with #[delta(serde(...))], the arguments
+/// in there are used.  Otherwise they're copied from its #[serde(...)]
+/// attributes, so that the generated delta enum uses the same wire format.
+pub(crate) fn serde_enum_repr(attrs: &[Attribute]) -> Vec<NestedMeta> {
+    let is_repr_arg = |arg: &NestedMeta| match arg {
+        NestedMeta::Meta(Meta::NameValue(nv)) =>
+            nv.path.is_ident("tag") || nv.path.is_ident("content"),
+        NestedMeta::Meta(Meta::Path(path)) => path.is_ident("untagged"),
+        _ => false,
+    };
+    let delta_serde_args: Vec<NestedMeta> = delta_args(attrs).into_iter()
+        .filter_map(|arg| match arg {
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("serde") =>
+                Some(list.nested.into_iter()),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    if !delta_serde_args.is_empty() {
+        return delta_serde_args.into_iter().filter(is_repr_arg).collect();
+    }
+    attrs.iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested.into_iter()),
+            _ => None,
+        })
+        .flatten()
+        .filter(is_repr_arg)
+        .collect()
+}
+
+/// Return the traits that the input type's #[delta(derive(...))] attributes
+/// request to be derived for the generated delta type, e.g. `PartialOrd`.
+/// Since `Ord` requires `Eq`, requesting `Ord` implies `Eq` as well.
+pub(crate) fn delta_derives(attrs: &[Attribute]) -> DeriveResult<Vec<Path>> {
+    let mut derives: Vec<Path> = vec![];
+    for arg in delta_args(attrs) {
+        let list: MetaList = match arg {
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("derive") => list,
+            _ => continue,
         };
-        ignore = ignore || is_delta_attr && arg_is_ignore_field
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) => derives.push(path),
+                _ => return Err(DeriveError::InvalidMarkerValue {
+                    marker: "derive"
+                }),
+            }
+        }
     }
-    ignore
+    let requests = |name: &str| derives.iter().any(|path| path.is_ident(name));
+    if requests("Ord") && !requests("Eq") {
+        derives.insert(0, parse_quote! { Eq });
+    }
+    Ok(derives)
+}
+
+/// The keys that may occur in a `#[delta(...)]` attribute of the input type.
+const CONTAINER_MARKERS: &[&str] = &[
+    "default", "delta_name", "derive", "patch", "serde",
+];
+
+/// The keys that may occur in a `#[delta(...)]` attribute of a field.
+const FIELD_MARKERS: &[&str] = &[
+    "delta_type", "ignore_field", "skip", "sort_keys_by",
+];
+
+/// Ensure that every key in the `#[delta(...)]` attributes of `input`, its
+/// fields and its variants is a known one, so that e.g. a misspelled marker
+/// is reported rather than silently ignored.  Each key must also be placed
+/// where it has an effect: on the input type itself or on a field.  Input
+/// enum variants can't be marked at all.
+pub(crate) fn validate_markers(input: &DeriveInput) -> DeriveResult<()> {
+    // NOTE: Each attribute is paired with the keys that are allowed in it:
+    let mut attrs: Vec<(&Attribute, &[&str])> = input.attrs.iter()
+        .map(|attr| (attr, CONTAINER_MARKERS))
+        .collect();
+    let fields: Vec<&Field> = match &input.data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => {
+            for variant in data.variants.iter() {
+                attrs.extend(variant.attrs.iter().map(|attr| (attr, &[][..])));
+            }
+            data.variants.iter().flat_map(|variant| variant.fields.iter()).collect()
+        },
+        Data::Union(_) => vec![],
+    };
+    attrs.extend(
+        fields.iter()
+            .flat_map(|field| field.attrs.iter())
+            .map(|attr| (attr, FIELD_MARKERS))
+    );
+    for (attr, allowed) in attrs {
+        if !attr.path.is_ident("delta") { continue }
+        let args: Vec<NestedMeta> = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.into_iter().collect(),
+            _ => return Err(DeriveError::UnknownMarker {
+                marker: quote::quote!(#attr).to_string(),
+            }),
+        };
+        for arg in args {
+            let path: &Path = match &arg {
+                NestedMeta::Meta(meta) => meta.path(),
+                NestedMeta::Lit(_) => return Err(DeriveError::UnknownMarker {
+                    marker: quote::quote!(#arg).to_string(),
+                }),
+            };
+            let is_in = |markers: &[&str]| markers.iter().any(|m| path.is_ident(m));
+            if is_in(allowed) { continue }
+            let allowed_on: &'static str = if is_in(CONTAINER_MARKERS) {
+                "the input struct or enum"
+            } else if is_in(FIELD_MARKERS) {
+                "a field"
+            } else {
+                return Err(DeriveError::UnknownMarker {
+                    marker: quote::quote!(#path).to_string(),
+                });
+            };
+            return Err(DeriveError::MisplacedMarker {
+                marker: quote::quote!(#path).to_string(),
+                allowed_on,
+                span: path.span(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns true iff. any of the `#[delta(...)]` attributes
+/// in `attrs` contains the bare word `marker`.
+fn has_marker(attrs: &[Attribute], marker: &str) -> bool {
+    delta_args(attrs).iter().any(|arg| match arg {
+        NestedMeta::Meta(Meta::Path(path)) => path.is_ident(marker),
+        _ => false,
+    })
+}
+
+/// Collect the arguments of all `#[delta(...)]` attributes in `attrs`.
+fn delta_args(attrs: &[Attribute]) -> Vec<NestedMeta> {
+    attrs.iter()
+        .filter(|attr| attr.path.is_ident("delta"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested.into_iter()),
+            _ => None,
+        })
+        .flatten()
+        .collect()
 }
@@ -5,12 +5,13 @@
 pub(crate) mod enums;
 pub(crate) mod markers;
 pub(crate) mod structs;
+pub(crate) mod transparent;
 
 use crate::{DeriveError, DeriveResult};
 use crate::gen::markers::*;
 use proc_macro2::{
     Ident as Ident2, Literal as Literal2, Span as Span2,
-    TokenStream as TokenStream2
+    TokenStream as TokenStream2, TokenTree as TokenTree2
 };
 use syn::*;
 use syn::punctuated::*;
@@ -29,12 +30,22 @@ pub enum InputType {
         delta_type_name: Ident2,
         /// A description of the input enum's variants
         enum_variants: Vec<EnumVariant>,
+        /// Indicates whether the input enum is marked with
+        /// `#[delta(default)]`, i.e. whether to define a `Default`
+        /// impl for the generated delta type that leaves values unchanged
+        default_delta: bool,
+        /// The serde enum representation arguments e.g. `tag = "type"`
+        /// to copy onto the generated delta enum
+        serde_repr: Vec<NestedMeta>,
+        /// The traits to derive for the generated delta enum in addition
+        /// to the default ones, as requested with `#[delta(derive(...))]`
+        delta_derives: Vec<Path>,
         /// The input enum's type parameter declarations,
         /// including any trait bounds e.g. <T: Copy, U, V>
         type_param_decls: Punctuated<GenericParam, Comma>,
-        /// The input enum's type parameters without trait bounds e.g. <T, U, V>
-        type_params: Punctuated<Ident, Comma>,
-        // TODO: defined lifetimes
+        /// The input enum's lifetime, type and const parameters without
+        /// trait bounds or types e.g. <'a, T, U, N>
+        type_params: Punctuated<GenericArgument, Comma>,
         /// The input enum's where clause
         where_clause: WhereClause,
     },
@@ -49,13 +60,27 @@ pub enum InputType {
         delta_type_name: Ident2,
         /// A description of the input struct's fields
         fields: Vec<FieldDesc>,
+        /// Indicates whether the input struct is marked with
+        /// `#[delta(default)]`, i.e. whether to define a
+        /// `Default` impl for the generated delta type
+        default_delta: bool,
+        /// Indicates whether the input struct is marked with
+        /// `#[delta(patch)]`, i.e. whether to define a patch type
+        /// and an `apply_patch()` method for the input struct
+        patch: bool,
+        /// Indicates whether the input struct is a `#[repr(transparent)]`
+        /// newtype, i.e. whether the generated delta struct should wrap
+        /// the delta of its single field directly rather than an `Option`
+        transparent: bool,
+        /// The traits to derive for the generated delta struct in addition
+        /// to the default ones, as requested with `#[delta(derive(...))]`
+        delta_derives: Vec<Path>,
         /// The input struct's type parameter declarations including
         /// any trait bounds e.g. <T: Copy, U, V>
         type_param_decls: Punctuated<GenericParam, Comma>,
-        /// The input struct's type parameters without
-        /// trait bounds e.g. <T, U, V>
-        type_params: Punctuated<Ident, Comma>,
-        // TODO: defined lifetimes
+        /// The input struct's lifetime, type and const parameters without
+        /// trait bounds or types e.g. <'a, T, U, N>
+        type_params: Punctuated<GenericArgument, Comma>,
         /// The input struct's where clause
         where_clause: WhereClause,
     },
@@ -67,6 +92,7 @@ pub enum InputType {
 
 impl InputType {
     pub fn parse(input: &DeriveInput) -> DeriveResult<Self> {
+        validate_markers(input)?;
         match &input.data {
             Data::Struct(DataStruct { fields, .. }) if !fields.is_empty() =>
                 Self::parse_struct(input, fields),
@@ -82,15 +108,18 @@ impl InputType {
         input: &DeriveInput,
         input_fields: &Fields,
     ) -> DeriveResult<Self> {
-        let mut new = Self::new_struct(input);
+        let mut new = Self::new_struct(input)?;
         if let Self::Struct { struct_variant, fields, .. } = &mut new {
             for (fidx, field) in input_fields.iter().enumerate() {
+                ensure_owned_field(field, fidx)?;
                 if let Some(field_ident) = field.ident.as_ref() {
                     *struct_variant = StructVariant::NamedStruct;
                     fields.push(FieldDesc::Named {
                         name: field_ident.clone(),
                         ty: field.ty.clone(),
                         ignore_field: ignore_field(field),
+                        sort_keys_by: sort_keys_by(field)?,
+                        delta_type: delta_type(field)?,
                     });
                 } else {
                     *struct_variant = StructVariant::TupleStruct;
@@ -98,6 +127,8 @@ impl InputType {
                         position: Literal2::usize_unsuffixed(fidx),
                         ty: field.ty.clone(),
                         ignore_field: ignore_field(field),
+                        sort_keys_by: sort_keys_by(field)?,
+                        delta_type: delta_type(field)?,
                     });
                 }
             }
@@ -106,11 +137,19 @@ impl InputType {
                 fields.iter().all(|field| field.is_positional())
             )?;
         }
+        if let Self::Struct { fields, default_delta, transparent, .. } = &mut new {
+            // NOTE: A `#[delta(default)]` delta leaves the field untouched,
+            //       which requires the delta to wrap it in an `Option`:
+            *transparent = repr_transparent(&input.attrs)
+                && fields.len() == 1
+                && !fields[0].ignore_field()
+                && !*default_delta;
+        }
         Ok(new)
     }
 
     fn parse_unit_struct(input: &DeriveInput) -> DeriveResult<Self> {
-        let mut new = Self::new_struct(input);
+        let mut new = Self::new_struct(input)?;
         if let Self::Struct { struct_variant, .. } = &mut new {
             *struct_variant = StructVariant::UnitStruct;
         }
@@ -121,17 +160,20 @@ impl InputType {
         input: &DeriveInput,
         input_enum_variants: &Punctuated<Variant, Comma>,
     ) -> DeriveResult<Self> {
-        let mut new = Self::new_enum(input);
+        let mut new = Self::new_enum(input)?;
         if let Self::Enum { enum_variants, .. } = &mut new {
             for iev in input_enum_variants {
                 let mut variant = EnumVariant::new(&iev.ident);
                 for (fidx, field) in iev.fields.iter().enumerate() {
+                    ensure_owned_field(field, fidx)?;
                     if let Some(field_ident) = field.ident.as_ref() {
                         variant.struct_variant = StructVariant::NamedStruct;
                         variant.add_field(FieldDesc::Named {
                             name: field_ident.clone(),
                             ty: field.ty.clone(),
                             ignore_field: ignore_field(field),
+                            sort_keys_by: sort_keys_by(field)?,
+                            delta_type: delta_type(field)?,
                         });
                     } else {
                         variant.struct_variant = StructVariant::TupleStruct;
@@ -139,6 +181,8 @@ impl InputType {
                             position: Literal2::usize_unsuffixed(fidx),
                             ty: field.ty.clone(),
                             ignore_field: ignore_field(field),
+                            sort_keys_by: sort_keys_by(field)?,
+                            delta_type: delta_type(field)?,
                         });
                     }
                 }
@@ -152,39 +196,45 @@ impl InputType {
         Ok(new)
     }
 
-    fn new_enum(input: &DeriveInput) -> Self {
-        Self::Enum {
+    fn new_enum(input: &DeriveInput) -> DeriveResult<Self> {
+        if patch(&input.attrs) {
+            return Err(DeriveError::UnsupportedMarker { marker: "patch" });
+        }
+        Ok(Self::Enum {
             type_name: input.ident.clone(),
-            delta_type_name: format_ident!("{}Delta", &input.ident),
+            delta_type_name: delta_name(&input.attrs, &input.ident)?,
             enum_variants: vec![],
+            default_delta: default_delta(&input.attrs),
+            serde_repr: serde_enum_repr(&input.attrs),
+            delta_derives: delta_derives(&input.attrs)?,
             type_param_decls: input.generics.params.clone(),
-            type_params: input.generics.type_params()
-                .map(|type_param| type_param.ident.clone())
-                .collect(),
+            type_params: type_params(&input.generics),
             where_clause: input.generics.where_clause.clone()
                 .unwrap_or_else(|| WhereClause {
                     where_token: Token![where](Span2::call_site()),
                     predicates: Punctuated::new(),
                 }),
-        }
+        })
     }
 
-    fn new_struct(input: &DeriveInput) -> Self {
-        Self::Struct {
+    fn new_struct(input: &DeriveInput) -> DeriveResult<Self> {
+        Ok(Self::Struct {
             struct_variant: StructVariant::UnitStruct,
             type_name: input.ident.clone(),
-            delta_type_name: format_ident!("{}Delta", &input.ident),
+            delta_type_name: delta_name(&input.attrs, &input.ident)?,
             fields: vec![],
+            default_delta: default_delta(&input.attrs),
+            patch: patch(&input.attrs),
+            transparent: false,
+            delta_derives: delta_derives(&input.attrs)?,
             type_param_decls: input.generics.params.clone(),
-            type_params: input.generics.type_params()
-                .map(|type_param| type_param.ident.clone())
-                .collect(),
+            type_params: type_params(&input.generics),
             where_clause: input.generics.where_clause.clone()
                 .unwrap_or_else(|| WhereClause {
                     where_token: Token![where](Span2::call_site()),
                     predicates: Punctuated::new(),
                 }),
-        }
+        })
     }
 
     pub fn is_enum(&self) -> bool { matches!(self, Self::Enum { .. }) }
@@ -209,6 +259,39 @@ impl InputType {
         })
     }
 
+    /// Return the name of the extra variant of the delta type of the input
+    /// enum that leaves a value unchanged.  This is `Unchanged`, with as
+    /// many underscores appended as needed to differ from the names of
+    /// the variants of the input enum, so that it never clashes with them.
+    pub fn unchanged_variant_name(&self) -> DeriveResult<Ident2> {
+        let enum_variants: &[EnumVariant] = self.enum_variants()?;
+        let mut name: String = "Unchanged".to_string();
+        while enum_variants.iter().any(|variant| variant.name == name) {
+            name.push('_');
+        }
+        Ok(Ident2::new(&name, Span2::call_site()))
+    }
+
+    /// Return the serde enum representation arguments of the input enum.
+    pub fn serde_repr(&self) -> DeriveResult<&[NestedMeta]> {
+        Ok(match self {
+            Self::Enum { serde_repr, .. } => serde_repr,
+            Self::Struct { type_name, .. } =>
+                panic!("Struct {} doesn't have an enum representation", type_name),
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    /// Return the traits to derive for the generated delta type,
+    /// in addition to the ones that are always derived.
+    pub fn delta_derives(&self) -> DeriveResult<&[Path]> {
+        Ok(match self {
+            Self::Enum   { delta_derives, .. } => delta_derives,
+            Self::Struct { delta_derives, .. } => delta_derives,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
     pub fn type_name(&self) -> DeriveResult<&Ident2> {
         Ok(match self {
             Self::Enum   { type_name, .. } => type_name,
@@ -225,7 +308,7 @@ impl InputType {
         })
     }
 
-    pub fn type_params(&self) -> DeriveResult<&Punctuated<Ident2, Comma>> {
+    pub fn type_params(&self) -> DeriveResult<&Punctuated<GenericArgument, Comma>> {
         Ok(match self {
             Self::Enum   { type_params, .. } => type_params,
             Self::Struct { type_params, .. } => type_params,
@@ -250,6 +333,34 @@ impl InputType {
         })
     }
 
+    /// Returns true iff. the input type is marked with `#[delta(default)]`.
+    pub fn default_delta(&self) -> DeriveResult<bool> {
+        Ok(match self {
+            Self::Enum   { default_delta, .. } => *default_delta,
+            Self::Struct { default_delta, .. } => *default_delta,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    /// Returns true iff. the input struct is marked with `#[delta(patch)]`.
+    pub fn patch(&self) -> DeriveResult<bool> {
+        Ok(match self {
+            Self::Enum   { .. } => false,
+            Self::Struct { patch, .. } => *patch,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    /// Returns true iff. the input struct is a `#[repr(transparent)]`
+    /// newtype with a transparent delta type.
+    pub fn transparent(&self) -> DeriveResult<bool> {
+        Ok(match self {
+            Self::Enum   { .. } => false,
+            Self::Struct { transparent, .. } => *transparent,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
     /// Return the input type's `WhereClause`.
     pub fn where_clause(&self) -> DeriveResult<&WhereClause> {
         Ok(match self {
@@ -261,21 +372,44 @@ impl InputType {
 
     pub fn define_delta_type(&self) -> DeriveResult<TokenStream2> {
         Ok(match self {
+            Self::Struct { transparent: true, .. } =>
+                transparent::define_delta_struct(self)?,
             Self::Struct { .. } => structs::define_delta_struct(self)?,
             Self::Enum   { .. } => enums::define_delta_enum(self)?,
             Self::Union => panic!("Unions are not supported."),
         })
     }
 
+    /// Define the patch type and the `apply_patch()` method of the input
+    /// type if it is marked with `#[delta(patch)]`, and nothing otherwise.
+    pub fn define_patch_type(&self) -> DeriveResult<TokenStream2> {
+        Ok(match self {
+            Self::Struct { patch: true, .. } => structs::define_patch_struct(self)?,
+            Self::Struct { .. } | Self::Enum { .. } => TokenStream2::new(),
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
     #[allow(non_snake_case)]
     pub fn define_Debug_impl(&self) -> DeriveResult<TokenStream2> {
         Ok(match self {
+            Self::Struct { transparent: true, .. } =>
+                transparent::define_Debug_impl(self)?,
             Self::Struct { .. } => structs::define_Debug_impl(self)?,
             Self::Enum   { .. } => enums::define_Debug_impl(self)?,
             Self::Union => panic!("Unions are not supported."),
         })
     }
 
+    #[allow(non_snake_case)]
+    pub fn define_Default_impl(&self) -> DeriveResult<TokenStream2> {
+        Ok(match self {
+            Self::Struct { .. } => structs::define_Default_impl(self)?,
+            Self::Enum   { .. } => enums::define_Default_impl(self)?,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
     #[allow(non_snake_case)]
     pub fn define_Core_impl(&self) -> DeriveResult<TokenStream2> {
         Ok(match self {
@@ -288,6 +422,8 @@ impl InputType {
     #[allow(non_snake_case)]
     pub fn define_Apply_impl(&self) -> DeriveResult<TokenStream2> {
         Ok(match self {
+            Self::Struct { transparent: true, .. } =>
+                transparent::define_Apply_impl(self)?,
             Self::Struct { .. } => structs::define_Apply_impl(self)?,
             Self::Enum   { .. } => enums::define_Apply_impl(self)?,
             Self::Union => panic!("Unions are not supported."),
@@ -297,6 +433,8 @@ impl InputType {
     #[allow(non_snake_case)]
     pub fn define_Delta_impl(&self) -> DeriveResult<TokenStream2> {
         Ok(match self {
+            Self::Struct { transparent: true, .. } =>
+                transparent::define_Delta_impl(self)?,
             Self::Struct { .. } => structs::define_Delta_impl(self)?,
             Self::Enum   { .. } => enums::define_Delta_impl(self)?,
             Self::Union => panic!("Unions are not supported."),
@@ -306,6 +444,8 @@ impl InputType {
     #[allow(non_snake_case)]
     pub fn define_FromDelta_impl(&self) -> DeriveResult<TokenStream2> {
         Ok(match self {
+            Self::Struct { transparent: true, .. } =>
+                transparent::define_FromDelta_impl(self)?,
             Self::Struct { .. } => structs::define_FromDelta_impl(self)?,
             Self::Enum   { .. } => enums::define_FromDelta_impl(self)?,
             Self::Union => panic!("Unions are not supported."),
@@ -315,13 +455,97 @@ impl InputType {
     #[allow(non_snake_case)]
     pub fn define_IntoDelta_impl(&self) -> DeriveResult<TokenStream2> {
         Ok(match self {
+            Self::Struct { transparent: true, .. } =>
+                transparent::define_IntoDelta_impl(self)?,
             Self::Struct { .. } => structs::define_IntoDelta_impl(self)?,
             Self::Enum   { .. } => enums::define_IntoDelta_impl(self)?,
             Self::Union => panic!("Unions are not supported."),
         })
     }
+
+    #[allow(non_snake_case)]
+    pub fn define_DeltaOps_impl(&self) -> DeriveResult<TokenStream2> {
+        Ok(match self {
+            Self::Struct { transparent: true, .. } =>
+                transparent::define_DeltaOps_impl(self)?,
+            Self::Struct { .. } => structs::define_DeltaOps_impl(self)?,
+            Self::Enum   { .. } => enums::define_DeltaOps_impl(self)?,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    #[allow(non_snake_case)]
+    pub fn define_Compose_impl(&self) -> DeriveResult<TokenStream2> {
+        Ok(match self {
+            Self::Struct { transparent: true, .. } =>
+                transparent::define_Compose_impl(self)?,
+            Self::Struct { .. } => structs::define_Compose_impl(self)?,
+            Self::Enum   { .. } => enums::define_Compose_impl(self)?,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    /// Define the `EmptyDelta` impl of the input type if it's a struct,
+    /// and nothing otherwise: a delta to an enum variant changes the
+    /// variant of any value it's applied to, so an enum delta type has
+    /// no identity.
+    #[allow(non_snake_case)]
+    pub fn define_EmptyDelta_impl(&self) -> DeriveResult<TokenStream2> {
+        Ok(match self {
+            Self::Struct { transparent: true, .. } =>
+                transparent::define_EmptyDelta_impl(self)?,
+            Self::Struct { .. } => structs::define_EmptyDelta_impl(self)?,
+            Self::Enum   { .. } => TokenStream2::new(),
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    #[allow(non_snake_case)]
+    pub fn define_Invert_impl(&self) -> DeriveResult<TokenStream2> {
+        Ok(match self {
+            Self::Struct { transparent: true, .. } =>
+                transparent::define_Invert_impl(self)?,
+            Self::Struct { .. } => structs::define_Invert_impl(self)?,
+            Self::Enum   { .. } => enums::define_Invert_impl(self)?,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+}
+
+
+/// Return the lifetime, type and const parameters of `generics` in the
+/// order in which they're declared, i.e. as the generic arguments of the
+/// type they're declared on.
+fn type_params(generics: &Generics) -> Punctuated<GenericArgument, Comma> {
+    // NOTE: A const parameter is passed on as a path just like a type
+    //       parameter, so either is represented as a `Type`:
+    let path_arg = |ident: &Ident| GenericArgument::Type(Type::Path(TypePath {
+        qself: None,
+        path: Path::from(ident.clone()),
+    }));
+    generics.params.iter()
+        .map(|generic_param| match generic_param {
+            GenericParam::Lifetime(lifetime_def) =>
+                GenericArgument::Lifetime(lifetime_def.lifetime.clone()),
+            GenericParam::Type(type_param)   => path_arg(&type_param.ident),
+            GenericParam::Const(const_param) => path_arg(&const_param.ident),
+        })
+        .collect()
 }
 
+/// Ensure that `field` owns its value unless it's ignored, since there's
+/// no delta type for a reference.
+fn ensure_owned_field(field: &Field, fidx: usize) -> DeriveResult<()> {
+    if ignore_field(field) { return Ok(()) }
+    match &field.ty {
+        Type::Reference(_) => Err(DeriveError::BorrowedField {
+            field: field.ident.as_ref()
+                .map(|field_ident| field_ident.to_string())
+                .unwrap_or_else(|| fidx.to_string()),
+        }),
+        _ => Ok(()),
+    }
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum StructVariant {
@@ -372,12 +596,16 @@ pub enum FieldDesc {
         name: Ident2,
         ty: Type,
         ignore_field: bool,
+        sort_keys_by: Option<Path>,
+        delta_type: Option<Type>,
     },
     /// A field that's part of a tuple struct
     Positional {
         position: Literal2,
         ty: Type,
         ignore_field: bool,
+        sort_keys_by: Option<Path>,
+        delta_type: Option<Type>,
     }
 }
 
@@ -411,7 +639,8 @@ impl FieldDesc {
         }
     }
 
-    /// Returns true iff. the field was marked with `#[delta(ignore_field)]`.
+    /// Returns true iff. the field was marked with `#[delta(ignore_field)]`
+    /// or `#[delta(skip)]`.
     pub fn ignore_field(&self) -> bool {
         match self {
             Self::Named      { ignore_field, .. } => *ignore_field,
@@ -419,13 +648,197 @@ impl FieldDesc {
         }
     }
 
+    /// Returns the comparator `path` iff. the field was marked
+    /// with `#[delta(sort_keys_by = "path")]`.
+    pub fn sort_keys_by(&self) -> Option<&Path> {
+        match self {
+            Self::Named      { sort_keys_by, .. } => sort_keys_by.as_ref(),
+            Self::Positional { sort_keys_by, .. } => sort_keys_by.as_ref(),
+        }
+    }
+
+    /// Returns the type `D` iff. the field was marked
+    /// with `#[delta(delta_type = "D")]`.
+    pub fn custom_delta_type(&self) -> Option<&Type> {
+        match self {
+            Self::Named      { delta_type, .. } => delta_type.as_ref(),
+            Self::Positional { delta_type, .. } => delta_type.as_ref(),
+        }
+    }
+
+    /// Return the tokens that calculate the delta between the values
+    /// `lhs` and `rhs` of the field described by `self`.
+    pub fn delta_tokens(
+        &self,
+        lhs: TokenStream2,
+        rhs: TokenStream2,
+    ) -> TokenStream2 {
+        let ty: &Type = self.type_ref();
+        if let Some(custom) = self.custom_delta_type() {
+            return quote! {
+                <#custom as deltoid::FieldDelta<#ty>>::delta(&#lhs, #rhs)?
+            };
+        }
+        if let Some(inner) = self.boxed_option_inner() {
+            return quote! {
+                deltoid::OptionBoxDelta::<#inner>::delta(&#lhs, #rhs)?
+            };
+        }
+        match self.sort_keys_by() {
+            None => quote! { #lhs.delta(#rhs)? },
+            Some(cmp) => quote! {{
+                let mut delta = #lhs.delta(#rhs)?;
+                delta.sort_keys_by(#cmp);
+                delta
+            }},
+        }
+    }
+
+    /// Return the tokens that apply `delta` to the value `lhs` of
+    /// the field described by `self`.
+    pub fn apply_tokens(
+        &self,
+        lhs: TokenStream2,
+        delta: TokenStream2,
+    ) -> TokenStream2 {
+        let ty: &Type = self.type_ref();
+        if let Some(custom) = self.custom_delta_type() {
+            return quote! {
+                <#custom as deltoid::FieldDelta<#ty>>::apply(&#lhs, #delta)?
+            };
+        }
+        match self.boxed_option_inner() {
+            None => quote! { #lhs.apply(#delta)? },
+            Some(inner) => quote! {
+                deltoid::OptionBoxDelta::<#inner>::apply(&#lhs, #delta)?
+            },
+        }
+    }
+
+    /// Return the tokens that apply `delta` in place to the value
+    /// `lhs` of the field described by `self`.
+    pub fn apply_mut_tokens(
+        &self,
+        lhs: TokenStream2,
+        delta: TokenStream2,
+    ) -> TokenStream2 {
+        let ty: &Type = self.type_ref();
+        if let Some(custom) = self.custom_delta_type() {
+            return quote! {
+                #lhs = <#custom as deltoid::FieldDelta<#ty>>::apply(&#lhs, #delta)?;
+            };
+        }
+        match self.boxed_option_inner() {
+            None => quote! { deltoid::Apply::apply_mut(&mut #lhs, #delta)?; },
+            Some(inner) => quote! {
+                #lhs = deltoid::OptionBoxDelta::<#inner>::apply(&#lhs, #delta)?;
+            },
+        }
+    }
+
+    /// Return the tokens that convert `delta` to a value of
+    /// the field described by `self`.
+    pub fn field_from_delta_tokens(&self, delta: TokenStream2) -> TokenStream2 {
+        let ty: &Type = self.type_ref();
+        if let Some(custom) = self.custom_delta_type() {
+            return quote! {
+                <#custom as deltoid::FieldDelta<#ty>>::from_delta(#delta)?
+            };
+        }
+        match self.boxed_option_inner() {
+            None => quote! { <#ty>::from_delta(#delta)? },
+            Some(inner) => quote! {
+                deltoid::OptionBoxDelta::<#inner>::from_delta(#delta)?
+            },
+        }
+    }
+
+    /// Return the tokens that convert the `value` of the field
+    /// described by `self` to a delta.
+    pub fn field_into_delta_tokens(&self, value: TokenStream2) -> TokenStream2 {
+        let ty: &Type = self.type_ref();
+        if let Some(custom) = self.custom_delta_type() {
+            return quote! {
+                <#custom as deltoid::FieldDelta<#ty>>::into_delta(#value)?
+            };
+        }
+        match self.boxed_option_inner() {
+            None => quote! { #value.into_delta()? },
+            Some(inner) => quote! {
+                deltoid::OptionBoxDelta::<#inner>::into_delta(#value)?
+            },
+        }
+    }
+
+    /// Returns `T` iff. the type of `self` is syntactically `Option<Box<T>>`.
+    /// The delta of such a field is flattened to an `OptionBoxDelta<T>`,
+    /// rather than an `OptionDelta<Box<T>>`.
+    pub fn boxed_option_inner(&self) -> Option<&Type> {
+        fn generic_arg<'t>(ty: &'t Type, name: &str) -> Option<&'t Type> {
+            let path: &TypePath = match ty {
+                Type::Path(path) if path.qself.is_none() => path,
+                _ => return None,
+            };
+            let segment: &PathSegment = path.path.segments.last()?;
+            if segment.ident != name { return None }
+            match &segment.arguments {
+                PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                    match args.args.first()? {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    }
+                },
+                _ => None,
+            }
+        }
+        let boxed: &Type = generic_arg(self.type_ref(), "Option")?;
+        generic_arg(boxed, "Box")
+    }
+
     /// Return the tokens for the type of `self`.
     pub fn type_tokens(&self) -> TokenStream2 {
         let ty: &Type = self.type_ref();
         if self.ignore_field() {
             quote! { std::marker::PhantomData<#ty> }
         } else {
-            quote! { Option<<#ty as deltoid::Core>::Delta> }
+            let delta_type: TokenStream2 = self.delta_type_tokens();
+            quote! { Option<#delta_type> }
+        }
+    }
+
+    /// Return the tokens for the delta type of the field described by
+    /// `self`, i.e. its type in the delta type without the `Option<_>`.
+    pub fn delta_type_tokens(&self) -> TokenStream2 {
+        let ty: &Type = self.type_ref();
+        if let Some(custom) = self.custom_delta_type() {
+            return quote! { #custom };
         }
+        match self.boxed_option_inner() {
+            None => quote! { <#ty as deltoid::Core>::Delta },
+            Some(inner) => quote! { deltoid::OptionBoxDelta<#inner> },
+        }
+    }
+
+    /// Return the bound that the derived `Compose` impl places on the delta
+    /// type of the field described by `self`, or `None` if the type of the
+    /// field mentions the input type `type_name` itself.  The deltas of such
+    /// a recursive field are composed by way of the impl being derived, so
+    /// they need no bound.
+    pub fn compose_bound(&self, type_name: &Ident2) -> Option<TokenStream2> {
+        fn mentions(tokens: TokenStream2, type_name: &Ident2) -> bool {
+            tokens.into_iter().any(|token| match token {
+                TokenTree2::Ident(ident) => ident == *type_name || ident == "Self",
+                TokenTree2::Group(group) => mentions(group.stream(), type_name),
+                _ => false,
+            })
+        }
+        let ty: &Type = self.type_ref();
+        if self.custom_delta_type().is_none() && mentions(quote! { #ty }, type_name) {
+            return None;
+        }
+        let delta_type: TokenStream2 = self.delta_type_tokens();
+        Some(quote! {
+            for<'__a> &'__a #delta_type: deltoid::ComposeRef<#delta_type>
+        })
     }
 }
@@ -3,7 +3,7 @@
 
 use crate::DeriveResult;
 use crate::gen::{FieldDesc, InputType, StructVariant};
-use proc_macro2::{Ident as Ident2, TokenStream as TokenStream2};
+use proc_macro2::{Ident as Ident2, Literal as Literal2, TokenStream as TokenStream2};
 use quote::{format_ident, quote};
 use syn::*;
 use syn::punctuated::Punctuated;
@@ -38,6 +38,16 @@ pub(crate) fn define_delta_struct(input: &InputType) -> DeriveResult<TokenStream
         .map(|field: &FieldDesc| field.type_tokens())
         .collect();
     let where_clause = quote! { where };
+    let delta_derives: Vec<&Path> = input.delta_derives()?.iter()
+        // NOTE: A unit delta struct derives `Eq` and `Hash` regardless:
+        .filter(|path| *struct_variant != StructVariant::UnitStruct
+                || !path.is_ident("Eq") && !path.is_ident("Hash"))
+        .collect();
+    let derive_attr: TokenStream2 = if delta_derives.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[derive( #(#delta_derives),* )] }
+    };
     match struct_variant {
         StructVariant::NamedStruct => {
             let field_names: Vec<&Ident2> = fields.iter()
@@ -45,6 +55,7 @@ pub(crate) fn define_delta_struct(input: &InputType) -> DeriveResult<TokenStream
                 .collect::<DeriveResult<_>>()?;
             Ok(quote! {
                 #[derive(Clone, PartialEq)]
+                #derive_attr
                 #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
                 pub struct #delta_type_name<#(#type_param_decls),*>
                     #where_clause
@@ -57,6 +68,7 @@ pub(crate) fn define_delta_struct(input: &InputType) -> DeriveResult<TokenStream
         },
         StructVariant::TupleStruct => Ok(quote! {
             #[derive(Clone, PartialEq)]
+            #derive_attr
             #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
             pub struct #delta_type_name<#(#type_param_decls),*> (
                 #( #[doc(hidden)] pub(self) #field_types, )*
@@ -66,6 +78,7 @@ pub(crate) fn define_delta_struct(input: &InputType) -> DeriveResult<TokenStream
         }),
         StructVariant::UnitStruct => Ok(quote! {
             #[derive(Clone, PartialEq, Eq, Hash)]
+            #derive_attr
                 #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
             pub struct #delta_type_name<#(#type_param_decls),*>
                 #where_clause ;
@@ -73,6 +86,108 @@ pub(crate) fn define_delta_struct(input: &InputType) -> DeriveResult<TokenStream
     }
 }
 
+pub(crate) fn define_patch_struct(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let struct_variant: &StructVariant = input.struct_variant()?;
+    let type_name: &Ident2 = input.type_name()?;
+    let patch_type_name: Ident2 = format_ident!("{}Patch", type_name);
+    // NOTE: Ignored fields can't be patched, so the patch type omits them:
+    let fields: Vec<&FieldDesc> = input.fields()?.iter()
+        .filter(|field| !field.ignore_field())
+        .collect();
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
+        .map(|type_param_decl| quote! { #type_param_decl })
+        .collect();
+    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .collect();
+    let where_clause = quote! { where #(#predicates),* };
+    let field_types: Vec<&Type> = fields.iter()
+        .map(|field| field.type_ref())
+        .collect();
+    let (definition, default_value, assignments) = match struct_variant {
+        StructVariant::NamedStruct => {
+            let field_names: Vec<&Ident2> = fields.iter()
+                .map(|field| field.name_ref())
+                .collect::<DeriveResult<_>>()?;
+            (
+                quote! {
+                    pub struct #patch_type_name<#(#type_param_decls),*>
+                        #where_clause
+                    {
+                        #( pub #field_names: Option<#field_types>, )*
+                    }
+                },
+                quote! { Self { #( #field_names: None, )* } },
+                quote! {
+                    #( if let Some(value) = patch.#field_names {
+                        self.#field_names = value;
+                    } )*
+                },
+            )
+        },
+        StructVariant::TupleStruct => {
+            let field_positions: Vec<&Literal2> = fields.iter()
+                .map(|field| field.pos_ref())
+                .collect::<DeriveResult<_>>()?;
+            let patch_positions: Vec<Literal2> = (0 .. fields.len())
+                .map(Literal2::usize_unsuffixed)
+                .collect();
+            let nones = fields.iter().map(|_| quote! { None });
+            (
+                quote! {
+                    pub struct #patch_type_name<#(#type_param_decls),*> (
+                        #( pub Option<#field_types>, )*
+                    ) #where_clause ;
+                },
+                quote! { Self( #(#nones),* ) },
+                quote! {
+                    #( if let Some(value) = patch.#patch_positions {
+                        self.#field_positions = value;
+                    } )*
+                },
+            )
+        },
+        StructVariant::UnitStruct => (
+            quote! { pub struct #patch_type_name; },
+            quote! { Self },
+            quote! {},
+        ),
+    };
+    let patch_type_params = match struct_variant {
+        StructVariant::UnitStruct => quote! {},
+        _ => quote! { <#type_params> },
+    };
+    Ok(quote! {
+        /// A hand-authorable patch for the input type,
+        /// in which each field holds an optional new value.
+        #[derive(Clone, Debug, PartialEq)]
+        #definition
+
+        impl<#(#type_param_decls),*> std::default::Default
+            for #patch_type_name #patch_type_params
+            #where_clause
+        {
+            fn default() -> Self { #default_value }
+        }
+
+        impl<#(#type_param_decls),*> #type_name<#type_params>
+            #where_clause
+        {
+            /// Overwrite each field of `self` for which `patch`
+            /// holds a new value, leaving the other fields as they are.
+            #[allow(unused)]
+            pub fn apply_patch(&mut self, patch: #patch_type_name #patch_type_params) {
+                #assignments
+            }
+        }
+    })
+}
+
 pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2> {
     if !input.is_struct() { return bug_detected!() }
     let struct_variant: &StructVariant = input.struct_variant()?;
@@ -80,7 +195,7 @@ pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2>
     let fields: &[FieldDesc] = input.fields()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -222,6 +337,68 @@ pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2>
     }
 }
 
+pub(crate) fn define_Default_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    if !input.default_delta()? { return Ok(TokenStream2::new()) }
+    let struct_variant: &StructVariant = input.struct_variant()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let fields: &[FieldDesc] = input.fields()?;
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => {
+                let T: &Ident2 = &type_param.ident;
+                // NOTE: `bounds` defines trait bounds on the corresponding
+                // type parameter `T` in `InputType::Struct#type_param`:
+                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
+                    .map(|trait_bound| quote! { #trait_bound })
+                    .collect();
+                quote! {
+                    #T: deltoid::Core
+                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
+                }
+            },
+        })
+        .collect();
+    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .collect();
+    let where_clause = quote! { where #(#predicates),* };
+    // NOTE: The default delta leaves every field untouched when applied:
+    let field_values: Vec<TokenStream2> = fields.iter()
+        .map(|field: &FieldDesc| if field.ignore_field() {
+            quote! { std::marker::PhantomData }
+        } else {
+            quote! { None }
+        })
+        .collect();
+    let body: TokenStream2 = match struct_variant {
+        StructVariant::NamedStruct => {
+            let field_names: Vec<&Ident2> = fields.iter()
+                .map(|field: &FieldDesc| field.name_ref())
+                .collect::<DeriveResult<_>>()?;
+            quote! { Self { #( #field_names: #field_values, )* } }
+        },
+        StructVariant::TupleStruct => quote! { Self( #( #field_values, )* ) },
+        StructVariant::UnitStruct => quote! { Self },
+    };
+    Ok(quote! {
+        impl<#(#type_param_decls),*> std::default::Default
+            for #delta_type_name<#type_params>
+            #where_clause
+        {
+            fn default() -> Self {
+                #body
+            }
+        }
+    })
+}
+
 pub(crate) fn define_Core_impl(input: &InputType) -> DeriveResult<TokenStream2> {
     if !input.is_struct() { return bug_detected!() }
     let struct_variant: &StructVariant = input.struct_variant()?;
@@ -229,7 +406,7 @@ pub(crate) fn define_Core_impl(input: &InputType) -> DeriveResult<TokenStream2>
     let delta_type_name: &Ident2 = input.delta_type_name()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -293,7 +470,7 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
     let fields: &[FieldDesc] = input.fields()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -332,9 +509,13 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                             #fname: self.#fname.clone(),
                         }
                     } else {
+                        let value = field.apply_tokens(
+                            quote! { self.#fname },
+                            quote! { d },
+                        );
                         quote! {
                             #fname: if let Some(d) = delta.#fname {
-                                self.#fname.apply(d)?
+                                #value
                             } else {
                                 self.#fname.clone()
                             },
@@ -342,6 +523,19 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
+            let field_updates: Vec<TokenStream2> = fields.iter()
+                .filter(|field: &&FieldDesc| !field.ignore_field())
+                .map(|field: &FieldDesc| {
+                    let fname = field.name_ref()?;
+                    let update = field.apply_mut_tokens(
+                        quote! { self.#fname },
+                        quote! { d },
+                    );
+                    Ok(quote! {
+                        if let Some(d) = delta.#fname { #update }
+                    })
+                })
+                .collect::<DeriveResult<_>>()?;
             Ok(quote! {
                 impl<#(#type_param_decls),*> deltoid::Apply
                     for #type_name<#type_params>
@@ -353,6 +547,14 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     {
                         Ok(Self { #(#field_assignments)* })
                     }
+
+                    #[allow(unused)]
+                    fn apply_mut(&mut self, delta: Self::Delta)
+                                 -> deltoid::DeltaResult<()>
+                    {
+                        #(#field_updates)*
+                        Ok(())
+                    }
                 }
             })
         },
@@ -365,9 +567,13 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                             self.#fpos.clone(),
                         }
                     } else {
+                        let value = field.apply_tokens(
+                            quote! { self.#fpos },
+                            quote! { d },
+                        );
                         quote! {
                             if let Some(d) = delta.#fpos {
-                                self.#fpos.apply(d)?
+                                #value
                             } else {
                                 self.#fpos.clone()
                             },
@@ -375,6 +581,19 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
+            let field_updates: Vec<TokenStream2> = fields.iter()
+                .filter(|field: &&FieldDesc| !field.ignore_field())
+                .map(|field: &FieldDesc| {
+                    let fpos = field.pos_ref()?;
+                    let update = field.apply_mut_tokens(
+                        quote! { self.#fpos },
+                        quote! { d },
+                    );
+                    Ok(quote! {
+                        if let Some(d) = delta.#fpos { #update }
+                    })
+                })
+                .collect::<DeriveResult<_>>()?;
             Ok(quote! {
                 impl<#(#type_param_decls),*> deltoid::Apply
                     for #type_name<#type_params>
@@ -386,6 +605,14 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     {
                         Ok(Self( #(#field_assignments)* ))
                     }
+
+                    #[allow(unused)]
+                    fn apply_mut(&mut self, delta: Self::Delta)
+                                 -> deltoid::DeltaResult<()>
+                    {
+                        #(#field_updates)*
+                        Ok(())
+                    }
                 }
             })
         },
@@ -415,7 +642,7 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
     let fields: &[FieldDesc] = input.fields()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -453,9 +680,13 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     Ok(if field.ignore_field() {
                         quote! { #fname: std::marker::PhantomData }
                     } else {
+                        let delta = field.delta_tokens(
+                            quote! { self.#fname },
+                            quote! { &rhs.#fname },
+                        );
                         quote! {
                             #fname: if self.#fname != rhs.#fname {
-                                Some(self.#fname.delta(&rhs.#fname)?)
+                                Some(#delta)
                             } else {
                                 None
                             }
@@ -482,9 +713,13 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     Ok(if field.ignore_field() {
                         quote! { std::marker::PhantomData }
                     } else {
+                        let delta = field.delta_tokens(
+                            quote! { self.#fpos },
+                            quote! { &rhs.#fpos },
+                        );
                         quote! {
                             if self.#fpos != rhs.#fpos {
-                                Some(self.#fpos.delta(&rhs.#fpos)?)
+                                Some(#delta)
                             } else {
                                 None
                             }
@@ -526,7 +761,7 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
     let fields: &[FieldDesc] = input.fields()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -568,16 +803,15 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                     Ok(if field.ignore_field() {
                         quote! { #fname: Default::default() }
                     } else {
-                        quote! {
-                            #fname: <#ftype>::from_delta(
-                                #fname.ok_or_else(|| DeltaError::ExpectedValue {
-                                    type_name: stringify!(#ftype).to_string(),
-                                    file: file!().to_string(),
-                                    line: line!(),
-                                    column: column!(),
-                                })?
-                            )?
-                        }
+                        let value = field.field_from_delta_tokens(quote! {
+                            #fname.ok_or_else(|| DeltaError::ExpectedValue {
+                                type_name: stringify!(#ftype).to_string(),
+                                file: file!().to_string(),
+                                line: line!(),
+                                column: column!(),
+                            })?
+                        });
+                        quote! { #fname: #value }
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
@@ -603,16 +837,14 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                     Ok(if field.ignore_field() {
                         quote! { Default::default() }
                     } else {
-                        quote! {
-                            <#ftype>::from_delta(
-                                #fname.ok_or_else(|| DeltaError::ExpectedValue {
-                                    type_name: stringify!(#ftype).to_string(),
-                                    file: file!().to_string(),
-                                    line: line!(),
-                                    column: column!(),
-                                })?
-                            )?
-                        }
+                        field.field_from_delta_tokens(quote! {
+                            #fname.ok_or_else(|| DeltaError::ExpectedValue {
+                                type_name: stringify!(#ftype).to_string(),
+                                file: file!().to_string(),
+                                line: line!(),
+                                column: column!(),
+                            })?
+                        })
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
@@ -650,7 +882,7 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
     let fields: &[FieldDesc] = input.fields()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
     let in_where_clause: &WhereClause = input.where_clause()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
@@ -692,7 +924,8 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                     Ok(if field.ignore_field() {
                         quote! { #fname: std::marker::PhantomData }
                     } else {
-                        quote! { #fname: Some(#fname.into_delta()?) }
+                        let value = field.field_into_delta_tokens(quote! { #fname });
+                        quote! { #fname: Some(#value) }
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
@@ -714,7 +947,8 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                     Ok(if field.ignore_field() {
                         quote! { std::marker::PhantomData }
                     } else {
-                        quote! { Some(#fname.into_delta()?) }
+                        let value = field.field_into_delta_tokens(quote! { #fname });
+                        quote! { Some(#value) }
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
@@ -743,3 +977,315 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
         }
     })
 }
+
+pub(crate) fn define_DeltaOps_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let struct_variant: &StructVariant = input.struct_variant()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let fields: &[FieldDesc] = input.fields()?;
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => {
+                let T: &Ident2 = &type_param.ident;
+                // NOTE: `bounds` defines trait bounds on the corresponding
+                // type parameter `T` in `InputType::Struct#type_param`:
+                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
+                    .map(|trait_bound| quote! { #trait_bound })
+                    .collect();
+                quote! {
+                    #T: deltoid::Core
+                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
+                }
+            },
+        })
+        .collect();
+    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .collect();
+    let where_clause = quote! { where #(#predicates),* };
+    // NOTE: The delta of a field is `None` iff. the field is unchanged,
+    //       while ignored fields are never changed by applying a delta:
+    let members: Vec<TokenStream2> = fields.iter()
+        .filter(|field: &&FieldDesc| !field.ignore_field())
+        .map(|field: &FieldDesc| Ok(match struct_variant {
+            StructVariant::NamedStruct => {
+                let fname = field.name_ref()?;
+                quote! { #fname }
+            },
+            StructVariant::TupleStruct => {
+                let fpos = field.pos_ref()?;
+                quote! { #fpos }
+            },
+            StructVariant::UnitStruct => return bug_detected!(),
+        }))
+        .collect::<DeriveResult<_>>()?;
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::DeltaOps
+            for #delta_type_name<#type_params>
+            #where_clause
+        {
+            fn is_empty(&self) -> bool {
+                true #( && self.#members.is_none() )*
+            }
+        }
+    })
+}
+
+pub(crate) fn define_Compose_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let struct_variant: &StructVariant = input.struct_variant()?;
+    let type_name: &Ident2 = input.type_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let fields: &[FieldDesc] = input.fields()?;
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => {
+                let T: &Ident2 = &type_param.ident;
+                // NOTE: `bounds` defines trait bounds on the corresponding
+                // type parameter `T` in `InputType::Struct#type_param`:
+                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
+                    .map(|trait_bound| quote! { #trait_bound })
+                    .collect();
+                quote! {
+                    #T: deltoid::Core
+                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
+                }
+            },
+        })
+        .collect();
+    // NOTE: The delta type only implements `Compose` if the delta types
+    //       of all its fields do.  Those bounds are higher-ranked so that
+    //       they aren't rejected as trivially false for non-generic types,
+    //       and see `FieldDesc::compose_bound()` for their form:
+    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .chain(fields.iter()
+            .filter(|field: &&FieldDesc| !field.ignore_field())
+            .filter_map(|field: &FieldDesc| field.compose_bound(type_name)))
+        .collect();
+    let where_clause = quote! { where #(#predicates),* };
+    let field_values = |members: Vec<TokenStream2>| -> Vec<TokenStream2> {
+        fields.iter().zip(members)
+            .map(|(field, member): (&FieldDesc, TokenStream2)| if field.ignore_field() {
+                quote! { std::marker::PhantomData }
+            } else {
+                quote! {
+                    match (&self.#member, &next.#member) {
+                        (lhs, None) => lhs.clone(),
+                        (None, Some(rhs)) => Some(rhs.clone()),
+                        (Some(lhs), Some(rhs)) =>
+                            Some(deltoid::ComposeRef::compose_ref(lhs, rhs)?),
+                    }
+                }
+            })
+            .collect()
+    };
+    let body: TokenStream2 = match struct_variant {
+        StructVariant::NamedStruct => {
+            let field_names: Vec<&Ident2> = fields.iter()
+                .map(|field: &FieldDesc| field.name_ref())
+                .collect::<DeriveResult<_>>()?;
+            let field_values: Vec<TokenStream2> = field_values(
+                field_names.iter().map(|fname| quote! { #fname }).collect()
+            );
+            quote! { #delta_type_name { #( #field_names: #field_values, )* } }
+        },
+        StructVariant::TupleStruct => {
+            let field_positions: Vec<&Literal2> = fields.iter()
+                .map(|field: &FieldDesc| field.pos_ref())
+                .collect::<DeriveResult<_>>()?;
+            let field_values: Vec<TokenStream2> = field_values(
+                field_positions.iter().map(|fpos| quote! { #fpos }).collect()
+            );
+            quote! { #delta_type_name( #(#field_values),* ) }
+        },
+        StructVariant::UnitStruct => quote! { #delta_type_name },
+    };
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Compose
+            for #delta_type_name<#type_params>
+            #where_clause
+        {
+            #[allow(unused)]
+            fn compose(&self, next: &Self) -> deltoid::DeltaResult<Self> {
+                Ok(#body)
+            }
+        }
+    })
+}
+
+pub(crate) fn define_EmptyDelta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let struct_variant: &StructVariant = input.struct_variant()?;
+    let type_name: &Ident2 = input.type_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let fields: &[FieldDesc] = input.fields()?;
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => {
+                let T: &Ident2 = &type_param.ident;
+                // NOTE: `bounds` defines trait bounds on the corresponding
+                // type parameter `T` in `InputType::Struct#type_param`:
+                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
+                    .map(|trait_bound| quote! { #trait_bound })
+                    .collect();
+                quote! {
+                    #T: std::clone::Clone
+                    + std::fmt::Debug
+                        + std::cmp::PartialEq
+                        + deltoid::Core
+                        + for<'de> serde::Deserialize<'de>
+                        + serde::Serialize
+                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
+                }
+            },
+        })
+        .collect();
+    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .collect();
+    let where_clause = quote! { where #(#predicates),* };
+    // NOTE: An unchanged field has a `None` delta:
+    let field_values: Vec<TokenStream2> = fields.iter()
+        .map(|field: &FieldDesc| if field.ignore_field() {
+            quote! { std::marker::PhantomData }
+        } else {
+            quote! { None }
+        })
+        .collect();
+    let body: TokenStream2 = match struct_variant {
+        StructVariant::NamedStruct => {
+            let field_names: Vec<&Ident2> = fields.iter()
+                .map(|field: &FieldDesc| field.name_ref())
+                .collect::<DeriveResult<_>>()?;
+            quote! { #delta_type_name { #( #field_names: #field_values, )* } }
+        },
+        StructVariant::TupleStruct =>
+            quote! { #delta_type_name( #(#field_values),* ) },
+        StructVariant::UnitStruct => quote! { #delta_type_name },
+    };
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::EmptyDelta
+            for #type_name<#type_params>
+            #where_clause
+        {
+            fn identity_delta() -> Self::Delta {
+                #body
+            }
+        }
+    })
+}
+
+pub(crate) fn define_Invert_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let struct_variant: &StructVariant = input.struct_variant()?;
+    let type_name: &Ident2 = input.type_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let fields: &[FieldDesc] = input.fields()?;
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => {
+                let T: &Ident2 = &type_param.ident;
+                // NOTE: `bounds` defines trait bounds on the corresponding
+                // type parameter `T` in `InputType::Struct#type_param`:
+                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
+                    .map(|trait_bound| quote! { #trait_bound })
+                    .collect();
+                quote! {
+                    #T: deltoid::Core
+                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
+                }
+            },
+        })
+        .collect();
+    // NOTE: The delta type only implements `Invert` if the delta types
+    //       of all its fields do.  As with `Compose`, those bounds are
+    //       higher-ranked:
+    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .chain(fields.iter()
+            .filter(|field: &&FieldDesc| !field.ignore_field())
+            .map(|field: &FieldDesc| {
+                let field_type: &Type = field.type_ref();
+                let delta_type: TokenStream2 = field.delta_type_tokens();
+                quote! { for<'__a> #delta_type: deltoid::Invert<#field_type> }
+            }))
+        .collect();
+    let where_clause = quote! { where #(#predicates),* };
+    // NOTE: An unchanged field remains unchanged, and a changed field is
+    //       changed back based on its value in `base`:
+    let field_values = |members: Vec<TokenStream2>| -> Vec<TokenStream2> {
+        fields.iter().zip(members)
+            .map(|(field, member): (&FieldDesc, TokenStream2)| if field.ignore_field() {
+                quote! { std::marker::PhantomData }
+            } else {
+                quote! {
+                    match &self.#member {
+                        None => None,
+                        Some(delta) =>
+                            Some(deltoid::Invert::invert(delta, &base.#member)?),
+                    }
+                }
+            })
+            .collect()
+    };
+    let body: TokenStream2 = match struct_variant {
+        StructVariant::NamedStruct => {
+            let field_names: Vec<&Ident2> = fields.iter()
+                .map(|field: &FieldDesc| field.name_ref())
+                .collect::<DeriveResult<_>>()?;
+            let field_values: Vec<TokenStream2> = field_values(
+                field_names.iter().map(|fname| quote! { #fname }).collect()
+            );
+            quote! { #delta_type_name { #( #field_names: #field_values, )* } }
+        },
+        StructVariant::TupleStruct => {
+            let field_positions: Vec<&Literal2> = fields.iter()
+                .map(|field: &FieldDesc| field.pos_ref())
+                .collect::<DeriveResult<_>>()?;
+            let field_values: Vec<TokenStream2> = field_values(
+                field_positions.iter().map(|fpos| quote! { #fpos }).collect()
+            );
+            quote! { #delta_type_name( #(#field_values),* ) }
+        },
+        StructVariant::UnitStruct => quote! { #delta_type_name },
+    };
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Invert<#type_name<#type_params>>
+            for #delta_type_name<#type_params>
+            #where_clause
+        {
+            #[allow(unused)]
+            fn invert(
+                &self,
+                base: &#type_name<#type_params>,
+            ) -> deltoid::DeltaResult<Self> {
+                Ok(#body)
+            }
+        }
+    })
+}
@@ -0,0 +1,381 @@
+//! Code generation for `#[repr(transparent)]` newtype structs, whose
+//! delta type wraps the delta of their single field without an `Option`,
+//! making it layout- and serialization-identical to that delta.
+#![allow(non_snake_case)]
+
+use crate::DeriveResult;
+use crate::gen::{FieldDesc, InputType, StructVariant};
+use proc_macro2::{Ident as Ident2, TokenStream as TokenStream2};
+use quote::quote;
+use syn::*;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+
+/// Return the type parameter declarations of `input`, with `bounds`
+/// added to the user-specified bounds of each type parameter.
+fn type_param_decls(
+    input: &InputType,
+    bounds: TokenStream2,
+) -> DeriveResult<Vec<TokenStream2>> {
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    Ok(in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => {
+                let T: &Ident2 = &type_param.ident;
+                let user_bounds: Vec<TokenStream2> = type_param.bounds.iter()
+                    .map(|trait_bound| quote! { #trait_bound })
+                    .collect();
+                quote! {
+                    #T: #bounds
+                        #(+ #user_bounds)* // Copy user-specified type/lifetime bounds
+                }
+            },
+        })
+        .collect())
+}
+
+/// Return the where clause of `input`.
+fn where_clause(input: &InputType) -> DeriveResult<TokenStream2> {
+    let predicates: Vec<TokenStream2> = input.where_clause()?.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .collect();
+    Ok(quote! { where #(#predicates),* })
+}
+
+/// Return the single field of `input`, and the tokens to access it with.
+/// Since e.g. `Self { 0: value }` is valid for tuple structs as well,
+/// the same tokens can be used to construct a value of either kind.
+fn single_field(input: &InputType) -> DeriveResult<(&FieldDesc, TokenStream2)> {
+    let fields: &[FieldDesc] = input.fields()?;
+    ensure!(fields.len() == 1)?;
+    let field: &FieldDesc = &fields[0];
+    let member: TokenStream2 = match input.struct_variant()? {
+        StructVariant::NamedStruct => { let name = field.name_ref()?; quote! { #name } },
+        StructVariant::TupleStruct => { let pos = field.pos_ref()?; quote! { #pos } },
+        StructVariant::UnitStruct => return bug_detected!(),
+    };
+    Ok((field, member))
+}
+
+pub(crate) fn define_delta_struct(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let struct_variant: &StructVariant = input.struct_variant()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let (field, _) = single_field(input)?;
+    let field_type: TokenStream2 = field.delta_type_tokens();
+    let type_param_decls = type_param_decls(input, quote! { deltoid::Core })?;
+    let delta_derives: &[Path] = input.delta_derives()?;
+    let derive_attr: TokenStream2 = if delta_derives.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[derive( #(#delta_derives),* )] }
+    };
+    let attrs = quote! {
+        #[derive(Clone, PartialEq)]
+        #derive_attr
+        #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+        #[serde(transparent)]
+        #[repr(transparent)]
+    };
+    match struct_variant {
+        StructVariant::NamedStruct => {
+            let field_name: &Ident2 = field.name_ref()?;
+            Ok(quote! {
+                #attrs
+                pub struct #delta_type_name<#(#type_param_decls),*> where {
+                    #[doc(hidden)] pub(self) #field_name: #field_type,
+                }
+            })
+        },
+        StructVariant::TupleStruct => Ok(quote! {
+            #attrs
+            pub struct #delta_type_name<#(#type_param_decls),*> (
+                #[doc(hidden)] pub(self) #field_type,
+            ) where ;
+        }),
+        StructVariant::UnitStruct => bug_detected!(),
+    }
+}
+
+pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let (_, member) = single_field(input)?;
+    let type_param_decls = type_param_decls(input, quote! {
+        deltoid::Core + std::fmt::Debug
+    })?;
+    let where_clause: TokenStream2 = where_clause(input)?;
+    Ok(quote! {
+        impl<#(#type_param_decls),*> std::fmt::Debug
+            for #delta_type_name<#type_params>
+            #where_clause
+        {
+            fn fmt(&self, f: &mut std::fmt::Formatter)
+                   -> Result<(), std::fmt::Error>
+            {
+                const NAME: &str = stringify!(#delta_type_name);
+                write!(f, "{}({:?})", NAME, &self.#member)
+            }
+        }
+    })
+}
+
+pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let type_name: &Ident2 = input.type_name()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let (field, member) = single_field(input)?;
+    let type_param_decls = type_param_decls(input, quote! {
+        std::clone::Clone
+            + std::fmt::Debug
+            + std::cmp::PartialEq
+            + deltoid::Apply
+            + for<'de> serde::Deserialize<'de>
+            + serde::Serialize
+    })?;
+    let where_clause: TokenStream2 = where_clause(input)?;
+    let value = field.apply_tokens(
+        quote! { self.#member },
+        quote! { delta.#member },
+    );
+    let apply_mut = field.apply_mut_tokens(
+        quote! { self.#member },
+        quote! { delta.#member },
+    );
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Apply
+            for #type_name<#type_params>
+            #where_clause
+        {
+            fn apply(&self, delta: Self::Delta)
+                     -> deltoid::DeltaResult<Self>
+            {
+                #[allow(unused)] use deltoid::Apply;
+                Ok(Self { #member: #value })
+            }
+
+            fn apply_mut(&mut self, delta: Self::Delta)
+                         -> deltoid::DeltaResult<()>
+            {
+                #apply_mut
+                Ok(())
+            }
+        }
+    })
+}
+
+pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let type_name: &Ident2 = input.type_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let (field, member) = single_field(input)?;
+    let type_param_decls = type_param_decls(input, quote! {
+        std::clone::Clone
+            + std::fmt::Debug
+            + std::cmp::PartialEq
+            + deltoid::Delta
+            + for<'de> serde::Deserialize<'de>
+            + serde::Serialize
+    })?;
+    let where_clause: TokenStream2 = where_clause(input)?;
+    let delta = field.delta_tokens(
+        quote! { self.#member },
+        quote! { &rhs.#member },
+    );
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Delta
+            for #type_name<#type_params>
+            #where_clause
+        {
+            fn delta(&self, rhs: &Self) -> deltoid::DeltaResult<Self::Delta> {
+                #[allow(unused)] use deltoid::Delta;
+                Ok(#delta_type_name { #member: #delta })
+            }
+        }
+    })
+}
+
+pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let type_name: &Ident2 = input.type_name()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let (field, member) = single_field(input)?;
+    let type_param_decls = type_param_decls(input, quote! {
+        std::clone::Clone
+            + std::fmt::Debug
+            + std::cmp::PartialEq
+            + deltoid::FromDelta
+            + for<'de> serde::Deserialize<'de>
+            + serde::Serialize
+    })?;
+    let where_clause: TokenStream2 = where_clause(input)?;
+    let value = field.field_from_delta_tokens(quote! { delta.#member });
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::FromDelta
+            for #type_name<#type_params>
+            #where_clause
+        {
+            fn from_delta(delta: Self::Delta) -> deltoid::DeltaResult<Self> {
+                #[allow(unused)] use deltoid::FromDelta;
+                Ok(Self { #member: #value })
+            }
+        }
+    })
+}
+
+pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let type_name: &Ident2 = input.type_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let (field, member) = single_field(input)?;
+    let type_param_decls = type_param_decls(input, quote! {
+        std::clone::Clone
+            + std::fmt::Debug
+            + std::cmp::PartialEq
+            + deltoid::IntoDelta
+            + for<'de> serde::Deserialize<'de>
+            + serde::Serialize
+    })?;
+    let where_clause: TokenStream2 = where_clause(input)?;
+    let value = field.field_into_delta_tokens(quote! { self.#member });
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::IntoDelta
+            for #type_name<#type_params>
+            #where_clause
+        {
+            fn into_delta(self) -> deltoid::DeltaResult<Self::Delta> {
+                #[allow(unused)] use deltoid::IntoDelta;
+                Ok(#delta_type_name { #member: #value })
+            }
+        }
+    })
+}
+
+pub(crate) fn define_DeltaOps_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let (field, member) = single_field(input)?;
+    let field_type: TokenStream2 = field.delta_type_tokens();
+    let type_param_decls = type_param_decls(input, quote! { deltoid::Core })?;
+    let predicates: Vec<TokenStream2> = input.where_clause()?.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .collect();
+    // NOTE: The delta type only implements `DeltaOps` if the delta type
+    //       of its single field does.  The bound is higher-ranked so that
+    //       it isn't rejected as trivially false for non-generic types:
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::DeltaOps
+            for #delta_type_name<#type_params>
+            where #(#predicates,)* for<'__a> #field_type: deltoid::DeltaOps
+        {
+            fn is_empty(&self) -> bool {
+                deltoid::DeltaOps::is_empty(&self.#member)
+            }
+        }
+    })
+}
+
+pub(crate) fn define_Compose_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let type_name: &Ident2 = input.type_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let (field, member) = single_field(input)?;
+    let type_param_decls = type_param_decls(input, quote! { deltoid::Core })?;
+    // NOTE: As with `DeltaOps`, the bound on the delta type of the field
+    //       is higher-ranked, and see `FieldDesc::compose_bound()` for its
+    //       form:
+    let predicates: Vec<TokenStream2> = input.where_clause()?.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .chain(field.compose_bound(type_name))
+        .collect();
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Compose
+            for #delta_type_name<#type_params>
+            where #(#predicates),*
+        {
+            fn compose(&self, next: &Self) -> deltoid::DeltaResult<Self> {
+                Ok(Self {
+                    #member: deltoid::ComposeRef::compose_ref(&self.#member, &next.#member)?,
+                })
+            }
+        }
+    })
+}
+
+pub(crate) fn define_EmptyDelta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let type_name: &Ident2 = input.type_name()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let (field, member) = single_field(input)?;
+    // NOTE: The delta of an `Option<Box<T>>` field, or of a field with a
+    //       custom delta type, isn't the delta type of the field type, so
+    //       it has no identity delta to forward to:
+    if field.boxed_option_inner().is_some() || field.custom_delta_type().is_some() {
+        return Ok(TokenStream2::new())
+    }
+    let field_type: &Type = field.type_ref();
+    let type_param_decls = type_param_decls(input, quote! {
+        std::clone::Clone
+            + std::fmt::Debug
+            + std::cmp::PartialEq
+            + deltoid::Core
+            + for<'de> serde::Deserialize<'de>
+            + serde::Serialize
+    })?;
+    let predicates: Vec<TokenStream2> = input.where_clause()?.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .collect();
+    // NOTE: As with `DeltaOps`, the bound on the field type is higher-ranked:
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::EmptyDelta
+            for #type_name<#type_params>
+            where #(#predicates,)* for<'__a> #field_type: deltoid::EmptyDelta
+        {
+            fn identity_delta() -> Self::Delta {
+                Self::Delta {
+                    #member: <#field_type as deltoid::EmptyDelta>::identity_delta(),
+                }
+            }
+        }
+    })
+}
+
+pub(crate) fn define_Invert_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let type_name: &Ident2 = input.type_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let type_params: &Punctuated<GenericArgument, Comma> = input.type_params()?;
+    let (field, member) = single_field(input)?;
+    let field_type: &Type = field.type_ref();
+    let field_delta_type: TokenStream2 = field.delta_type_tokens();
+    let type_param_decls = type_param_decls(input, quote! { deltoid::Core })?;
+    let predicates: Vec<TokenStream2> = input.where_clause()?.predicates.iter()
+        .map(|where_predicate| quote! { #where_predicate })
+        .collect();
+    // NOTE: As with `Compose`, the bound on the delta type of the field
+    //       is higher-ranked:
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Invert<#type_name<#type_params>>
+            for #delta_type_name<#type_params>
+            where #(#predicates,)*
+                for<'__a> #field_delta_type: deltoid::Invert<#field_type>
+        {
+            fn invert(
+                &self,
+                base: &#type_name<#type_params>,
+            ) -> deltoid::DeltaResult<Self> {
+                Ok(Self {
+                    #member: deltoid::Invert::invert(&self.#member, &base.#member)?,
+                })
+            }
+        }
+    })
+}
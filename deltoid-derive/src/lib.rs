@@ -22,10 +22,8 @@ use syn::{parse_macro_input, DeriveInput};
 #[proc_macro_derive(Delta, attributes(delta))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let output: TokenStream2 = derive_internal(input).unwrap(
-        // This is a HACK that allows more ergonomic code for the meat of
-        // the macro while still conforming to the required macro signature.
-    );
+    let output: TokenStream2 = derive_internal(input)
+        .unwrap_or_else(|err| err.to_compile_error());
     TokenStream::from(output)
 }
 
@@ -34,30 +32,48 @@ fn derive_internal(input: DeriveInput) -> DeriveResult<TokenStream2> {
     let input_type: InputType = InputType::parse(&input)?;
     let delta_type_definition = input_type.define_delta_type()?;
     let impl_Debug            = input_type.define_Debug_impl()?;
+    let impl_Default          = input_type.define_Default_impl()?;
     let impl_Core             = input_type.define_Core_impl()?;
     let impl_Apply            = input_type.define_Apply_impl()?;
     let impl_Delta            = input_type.define_Delta_impl()?;
     let impl_FromDelta        = input_type.define_FromDelta_impl()?;
     let impl_IntoDelta        = input_type.define_IntoDelta_impl()?;
+    let impl_DeltaOps         = input_type.define_DeltaOps_impl()?;
+    let impl_Compose          = input_type.define_Compose_impl()?;
+    let impl_EmptyDelta       = input_type.define_EmptyDelta_impl()?;
+    let impl_Invert           = input_type.define_Invert_impl()?;
+    let patch_type_definition = input_type.define_patch_type()?;
     let output: TokenStream2 = quote! {
         #delta_type_definition
         #impl_Debug
+        #impl_Default
         #impl_Core
         #impl_Apply
         #impl_Delta
         #impl_FromDelta
         #impl_IntoDelta
+        #impl_DeltaOps
+        #impl_Compose
+        #impl_EmptyDelta
+        #impl_Invert
+        #patch_type_definition
     };
 
     #[cfg(feature = "print-expansions--unstable")]
     print_generated_code(
         &delta_type_definition,
         &impl_Debug,
+        &impl_Default,
         &impl_Core,
         &impl_Apply,
         &impl_Delta,
         &impl_FromDelta,
         &impl_IntoDelta,
+        &impl_DeltaOps,
+        &impl_Compose,
+        &impl_EmptyDelta,
+        &impl_Invert,
+        &patch_type_definition,
     );
 
     #[cfg(feature = "dump-expansions--unstable")]
@@ -65,11 +81,17 @@ fn derive_internal(input: DeriveInput) -> DeriveResult<TokenStream2> {
         input_type.type_name()?,
         &delta_type_definition,
         &impl_Debug,
+        &impl_Default,
         &impl_Core,
         &impl_Apply,
         &impl_Delta,
         &impl_FromDelta,
         &impl_IntoDelta,
+        &impl_DeltaOps,
+        &impl_Compose,
+        &impl_EmptyDelta,
+        &impl_Invert,
+        &patch_type_definition,
     );
 
     Ok(output)
@@ -80,19 +102,31 @@ fn derive_internal(input: DeriveInput) -> DeriveResult<TokenStream2> {
 fn print_generated_code(
     delta_type_definition: &TokenStream2,
     impl_Debug: &TokenStream2,
+    impl_Default: &TokenStream2,
     impl_Core: &TokenStream2,
     impl_Apply: &TokenStream2,
     impl_Delta: &TokenStream2,
     impl_FromDelta: &TokenStream2,
     impl_IntoDelta: &TokenStream2,
+    impl_DeltaOps: &TokenStream2,
+    impl_Compose: &TokenStream2,
+    impl_EmptyDelta: &TokenStream2,
+    impl_Invert: &TokenStream2,
+    patch_type_definition: &TokenStream2,
 ) {
     println!("{}\n", delta_type_definition);
     println!("{}\n", impl_Debug);
+    println!("{}\n", impl_Default);
     println!("{}\n", impl_Core);
     println!("{}\n", impl_Apply);
     println!("{}\n", impl_Delta);
     println!("{}\n", impl_FromDelta);
     println!("{}\n", impl_IntoDelta);
+    println!("{}\n", impl_DeltaOps);
+    println!("{}\n", impl_Compose);
+    println!("{}\n", impl_EmptyDelta);
+    println!("{}\n", impl_Invert);
+    println!("{}\n", patch_type_definition);
     println!("\n\n\n\n");
 }
 
@@ -102,11 +136,17 @@ fn write_generated_code_to_file(
     type_name: &Ident2,
     delta_type_definition: &TokenStream2,
     impl_Debug: &TokenStream2,
+    impl_Default: &TokenStream2,
     impl_Core: &TokenStream2,
     impl_Apply: &TokenStream2,
     impl_Delta: &TokenStream2,
     impl_FromDelta: &TokenStream2,
     impl_IntoDelta: &TokenStream2,
+    impl_DeltaOps: &TokenStream2,
+    impl_Compose: &TokenStream2,
+    impl_EmptyDelta: &TokenStream2,
+    impl_Invert: &TokenStream2,
+    patch_type_definition: &TokenStream2,
 ) {
     let manifest_dir: &Path = Path::new(env!("CARGO_MANIFEST_DIR"));
     let expanded_dir: PathBuf = manifest_dir.join("expanded");
@@ -125,6 +165,10 @@ fn write_generated_code_to_file(
         .expect("Failed to write impl_Debug");
     file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
 
+    file.write_all(format!("{}", impl_Default).as_bytes())
+        .expect("Failed to write impl_Default");
+    file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
+
     file.write_all(format!("{}", impl_Core).as_bytes())
         .expect("Failed to write impl_Core");
     file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
@@ -145,6 +189,26 @@ fn write_generated_code_to_file(
         .expect("Failed to write impl_IntoDelta");
     file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
 
+    file.write_all(format!("{}", impl_DeltaOps).as_bytes())
+        .expect("Failed to write impl_DeltaOps");
+    file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
+
+    file.write_all(format!("{}", impl_Compose).as_bytes())
+        .expect("Failed to write impl_Compose");
+    file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
+
+    file.write_all(format!("{}", impl_EmptyDelta).as_bytes())
+        .expect("Failed to write impl_EmptyDelta");
+    file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
+
+    file.write_all(format!("{}", impl_Invert).as_bytes())
+        .expect("Failed to write impl_Invert");
+    file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
+
+    file.write_all(format!("{}", patch_type_definition).as_bytes())
+        .expect("Failed to write patch_type_definition");
+    file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
+
     file.flush().expect(&format!("Failed to flush {}", filename.display()));
     std::process::Command::new("rustfmt")
         .args(&[
@@ -174,3 +238,122 @@ fn open_file<P: AsRef<Path>>(path: P) -> File {
         .open(path)
         .expect(&format!("Failed to open {}", path.display()))
 }
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn derive__unknown_marker() {
+        let input: DeriveInput = parse_quote! {
+            struct Foo {
+                #[delta(ignor_field)]
+                bar: u8,
+            }
+        };
+        let err: DeriveError = derive_internal(input).unwrap_err();
+        let diagnostic: String = err.to_compile_error().to_string();
+        assert!(
+            diagnostic.contains("unknown #[delta(...)] attribute key `ignor_field`"),
+            "{}", diagnostic
+        );
+        let input: DeriveInput = parse_quote! {
+            #[delta(default, patch)]
+            struct Foo {
+                #[delta(ignore_field)]
+                bar: u8,
+            }
+        };
+        assert!(derive_internal(input).is_ok());
+        // NOTE: Known markers are rejected where they have no effect:
+        let misplaced: Vec<(DeriveInput, &str)> = vec![
+            (parse_quote! {
+                struct Foo {
+                    #[delta(patch)]
+                    bar: u8,
+                }
+            }, "#[delta(patch)] can only be used on the input struct or enum"),
+            (parse_quote! {
+                #[delta(skip)]
+                struct Foo(u8);
+            }, "#[delta(skip)] can only be used on a field"),
+            (parse_quote! {
+                enum Foo {
+                    #[delta(default)]
+                    Bar,
+                }
+            }, "#[delta(default)] can only be used on the input struct or enum"),
+            (parse_quote! {
+                enum Foo {
+                    Bar(#[delta(delta_name = "Baz")] u8),
+                }
+            }, "#[delta(delta_name)] can only be used on the input struct or enum"),
+        ];
+        for (input, expected) in misplaced {
+            let err: DeriveError = derive_internal(input).unwrap_err();
+            assert!(matches!(err, DeriveError::MisplacedMarker { .. }), "{:?}", err);
+            let diagnostic: String = err.to_compile_error().to_string();
+            assert!(diagnostic.contains(expected), "{}", diagnostic);
+        }
+    }
+
+    #[test]
+    fn derive__delta_name() {
+        for delta_name in ["", "1Foo", "Foo Delta", "fn"] {
+            let input: DeriveInput = parse_quote! {
+                #[delta(delta_name = #delta_name)]
+                struct Foo(u8);
+            };
+            let err: DeriveError = derive_internal(input).unwrap_err();
+            assert!(
+                matches!(err, DeriveError::InvalidMarkerValue { marker: "delta_name" }),
+                "{:?}", err
+            );
+        }
+        let input: DeriveInput = parse_quote! {
+            #[delta(delta_name = 42)]
+            enum Foo { Bar }
+        };
+        assert!(derive_internal(input).is_err());
+    }
+
+    #[test]
+    fn derive__borrowed_field() {
+        let input: DeriveInput = parse_quote! {
+            struct Ref<'a, T> {
+                name: &'a str,
+                value: T,
+            }
+        };
+        let err: DeriveError = derive_internal(input).unwrap_err();
+        let diagnostic: String = err.to_compile_error().to_string();
+        assert!(diagnostic.contains("field `name` borrows its value"), "{}", diagnostic);
+        let input: DeriveInput = parse_quote! {
+            struct Ref<'a, T> {
+                #[delta(ignore_field)]
+                name: &'a str,
+                value: T,
+            }
+        };
+        assert!(derive_internal(input).is_ok());
+    }
+
+    #[test]
+    fn derive__unchanged_variant_name() {
+        let input: DeriveInput = parse_quote! {
+            enum Status { Changed(u8), Unchanged, Unchanged_ }
+        };
+        let tokens: String = derive_internal(input).unwrap().to_string();
+        // NOTE: The delta type's own variant doesn't clash with either:
+        assert!(tokens.contains("Unchanged__ ,"), "{}", tokens);
+        let input: DeriveInput = parse_quote! {
+            enum Status { Changed(u8) }
+        };
+        let tokens: String = derive_internal(input).unwrap().to_string();
+        assert!(tokens.contains("Unchanged ,"), "{}", tokens);
+    }
+}
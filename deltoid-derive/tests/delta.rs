@@ -2,11 +2,13 @@
 #![allow(non_snake_case)]
 
 #[allow(unused)] use deltoid::{
-    Core, Apply, Delta, DeltaResult, FromDelta, IntoDelta,
-    BoolDelta, StringDelta, U8Delta, UnitDelta,
+    Core, Apply, Compose, Delta, DeltaError, DeltaOps, DeltaResult, EmptyDelta,
+    FieldDelta, FromDelta, IntoDelta, Invert,
+    BoolDelta, HashMapDelta, StringDelta, StringOp, U8Delta, UnitDelta,
 };
 use deltoid_derive::Delta;
 use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 
@@ -55,6 +57,233 @@ pub struct Baz;
 #[derive(Clone, Debug, PartialEq, Default, Delta, Deserialize, Serialize)]
 pub struct Plow(std::borrow::Cow<'static, String>);
 
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(default)]
+pub struct Waldo<T> {
+    #[delta(ignore_field)]
+    w0: u8,
+    w1: T,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Grunt {
+    #[delta(skip)]
+    cache: Vec<u8>,
+    name: String,
+    count: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum Shape {
+    Circle { radius: u8 },
+    Rect { width: u8, height: u8 },
+    Empty,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub enum Status {
+    Changed(u8),
+    Unchanged,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(serde(tag = "kind", content = "data"))]
+pub enum Token {
+    Word { text: String },
+    Eof,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Thud {
+    #[delta(sort_keys_by = "reverse_cmp")]
+    entries: HashMap<String, u8>,
+}
+
+fn reverse_cmp(lhs: &String, rhs: &String) -> std::cmp::Ordering {
+    rhs.cmp(lhs)
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Garply {
+    value: u8,
+    next: Option<Box<Garply>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Delta, Deserialize, Serialize)]
+pub struct Cell {
+    row: u8,
+    col: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(derive(PartialOrd, Ord))]
+pub struct Wibble {
+    version: u32,
+    label: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(default)]
+pub enum Fred<T> {
+    Plugh(T),
+    Xyzzy { x0: u8, x1: T },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Delta, Deserialize, Serialize)]
+pub struct Reading {
+    value: u32,
+    timestamp: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[repr(transparent)]
+pub struct Meters(Vec<u32>);
+
+#[derive(Clone, Debug, PartialEq, Delta)]
+pub struct Buffer<const N: usize> {
+    data: [u8; N],
+    len: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta)]
+pub enum Frame<const N: usize> {
+    Data([u8; N]),
+    Ack,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Sink {
+    boxed: Box<u8>,
+    option: Option<u8>,
+    result: Result<u8, String>,
+    tuple: (u8, String),
+    array: [u8; 3],
+    range: std::ops::Range<u8>,
+    duration: std::time::Duration,
+    wrapping: std::num::Wrapping<u32>,
+    path: std::path::PathBuf,
+}
+
+fn sink(n: u8) -> Sink {
+    Sink {
+        boxed: Box::new(n),
+        option: if n == 0 { None } else { Some(n) },
+        result: if n == 1 { Err(n.to_string()) } else { Ok(n) },
+        tuple: (n, "foo".repeat(n as usize)),
+        array: [n, 1, n],
+        range: n .. 10,
+        duration: std::time::Duration::new(n as u64, n as u32),
+        wrapping: std::num::Wrapping(u32::MAX - n as u32),
+        path: format!("/tmp/{}", n).into(),
+    }
+}
+
+/// A hand-written delta for a `u32` counter, which holds the offset
+/// from the old to the new count rather than the new count.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct CounterOffset(i64);
+
+impl FieldDelta<u32> for CounterOffset {
+    fn delta(lhs: &u32, rhs: &u32) -> DeltaResult<Self> {
+        Ok(CounterOffset(*rhs as i64 - *lhs as i64))
+    }
+
+    fn apply(base: &u32, delta: Self) -> DeltaResult<u32> {
+        use std::convert::TryFrom;
+        u32::try_from(*base as i64 + delta.0)
+            .map_err(|_| DeltaError::Overflow("u32".to_string()))
+    }
+
+    fn from_delta(delta: Self) -> DeltaResult<u32> {
+        Self::apply(&0, delta)
+    }
+
+    fn into_delta(value: u32) -> DeltaResult<Self> {
+        Self::delta(&0, &value)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Hits {
+    #[delta(delta_type = "CounterOffset")]
+    count: u32,
+    page: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub enum Stat {
+    Count(#[delta(delta_type = "crate::CounterOffset")] u32),
+    Unknown,
+}
+
+mod settings {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+    #[delta(delta_name = "SettingsConfigDelta")]
+    pub struct Config {
+        pub verbose: bool,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+    #[delta(delta_name = "SettingsModeDelta")]
+    pub enum Mode {
+        Fast,
+        Slow { factor: u8 },
+    }
+}
+
+// NOTE: Without a `delta_name` this would generate
+//       a second `ConfigDelta` in the same module:
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Config {
+    name: String,
+}
+
+pub use settings::{SettingsConfigDelta, SettingsModeDelta};
+
+#[derive(Clone, Debug, PartialEq, Delta)]
+pub struct Labeled<'a, T> {
+    value: T,
+    #[delta(ignore_field)]
+    label: &'a str,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta)]
+pub struct Tagged<'a, T>(T, std::marker::PhantomData<&'a ()>);
+
+#[derive(Clone, Debug, PartialEq, Delta)]
+pub enum Borrowed<'a, T> {
+    Value(T),
+    Label {
+        #[delta(ignore_field)]
+        label: &'a str,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Lap {
+    number: u32,
+    time: std::time::Duration,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Ticket {
+    id: std::num::NonZeroU32,
+    title: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(patch)]
+pub struct Plover<T> {
+    name: String,
+    value: T,
+    #[delta(ignore_field)]
+    cache: std::marker::PhantomData<T>,
+}
+
 
 
 
@@ -136,7 +365,7 @@ pub fn enum_tuple_struct_variant__delta__different_values() -> DeltaResult<()> {
     let delta = val0.delta(&val1)?;
     let expected = Qux2Delta::Floof(
         std::marker::PhantomData,
-        Some(StringDelta(Some("bar".into())))
+        Some(StringDelta::Replace("bar".into()))
     );
     assert_eq!(delta, expected, "{:#?} != {:#?}", delta, expected);
     Ok(())
@@ -147,7 +376,7 @@ pub fn enum_tuple_struct_variant__apply__different_values() -> DeltaResult<()> {
     let val0: Qux2<String, ()> = Qux2::Floof(42, String::from("foo"));
     let delta = Qux2Delta::Floof(
         std::marker::PhantomData,
-        Some(StringDelta(Some("bar".into())))
+        Some(StringDelta::Replace("bar".into()))
     );
     let val1 = val0.apply(delta)?;
     let expected: Qux2<String, ()> = Qux2::Floof(42, String::from("bar"));
@@ -163,6 +392,10 @@ pub fn enum_unit_struct_variant__delta() -> DeltaResult<()> {
     let val0: Qux2<String, ()> = Qux2::Gah;
     let val1: Qux2<String, ()> = Qux2::Gah;
     let delta = val0.delta(&val1)?;
+    let expected = Qux2Delta::Unchanged;
+    assert_eq!(delta, expected, "{:#?} != {:#?}", delta, expected);
+    let val2: Qux2<String, ()> = Qux2::Blah { one: 42u8, two: () };
+    let delta = val2.delta(&val1)?;
     let expected = Qux2Delta::Gah;
     assert_eq!(delta, expected, "{:#?} != {:#?}", delta, expected);
     Ok(())
@@ -197,7 +430,10 @@ pub fn struct__delta__same_values() -> DeltaResult<()> {
     let expected: Foo0Delta<u16> = Foo0Delta {
         f0: std::marker::PhantomData,
         f1: Some(300u16.into_delta()?),
-        f2: Some("hello world!!!".to_string().into_delta()?),
+        f2: Some(StringDelta::Edit(vec![
+            StringOp::Retain(11),
+            StringOp::Insert("!!!".into()),
+        ])),
     };
     assert_eq!(delta, expected, "{:#?} != {:#?}", delta, expected);
     Ok(())
@@ -241,7 +477,12 @@ pub fn struct__delta__different_values() -> DeltaResult<()> {
     let expected: Foo0Delta<u16> = Foo0Delta {
         f0: std::marker::PhantomData,
         f1: Some(300u16.into_delta()?),
-        f2: Some("hai world".to_string().into_delta()?),
+        f2: Some(StringDelta::Edit(vec![
+            StringOp::Retain(1),
+            StringOp::Delete(4),
+            StringOp::Insert("ai".into()),
+            StringOp::Retain(6),
+        ])),
     };
     assert_eq!(delta, expected, "{:#?} != {:#?}", delta, expected);
     Ok(())
@@ -392,3 +633,634 @@ pub fn nested_data__apply() -> DeltaResult<()>  {
     assert_eq!(val1, expected, "{:#?} != {:#?}", val1, expected);
     Ok(())
 }
+
+
+
+#[test]
+pub fn struct__default_delta__apply() -> DeltaResult<()> {
+    let val0: Waldo<String> = Waldo { w0: 42u8, w1: String::from("foo") };
+    let delta: WaldoDelta<String> = WaldoDelta::default();
+    let expected = WaldoDelta { w0: std::marker::PhantomData, w1: None };
+    assert_eq!(delta, expected, "{:#?} != {:#?}", delta, expected);
+    let val1 = val0.apply(delta)?;
+    assert_eq!(val1, val0, "{:#?} != {:#?}", val1, val0);
+    Ok(())
+}
+
+#[test]
+pub fn enum__default_delta__apply() -> DeltaResult<()> {
+    let val0: Fred<String> = Fred::Xyzzy { x0: 42u8, x1: String::from("foo") };
+    let delta: FredDelta<String> = FredDelta::default();
+    let expected = FredDelta::Unchanged;
+    assert_eq!(delta, expected, "{:#?} != {:#?}", delta, expected);
+    assert!(delta.is_empty());
+    let val1 = val0.apply(delta.clone())?;
+    assert_eq!(val1, val0, "{:#?} != {:#?}", val1, val0);
+    let val0: Fred<String> = Fred::Plugh(String::from("foo"));
+    let val1 = val0.apply(delta)?;
+    assert_eq!(val1, val0, "{:#?} != {:#?}", val1, val0);
+    Ok(())
+}
+
+#[test]
+pub fn struct__sort_keys_by__deterministic_output() -> DeltaResult<()> {
+    let thud = |pairs: &[(&str, u8)]| Thud {
+        entries: pairs.iter().map(|&(k, v)| (k.to_string(), v)).collect(),
+    };
+    let serialized: Vec<String> = (0 .. 10)
+        .map(|_| {
+            let val0 = thud(&[("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+            let val1 = thud(&[("a", 0), ("c", 3), ("d", 5), ("e", 6), ("f", 7)]);
+            let delta = val0.delta(&val1)?;
+            Ok(serde_json::to_string(&delta).expect("Failed to serialize"))
+        })
+        .collect::<DeltaResult<_>>()?;
+    let expected = concat!(
+        r#"{"entries":["#,
+        r#"{"Add":{"key":"f","value":7}},"#,
+        r#"{"Add":{"key":"e","value":6}},"#,
+        r#"{"Edit":{"key":"d","value":5}},"#,
+        r#"{"Remove":{"key":"b"}},"#,
+        r#"{"Edit":{"key":"a","value":0}}"#,
+        r#"]}"#,
+    );
+    assert!(serialized.iter().all(|json| json == expected), "{:#?}", serialized);
+    Ok(())
+}
+
+#[test]
+pub fn struct__skip_field__apply() -> DeltaResult<()> {
+    let val0 = Grunt { cache: vec![1, 2, 3], name: "foo".into(), count: 1 };
+    let val1 = Grunt { cache: vec![4], name: "bar".into(), count: 2 };
+    let delta = val0.delta(&val1)?;
+    let expected = GruntDelta {
+        cache: std::marker::PhantomData,
+        name: Some("bar".to_string().into_delta()?),
+        count: Some(U8Delta(Some(2))),
+    };
+    assert_eq!(delta, expected, "{:#?} != {:#?}", delta, expected);
+    let val2 = val0.apply(delta)?;
+    let expected = Grunt { cache: vec![1, 2, 3], name: "bar".into(), count: 2 };
+    assert_eq!(val2, expected, "{:#?} != {:#?}", val2, expected);
+    Ok(())
+}
+
+#[test]
+pub fn struct__delta__postcard_round_trip() -> DeltaResult<()> {
+    use deltoid::PostcardCodec;
+    let val0 = Grunt { cache: vec![1, 2, 3], name: "foo".into(), count: 1 };
+    let val1 = Grunt { cache: vec![], name: "foo".into(), count: 2 };
+    let delta: GruntDelta = val0.delta(&val1)?;
+    let bytes: Vec<u8> = delta.to_postcard()?;
+    let decoded = GruntDelta::from_postcard(&bytes)?;
+    assert_eq!(decoded, delta, "{:#?} != {:#?}", decoded, delta);
+    assert_eq!(val0.apply(decoded)?, Grunt { count: 2, ..val0.clone() });
+    Ok(())
+}
+
+#[test]
+pub fn enum__delta__serde_tag() -> DeltaResult<()> {
+    let val0 = Shape::Rect { width: 1, height: 2 };
+    let val1 = Shape::Rect { width: 3, height: 2 };
+    let delta = val0.delta(&val1)?;
+    let json = serde_json::to_string(&delta).expect("Failed to serialize");
+    assert_eq!(json, r#"{"type":"Rect","width":3,"height":null}"#);
+    let decoded: ShapeDelta = serde_json::from_str(&json).expect("Failed to deserialize");
+    assert_eq!(decoded, delta, "{:#?} != {:#?}", decoded, delta);
+
+    let delta = val0.delta(&Shape::Empty)?;
+    let json = serde_json::to_string(&delta).expect("Failed to serialize");
+    assert_eq!(json, r#"{"type":"Empty"}"#);
+
+    let delta = Token::Eof.delta(&Token::Word { text: "foo".into() })?;
+    let json = serde_json::to_string(&delta).expect("Failed to serialize");
+    assert_eq!(json, r#"{"kind":"Word","data":{"text":"foo"}}"#);
+    Ok(())
+}
+
+
+#[test]
+pub fn struct__delta__option_box_field() -> DeltaResult<()> {
+    let val0 = Garply { value: 1, next: None };
+    let val1 = Garply {
+        value: 1,
+        next: Some(Box::new(Garply { value: 2, next: None })),
+    };
+    let delta = val0.delta(&val1)?;
+    let json = serde_json::to_string(&delta).expect("Failed to serialize");
+    assert_eq!(json, r#"{"value":null,"next":{"Some":{"value":2,"next":"None"}}}"#);
+    let decoded: GarplyDelta = serde_json::from_str(&json).expect("Failed to deserialize");
+    assert_eq!(decoded, delta, "{:#?} != {:#?}", decoded, delta);
+    assert_eq!(val0.apply(delta)?, val1);
+
+    let delta = val1.delta(&val0)?;
+    let json = serde_json::to_string(&delta).expect("Failed to serialize");
+    assert_eq!(json, r#"{"value":null,"next":"None"}"#);
+    assert_eq!(val1.apply(delta)?, val0);
+    assert_eq!(Garply::from_delta(val1.clone().into_delta()?)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn struct__delta__derive_ord() -> DeltaResult<()> {
+    let base = Wibble { version: 1, label: "a".into() };
+    let mut deltas: Vec<WibbleDelta> = vec![
+        base.delta(&Wibble { version: 3, label: "a".into() })?,
+        base.delta(&Wibble { version: 2, label: "b".into() })?,
+        base.delta(&Wibble { version: 2, label: "a".into() })?,
+        base.delta(&base)?,
+    ];
+    deltas.sort();
+    let versions: Vec<u32> = deltas.iter()
+        .map(|delta| base.apply(delta.clone()).map(|w| w.version))
+        .collect::<DeltaResult<_>>()?;
+    assert_eq!(versions, vec![1, 2, 2, 3]);
+    assert_eq!(base.apply(deltas[1].clone())?.label, "a");
+    assert_eq!(base.apply(deltas[2].clone())?.label, "b");
+    assert!(deltas[0] < deltas[1]);
+    Ok(())
+}
+
+#[test]
+pub fn hashmap__delta__struct_keys() -> DeltaResult<()> {
+    let cell = |row: u8, col: u8| Cell { row, col };
+    let mut map0: HashMap<Cell, String> = HashMap::new();
+    map0.insert(cell(0, 0), "a".into());
+    map0.insert(cell(0, 1), "b".into());
+    let mut map1: HashMap<Cell, String> = HashMap::new();
+    map1.insert(cell(0, 0), "a".into());
+    map1.insert(cell(1, 1), "c".into());
+    let delta = map0.delta(&map1)?;
+    assert_eq!(delta.len(), 2);
+    let json = serde_json::to_string(&delta).expect("Failed to serialize");
+    assert!(json.contains(r#"{"Add":{"key":{"row":1,"col":1},"value":"c"}}"#), "{}", json);
+    assert!(json.contains(r#"{"Remove":{"key":{"row":0,"col":1}}}"#), "{}", json);
+    let decoded: <HashMap<Cell, String> as Core>::Delta =
+        serde_json::from_str(&json).expect("Failed to deserialize");
+    assert_eq!(map0.apply(decoded)?, map1);
+    assert_eq!(map1.apply(map1.delta(&map0)?)?, map0);
+    Ok(())
+}
+
+#[test]
+pub fn struct__apply_patch() -> DeltaResult<()> {
+    let mut plover: Plover<u32> = Plover {
+        name: "w".into(),
+        value: 1,
+        cache: std::marker::PhantomData,
+    };
+    let patch: PloverPatch<u32> = PloverPatch {
+        value: Some(42),
+        ..Default::default()
+    };
+    plover.apply_patch(patch);
+    assert_eq!(plover, Plover {
+        name: "w".into(),
+        value: 42,
+        cache: std::marker::PhantomData,
+    });
+    Ok(())
+}
+
+#[test]
+pub fn struct__delta__repr_transparent() -> DeltaResult<()> {
+    let val0 = Meters(vec![1, 2, 3]);
+    let val1 = Meters(vec![1, 5, 3, 4]);
+    let delta: MetersDelta = val0.delta(&val1)?;
+    let inner: <Vec<u32> as Core>::Delta = val0.0.delta(&val1.0)?;
+    assert_eq!(
+        serde_json::to_vec(&delta).expect("Failed to serialize"),
+        serde_json::to_vec(&inner).expect("Failed to serialize"),
+    );
+    assert_eq!(
+        std::mem::size_of::<MetersDelta>(),
+        std::mem::size_of::<<Vec<u32> as Core>::Delta>(),
+    );
+    assert_eq!(val0.apply(delta)?, val1);
+    assert_eq!(Meters::from_delta(val1.clone().into_delta()?)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn hashmap__delta_with__ignored_value_field() -> DeltaResult<()> {
+    let reading = |value: u32, timestamp: u64| Reading { value, timestamp };
+    let mut map0: HashMap<String, Reading> = HashMap::new();
+    map0.insert("a".into(), reading(1, 100));
+    map0.insert("b".into(), reading(2, 100));
+    let mut map1: HashMap<String, Reading> = HashMap::new();
+    map1.insert("a".into(), reading(1, 200));
+    map1.insert("b".into(), reading(3, 200));
+    // NOTE: Diff the values as if their timestamps were equal:
+    let delta = HashMapDelta::delta_with(&map0, &map1, |lhs, rhs| {
+        let rhs = Reading { timestamp: lhs.timestamp, ..rhs.clone() };
+        Ok(if *lhs != rhs { Some(lhs.delta(&rhs)?) } else { None })
+    })?;
+    assert_eq!(delta.changed_keys().collect::<Vec<_>>(), vec!["b"]);
+    let map2 = map0.apply(delta)?;
+    assert_eq!(map2["a"], reading(1, 100));
+    assert_eq!(map2["b"], reading(3, 100));
+    Ok(())
+}
+
+#[test]
+pub fn hashmap__apply_delta_idempotent__struct_values() -> DeltaResult<()> {
+    let reading = |value: u32, timestamp: u64| Reading { value, timestamp };
+    let mut map0: HashMap<String, Reading> = HashMap::new();
+    map0.insert("a".into(), reading(1, 100));
+    map0.insert("b".into(), reading(2, 100));
+    let mut map1: HashMap<String, Reading> = map0.clone();
+    map1.insert("b".into(), reading(2, 200));
+    let delta = map0.delta(&map1)?;
+    let map2 = HashMapDelta::apply_delta_idempotent(&map0, delta.clone())?;
+    assert_eq!(map2, map0.apply(delta.clone())?);
+    assert_eq!(map2, map1);
+    let map3 = HashMapDelta::apply_delta_idempotent(&map2, delta)?;
+    assert_eq!(map3, map1);
+    Ok(())
+}
+
+#[test]
+pub fn struct_with_duration_field__delta() -> DeltaResult<()> {
+    use std::time::Duration;
+    let lap0 = Lap { number: 1, time: Duration::from_millis(61_250) };
+    let lap1 = Lap { number: 1, time: Duration::from_millis(61_500) };
+    let delta = lap0.delta(&lap1)?;
+    let expected = LapDelta {
+        number: None,
+        time: Some(deltoid::DurationDelta { secs: None, nanos: Some(500_000_000) }),
+    };
+    assert_eq!(delta, expected);
+    assert_eq!(lap0.apply(delta)?, lap1);
+    Ok(())
+}
+
+#[test]
+pub fn struct_with_nonzero_field__delta() -> DeltaResult<()> {
+    use std::num::NonZeroU32;
+    let ticket0 = Ticket { id: NonZeroU32::new(7).unwrap(), title: "foo".into() };
+    let ticket1 = Ticket { id: NonZeroU32::new(7).unwrap(), title: "bar".into() };
+    let delta = ticket0.delta(&ticket1)?;
+    assert_eq!(delta.id, None);
+    assert_eq!(ticket0.apply(delta)?, ticket1);
+    let ticket2 = Ticket { id: NonZeroU32::new(8).unwrap(), title: "bar".into() };
+    assert_eq!(ticket1.apply(ticket1.delta(&ticket2)?)?, ticket2);
+    Ok(())
+}
+
+#[test]
+pub fn vec_of_structs__delta__edits_in_place() -> DeltaResult<()> {
+    use deltoid::vec::{EltDelta, VecDelta};
+    use std::num::NonZeroU32;
+    let ticket = |id: u32, title: &str| Ticket {
+        id: NonZeroU32::new(id).unwrap(),
+        title: title.into(),
+    };
+    let vec0 = vec![ticket(1, "foo"), ticket(2, "bar"), ticket(3, "baz")];
+    let vec1 = vec![ticket(1, "foo"), ticket(2, "qux"), ticket(3, "baz")];
+    let delta = vec0.delta(&vec1)?;
+    // NOTE: Only the changed field of the changed element is stored:
+    let expected: VecDelta<Ticket> = VecDelta(vec![EltDelta::Edit {
+        index: 1,
+        item: TicketDelta { id: None, title: Some(StringDelta::Replace("qux".into())) },
+    }]);
+    assert_eq!(delta, expected);
+    assert_eq!(vec0.apply(delta)?, vec1);
+
+    // NOTE: Elements beyond the end of the shorter Vec are stored whole:
+    let vec2 = vec![ticket(1, "foo"), ticket(2, "qux"), ticket(3, "baz"), ticket(4, "quux")];
+    let delta = vec1.delta(&vec2)?;
+    let expected: VecDelta<Ticket> = VecDelta(vec![
+        EltDelta::Add(ticket(4, "quux").into_delta()?),
+    ]);
+    assert_eq!(delta, expected);
+    assert_eq!(vec1.apply(delta)?, vec2);
+    Ok(())
+}
+
+#[test]
+pub fn delta__const_generics() -> DeltaResult<()> {
+    let buffer0: Buffer<4> = Buffer { data: [1, 2, 3, 4], len: 4 };
+    let buffer1: Buffer<4> = Buffer { data: [1, 2, 5, 4], len: 3 };
+    let delta: BufferDelta<4> = buffer0.delta(&buffer1)?;
+    assert_eq!(buffer0.apply(delta)?, buffer1);
+    assert_eq!(Buffer::<4>::from_delta(buffer1.clone().into_delta()?)?, buffer1);
+
+    let frame0: Frame<2> = Frame::Data([1, 2]);
+    let frame1: Frame<2> = Frame::Data([1, 3]);
+    let delta: FrameDelta<2> = frame0.delta(&frame1)?;
+    assert_eq!(frame0.apply(delta)?, frame1);
+    assert_eq!(frame1.apply(frame1.delta(&Frame::Ack)?)?, Frame::Ack);
+    Ok(())
+}
+
+#[test]
+pub fn delta__lifetime_params() -> DeltaResult<()> {
+    let label = String::from("foo");
+    let labeled0: Labeled<u8> = Labeled { value: 1, label: &label };
+    let labeled1: Labeled<u8> = Labeled { value: 2, label: &label };
+    let delta: LabeledDelta<u8> = labeled0.delta(&labeled1)?;
+    assert_eq!(labeled0.apply(delta)?, labeled1);
+
+    let tagged0: Tagged<u8> = Tagged(1, std::marker::PhantomData);
+    let tagged1: Tagged<u8> = Tagged(2, std::marker::PhantomData);
+    let delta: TaggedDelta<u8> = tagged0.delta(&tagged1)?;
+    assert_eq!(tagged0.apply(delta.clone())?, tagged1);
+    assert_eq!(Tagged::from_delta(tagged1.clone().into_delta()?)?, tagged1);
+    assert_eq!(tagged1.apply(delta.invert(&tagged0)?)?, tagged0);
+
+    let borrowed0: Borrowed<u8> = Borrowed::Value(1);
+    let borrowed1: Borrowed<u8> = Borrowed::Value(2);
+    assert_eq!(borrowed0.apply(borrowed0.delta(&borrowed1)?)?, borrowed1);
+    let borrowed2: Borrowed<u8> = Borrowed::Label { label: &label };
+    assert!(!borrowed0.delta(&borrowed2)?.is_empty());
+    Ok(())
+}
+
+#[test]
+pub fn delta__delta_name() -> DeltaResult<()> {
+    let config0 = settings::Config { verbose: false };
+    let config1 = settings::Config { verbose: true };
+    let delta: SettingsConfigDelta = config0.delta(&config1)?;
+    assert_eq!(config0.apply(delta)?, config1);
+    let config2 = Config { name: "foo".into() };
+    let delta: ConfigDelta = config2.delta(&Config { name: "bar".into() })?;
+    assert_eq!(config2.apply(delta)?, Config { name: "bar".into() });
+
+    let mode0 = settings::Mode::Fast;
+    let mode1 = settings::Mode::Slow { factor: 2 };
+    let delta: SettingsModeDelta = mode0.delta(&mode1)?;
+    assert_eq!(mode0.apply(delta)?, mode1);
+    Ok(())
+}
+
+#[test]
+pub fn delta__custom_delta_type() -> DeltaResult<()> {
+    let hits0 = Hits { count: 4_000_000_000, page: "index".into() };
+    let hits1 = Hits { count: 4_000_000_001, page: "index".into() };
+    let delta: HitsDelta = hits0.delta(&hits1)?;
+    let json = serde_json::to_string(&delta).expect("failed to serialize");
+    assert_eq!(json, r#"{"count":1,"page":null}"#);
+    assert_eq!(hits0.apply(delta.clone())?, hits1);
+    let mut hits2 = hits0.clone();
+    hits2.apply_mut(delta)?;
+    assert_eq!(hits2, hits1);
+    assert_eq!(Hits::from_delta(hits1.clone().into_delta()?)?, hits1);
+    assert_eq!(
+        Hits { count: 0, page: "index".into() }.apply(hits1.delta(&hits0)?),
+        Err(DeltaError::Overflow("u32".to_string())),
+    );
+
+    let stat0 = Stat::Count(10);
+    let stat1 = Stat::Count(7);
+    let delta: StatDelta = stat0.delta(&stat1)?;
+    let json = serde_json::to_string(&delta).expect("failed to serialize");
+    assert_eq!(json, r#"{"Count":-3}"#);
+    assert_eq!(stat0.apply(delta)?, stat1);
+    assert_eq!(Stat::Unknown.apply(Stat::Unknown.delta(&stat1)?)?, stat1);
+    Ok(())
+}
+
+#[test]
+pub fn delta__is_empty() -> DeltaResult<()> {
+    let foo0: Foo0<u8> = Foo0 { f0: (), f1: 42, f2: "foo".into() };
+    assert!(foo0.delta(&foo0)?.is_empty());
+    let foo1: Foo0<u8> = Foo0 { f0: (), f1: 42, f2: "bar".into() };
+    assert!(!foo0.delta(&foo1)?.is_empty());
+
+    let bar = Bar(1, 2u16);
+    assert!(bar.delta(&bar)?.is_empty());
+    assert!(!bar.delta(&Bar(1, 3u16))?.is_empty());
+    assert!(Baz.delta(&Baz)?.is_empty());
+
+    let grunt = Grunt { cache: vec![1], name: "foo".into(), count: 1 };
+    assert!(grunt.delta(&grunt)?.is_empty());
+    let garply = Garply { value: 1, next: Some(Box::new(Garply { value: 2, next: None })) };
+    assert!(garply.delta(&garply)?.is_empty());
+
+    let corge: Corge<u8, String> = Corge::Floof { one: 1, two: 2, three: "foo".into() };
+    assert!(corge.delta(&corge)?.is_empty());
+    let corge1: Corge<u8, String> = Corge::Grault(1, 2);
+    assert!(corge1.delta(&corge1)?.is_empty());
+    assert!(!corge.delta(&corge1)?.is_empty());
+    let circle = Shape::Circle { radius: 5 };
+    assert!(circle.delta(&circle)?.is_empty());
+    // NOTE: A delta between values of a variant without fields is empty,
+    //       while a delta to such a variant changes the variant:
+    let delta = Shape::Empty.delta(&Shape::Empty)?;
+    assert_eq!(delta, ShapeDelta::Unchanged);
+    assert!(delta.is_empty());
+    assert_eq!(Shape::Empty.apply(delta.clone())?, Shape::Empty);
+    assert_eq!(circle.apply(delta.clone())?, circle);
+    assert!(Shape::from_delta(delta.clone()).is_err());
+    assert_eq!(delta.invert(&Shape::Empty)?, ShapeDelta::Unchanged);
+    let delta = circle.delta(&Shape::Empty)?;
+    assert!(!delta.is_empty());
+    assert_eq!(circle.apply(delta.clone())?, Shape::Empty);
+    assert_eq!(delta.compose(&Shape::Empty.delta(&Shape::Empty)?)?, delta);
+    assert_eq!(ShapeDelta::Unchanged.compose(&delta)?, delta);
+    // NOTE: The no-change variant is renamed to avoid a user variant:
+    let delta = Status::Unchanged.delta(&Status::Unchanged)?;
+    assert_eq!(delta, StatusDelta::Unchanged_);
+    assert_eq!(format!("{:?}", delta), "StatusDelta::Unchanged_");
+    assert!(delta.is_empty());
+    let delta = Status::Changed(1).delta(&Status::Unchanged)?;
+    assert_eq!(delta, StatusDelta::Unchanged);
+    assert!(!delta.is_empty());
+    assert_eq!(Status::Changed(1).apply(delta)?, Status::Unchanged);
+
+    let meters = Meters(vec![1, 2, 3]);
+    assert!(meters.delta(&meters)?.is_empty());
+    assert!(!meters.delta(&Meters(vec![1, 2]))?.is_empty());
+    Ok(())
+}
+
+#[test]
+pub fn delta__identity_delta() -> DeltaResult<()> {
+    let foo0: Foo0<u8> = Foo0 { f0: (), f1: 42, f2: "foo".into() };
+    assert_eq!(foo0.apply(Foo0::<u8>::identity_delta())?, foo0);
+    assert!(Foo0::<u8>::identity_delta().is_empty());
+    let bar = Bar(1, 2u16);
+    assert_eq!(bar.apply(Bar::<u16>::identity_delta())?, bar);
+    assert_eq!(Baz.apply(Baz::identity_delta())?, Baz);
+    let meters = Meters(vec![1, 2, 3]);
+    assert_eq!(meters.apply(Meters::identity_delta())?, meters);
+    let garply = Garply { value: 1, next: Some(Box::new(Garply { value: 2, next: None })) };
+    assert_eq!(garply.apply(Garply::identity_delta())?, garply);
+    Ok(())
+}
+
+#[test]
+pub fn delta__invert__structs() -> DeltaResult<()> {
+    let foo0: Foo0<u8> = Foo0 { f0: (), f1: 1, f2: "foo".into() };
+    let foo1: Foo0<u8> = Foo0 { f0: (), f1: 2, f2: "foobar".into() };
+    let delta = foo0.delta(&foo1)?;
+    let inverse = delta.invert(&foo0)?;
+    assert_eq!(inverse, foo1.delta(&foo0)?);
+    assert_eq!(foo0.apply(delta)?.apply(inverse)?, foo0);
+
+    let bar0 = Bar(1, 10u16);
+    let delta = bar0.delta(&Bar(2, 10u16))?;
+    assert_eq!(bar0.apply(delta.clone())?.apply(delta.invert(&bar0)?)?, bar0);
+    assert_eq!(Baz.apply(Baz.delta(&Baz)?.invert(&Baz)?)?, Baz);
+
+    let meters = Meters(vec![1, 2, 3]);
+    let delta = meters.delta(&Meters(vec![3, 4]))?;
+    assert_eq!(meters.apply(delta.clone())?.apply(delta.invert(&meters)?)?, meters);
+
+    let garply0 = Garply { value: 1, next: Some(Box::new(Garply { value: 2, next: None })) };
+    let garply1 = Garply { value: 1, next: None };
+    let delta = garply0.delta(&garply1)?;
+    assert_eq!(garply0.apply(delta.clone())?.apply(delta.invert(&garply0)?)?, garply0);
+    let delta = garply1.delta(&garply0)?;
+    assert_eq!(garply1.apply(delta.clone())?.apply(delta.invert(&garply1)?)?, garply1);
+    Ok(())
+}
+
+#[test]
+pub fn delta__invert__std_fields() -> DeltaResult<()> {
+    let (sink1, sink2) = (sink(1), sink(2));
+    let delta = sink1.delta(&sink2)?;
+    let inverse = delta.invert(&sink1)?;
+    assert_eq!(inverse, sink2.delta(&sink1)?);
+    assert_eq!(sink1.apply(delta)?.apply(inverse)?, sink1);
+    Ok(())
+}
+
+#[test]
+pub fn delta__invert__enums() -> DeltaResult<()> {
+    let rect0 = Shape::Rect { width: 1, height: 2 };
+    let rect1 = Shape::Rect { width: 1, height: 3 };
+    let delta = rect0.delta(&rect1)?;
+    assert_eq!(rect0.apply(delta.clone())?.apply(delta.invert(&rect0)?)?, rect0);
+    // NOTE: Undoing a change of variant needs the fields of `base`:
+    for shape in [Shape::Circle { radius: 5 }, Shape::Empty] {
+        let delta = rect0.delta(&shape)?;
+        let inverse = delta.invert(&rect0)?;
+        assert_eq!(inverse, rect0.clone().into_delta()?);
+        assert_eq!(rect0.apply(delta)?.apply(inverse)?, rect0);
+    }
+
+    let corge0: Corge<u8, String> = Corge::Floof { one: 1, two: 2, three: "foo".into() };
+    let corge1: Corge<u8, String> = Corge::Floof { one: 1, two: 3, three: "bar".into() };
+    let corge2: Corge<u8, String> = Corge::Grault(1, 2);
+    for corge in [&corge1, &corge2] {
+        let delta = corge0.delta(corge)?;
+        assert_eq!(corge0.apply(delta.clone())?.apply(delta.invert(&corge0)?)?, corge0);
+        let delta = corge.delta(&corge0)?;
+        assert_eq!(corge.apply(delta.clone())?.apply(delta.invert(corge)?)?, *corge);
+    }
+    Ok(())
+}
+
+#[test]
+pub fn delta__compose__structs() -> DeltaResult<()> {
+    let foo0: Foo0<u8> = Foo0 { f0: (), f1: 1, f2: "foo".into() };
+    let foo1: Foo0<u8> = Foo0 { f0: (), f1: 2, f2: "foo".into() };
+    let foo2: Foo0<u8> = Foo0 { f0: (), f1: 2, f2: "foobar".into() };
+    let delta = foo0.delta(&foo1)?.compose(&foo1.delta(&foo2)?)?;
+    assert_eq!(delta, foo0.delta(&foo2)?);
+    assert_eq!(foo0.apply(delta)?, foo2);
+
+    let bar0 = Bar(1, 10u16);
+    let (bar1, bar2) = (Bar(2, 10u16), Bar(3, 20u16));
+    let delta = bar0.delta(&bar1)?.compose(&bar1.delta(&bar2)?)?;
+    assert_eq!(delta, bar0.delta(&bar2)?);
+    assert_eq!(bar0.apply(delta)?, bar2);
+    assert_eq!(Baz.delta(&Baz)?.compose(&Baz.delta(&Baz)?)?, Baz.delta(&Baz)?);
+
+    let meters0 = Meters(vec![1, 2, 3]);
+    let (meters1, meters2) = (Meters(vec![1, 2]), Meters(vec![0, 1, 2, 4]));
+    let delta = meters0.delta(&meters1)?.compose(&meters1.delta(&meters2)?)?;
+    assert_eq!(meters0.apply(delta)?, meters2);
+    Ok(())
+}
+
+#[test]
+pub fn delta__compose__std_fields() -> DeltaResult<()> {
+    let (sink0, sink1, sink2) = (sink(0), sink(1), sink(2));
+    let delta = sink0.delta(&sink1)?.compose(&sink1.delta(&sink2)?)?;
+    assert_eq!(sink0.apply(delta)?, sink2);
+    let delta = sink1.delta(&sink2)?.compose(&sink2.delta(&sink2)?)?;
+    assert_eq!(delta, sink1.delta(&sink2)?);
+
+    // NOTE: A recursive type composes by way of its own `Compose` impl:
+    let garply0 = Garply { value: 1, next: None };
+    let garply1 = Garply { value: 1, next: Some(Box::new(Garply { value: 2, next: None })) };
+    let garply2 = Garply { value: 3, next: Some(Box::new(Garply { value: 4, next: None })) };
+    let delta = garply0.delta(&garply1)?.compose(&garply1.delta(&garply2)?)?;
+    assert_eq!(garply0.apply(delta)?, garply2);
+    Ok(())
+}
+
+#[test]
+pub fn delta__compose__enums() -> DeltaResult<()> {
+    let rect0 = Shape::Rect { width: 1, height: 2 };
+    let rect1 = Shape::Rect { width: 3, height: 2 };
+    let rect2 = Shape::Rect { width: 3, height: 4 };
+    let delta = rect0.delta(&rect1)?.compose(&rect1.delta(&rect2)?)?;
+    assert_eq!(delta, rect0.delta(&rect2)?);
+    assert_eq!(rect0.apply(delta)?, rect2);
+
+    // NOTE: Changing the variant and then a field of the new variant:
+    let circle0 = Shape::Circle { radius: 5 };
+    let delta = circle0.delta(&rect1)?.compose(&rect1.delta(&rect2)?)?;
+    assert_eq!(delta, circle0.delta(&rect2)?);
+    assert_eq!(circle0.apply(delta)?, rect2);
+    let delta = rect0.delta(&circle0)?.compose(&circle0.delta(&Shape::Empty)?)?;
+    assert_eq!(rect0.apply(delta)?, Shape::Empty);
+
+    let corge0: Corge<u8, String> = Corge::Grault(1, 2);
+    let corge1: Corge<u8, String> = Corge::Quux;
+    let corge2: Corge<u8, String> = Corge::Floof { one: 1, two: 2, three: "foo".into() };
+    let delta = corge0.delta(&corge1)?.compose(&corge1.delta(&corge2)?)?;
+    assert_eq!(corge0.apply(delta)?, corge2);
+
+    // NOTE: A delta of one variant can't follow one of another variant:
+    let rect_delta = rect0.delta(&rect1)?;
+    let circle_delta = circle0.delta(&Shape::Circle { radius: 6 })?;
+    assert!(matches!(
+        circle_delta.compose(&rect_delta),
+        Err(DeltaError::FailedToApplyDelta { .. }),
+    ));
+    assert_eq!(rect_delta.compose(&circle_delta)?, circle_delta);
+    Ok(())
+}
+
+#[test]
+pub fn vec_of_structs__apply_mut__matches_apply() -> DeltaResult<()> {
+    use std::num::NonZeroU32;
+    let ticket = |id: u32, title: &str| Ticket {
+        id: NonZeroU32::new(id).unwrap(),
+        title: title.into(),
+    };
+    let states: Vec<Vec<Ticket>> = vec![
+        vec![ticket(1, "foo"), ticket(2, "bar"), ticket(3, "baz")],
+        vec![ticket(1, "foo"), ticket(2, "qux"), ticket(3, "baz")],
+        vec![ticket(1, "foo"), ticket(4, "qux")],
+        vec![ticket(5, "quux"), ticket(1, "foo"), ticket(4, "qux"), ticket(6, "")],
+    ];
+    for pair in states.windows(2) {
+        let delta = pair[0].delta(&pair[1])?;
+        let mut tickets: Vec<Ticket> = pair[0].clone();
+        tickets.apply_mut(delta.clone())?;
+        assert_eq!(tickets, pair[0].apply(delta)?);
+        assert_eq!(tickets, pair[1]);
+    }
+
+    let garply0 = Garply { value: 1, next: None };
+    let garply1 = Garply { value: 1, next: Some(Box::new(Garply { value: 2, next: None })) };
+    let mut garply2 = garply0.clone();
+    garply2.apply_mut(garply0.delta(&garply1)?)?;
+    assert_eq!(garply2, garply1);
+
+    let bar0 = Bar(1, 10u16);
+    let mut bar1 = bar0.clone();
+    bar1.apply_mut(bar0.delta(&Bar(2, 10u16))?)?;
+    assert_eq!(bar1, Bar(2, 10u16));
+
+    let meters0 = Meters(vec![1, 2, 3]);
+    let mut meters1 = meters0.clone();
+    meters1.apply_mut(meters0.delta(&Meters(vec![2, 3, 4]))?)?;
+    assert_eq!(meters1, Meters(vec![2, 3, 4]));
+    Ok(())
+}
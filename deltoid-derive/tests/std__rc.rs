@@ -3,7 +3,7 @@
 
 use std::rc::Rc;
 #[allow(unused)] use deltoid::{
-    Apply, Delta, DeltaResult, FromDelta, IntoDelta, RcDelta, StringDelta
+    Apply, Delta, DeltaResult, FromDelta, IntoDelta, RcDelta, StringDelta, StringOp
 };
 use deltoid_derive::Delta;
 use serde_derive::{Deserialize, Serialize};
@@ -22,7 +22,10 @@ fn Rc__delta() -> DeltaResult<()> {
     let delta0 = v0.delta(&v1)?;
     println!("delta0: {:#?}", delta0);
     let expected = RcDelta(Some(Box::new(Foo1Delta {
-        s: Some(StringDelta(Some("hello world!!".to_string()))),
+        s: Some(StringDelta::Edit(vec![
+            StringOp::Retain(11),
+            StringOp::Insert("!!".to_string()),
+        ])),
         i: None,
     })));
     assert_eq!(delta0, expected, "{:#?}\n    !=\n{:#?}", delta0, expected);
@@ -34,7 +37,10 @@ fn Rc__delta() -> DeltaResult<()> {
     let delta1 = v1.delta(&v0)?;
     println!("delta1: {:#?}", delta1);
     assert_eq!(delta1, RcDelta(Some(Box::new(Foo1Delta {
-        s: Some(StringDelta(Some("hello world".to_string()))),
+        s: Some(StringDelta::Edit(vec![
+            StringOp::Retain(11),
+            StringOp::Delete(2),
+        ])),
         i: None,
     }))));
     let v3 = v1.apply(delta1)?;
@@ -48,7 +54,7 @@ fn Rc__delta() -> DeltaResult<()> {
 fn Rc__apply() -> DeltaResult<()> {
     let v0 = Rc::new(Foo1 { s: "hello world".to_string(), i: 42 });
     let delta = RcDelta(Some(Box::new(Foo1Delta {
-        s: Some(StringDelta(Some("hello world!!".to_string()))),
+        s: Some(StringDelta::Replace("hello world!!".to_string())),
         i: None,
     })));
     let v1 = v0.apply(delta)?;
@@ -5,7 +5,7 @@
 
 #[allow(unused)] use deltoid::{
     Apply, Delta, DeltaResult, FromDelta, IntoDelta,
-    ArcDelta, RwLock, RwLockDelta, StringDelta
+    ArcDelta, RwLock, RwLockDelta, StringDelta, StringOp
 };
 use deltoid_derive::Delta;
 use serde_json;
@@ -100,9 +100,10 @@ fn RwLock__delta() {
     println!("delta: {:#?}", delta);
 
     let expected: RwLockDelta<Foo> = RwLockDelta(Some(FooDelta {
-        field0: Some(
-            "flapjacks are fun?".to_string().into_delta().unwrap()
-        ),
+        field0: Some(StringDelta::Edit(vec![
+            StringOp::Retain(17),
+            StringOp::Insert("?".to_string()),
+        ])),
         field1: None,
     }));
     println!("expected: {:#?}", expected);
@@ -121,7 +122,10 @@ fn Arc__delta() -> DeltaResult<()> {
     let delta0 = v0.delta(&v1)?;
     println!("delta0: {:#?}", delta0);
     let expected = ArcDelta(Some(Box::new(FooDelta {
-        field0: Some(StringDelta(Some("hello world!!".to_string()))),
+        field0: Some(StringDelta::Edit(vec![
+            StringOp::Retain(11),
+            StringOp::Insert("!!".to_string()),
+        ])),
         field1: None,
     })));
     assert_eq!(delta0, expected, "{:#?}\n    !=\n{:#?}", delta0, expected);
@@ -133,7 +137,10 @@ fn Arc__delta() -> DeltaResult<()> {
     let delta1 = v1.delta(&v0)?;
     println!("delta1: {:#?}", delta1);
     assert_eq!(delta1, ArcDelta(Some(Box::new(FooDelta {
-        field0: Some(StringDelta(Some("hello world".to_string()))),
+        field0: Some(StringDelta::Edit(vec![
+            StringOp::Retain(11),
+            StringOp::Delete(2),
+        ])),
         field1: None,
     }))));
     let v3 = v1.apply(delta1)?;
@@ -147,7 +154,7 @@ fn Arc__delta() -> DeltaResult<()> {
 fn Arc__apply() -> DeltaResult<()> {
     let v0 = Arc::new(Foo { field0: "hello world".to_string(), field1: 42 });
     let delta = ArcDelta(Some(Box::new(FooDelta {
-        field0: Some(StringDelta(Some("hello world!!".to_string()))),
+        field0: Some(StringDelta::Replace("hello world!!".to_string())),
         field1: None,
     })));
     let v1 = v0.apply(delta)?;
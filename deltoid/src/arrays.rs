@@ -1,6 +1,9 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::mem::{self, MaybeUninit};
@@ -101,6 +104,16 @@ where T: Clone + Debug + PartialEq + IntoDelta
     }
 }
 
+impl<T, const LEN: usize> EmptyDelta for [T; LEN]
+where T: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn identity_delta() -> Self::Delta {
+        ArrayDelta(vec![])
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
 pub struct ArrayDelta<T: Core, const LEN: usize> (
@@ -114,6 +127,40 @@ pub struct Edit<T: Core> {
     index: usize,
 }
 
+impl<T: Core, const LEN: usize> DeltaOps for ArrayDelta<T, LEN> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T, const LEN: usize> Compose for ArrayDelta<T, LEN>
+where T: Core + Clone,
+      T::Delta: Compose,
+{
+    /// The edits of both deltas are merged by index, and the result is
+    /// ordered by index like the deltas calculated by `delta()`.
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        let mut edits: Vec<Edit<T>> = self.0.clone();
+        for edit in next.0.iter() {
+            match edits.iter_mut().find(|e| e.index == edit.index) {
+                Some(e) => e.delta = e.delta.compose(&edit.delta)?,
+                None => edits.push(edit.clone()),
+            }
+        }
+        edits.sort_by_key(|edit| edit.index);
+        Ok(ArrayDelta(edits))
+    }
+}
+
+impl<T: Core, const LEN: usize> Invert<[T; LEN]> for ArrayDelta<T, LEN>
+where [T; LEN]: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &[T; LEN]) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 #[inline(never)]
 unsafe fn array_assume_init<T, const N: usize>(
     array: [MaybeUninit<T>; N]
@@ -260,4 +307,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn array_of_len_16__delta__single_changed_element() -> DeltaResult<()> {
+        let array0: [f32; 16] = [0.0; 16];
+        let mut array1: [f32; 16] = array0;
+        array1[7] = 1.5;
+        let delta: <[f32; 16] as Core>::Delta = array0.delta(&array1)?;
+        let json: String = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        assert_eq!(json, r#"[{"delta":1.5,"index":7}]"#);
+        let delta1: <[f32; 16] as Core>::Delta = serde_json::from_str(&json)
+            .expect("Could not deserialize from json");
+        assert_eq!(delta, delta1);
+        assert_eq!(array0.apply(delta)?, array1);
+        Ok(())
+    }
+
+    #[test]
+    fn DeltaOps__is_empty__array() -> DeltaResult<()> {
+        let (array0, array1): ([u8; 3], [u8; 3]) = ([1, 2, 3], [1, 5, 3]);
+        assert!(array0.delta(&array0)?.is_empty());
+        assert!(!array0.delta(&array1)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn ArrayDelta__compose() -> DeltaResult<()> {
+        let array0: [u8; 4] = [1, 2, 3, 4];
+        let (array1, array2): ([u8; 4], [u8; 4]) = ([1, 5, 3, 6], [7, 5, 3, 8]);
+        let delta = array0.delta(&array1)?.compose(&array1.delta(&array2)?)?;
+        assert_eq!(delta, array0.delta(&array2)?);
+        assert_eq!(array0.apply(delta)?, array2);
+        Ok(())
+    }
 }
@@ -1,6 +1,9 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de;
 use serde::ser::SerializeMap;
@@ -77,6 +80,16 @@ where B: Clone + Debug + PartialEq + IntoDelta
     }
 }
 
+impl<'a, B> EmptyDelta for Cow<'a, B>
+where B: Clone + Debug + PartialEq + Core + ToOwned
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn identity_delta() -> Self::Delta {
+        CowDelta { inner: None, _phantom: PhantomData }
+    }
+}
+
 
 
 #[derive(Clone, PartialEq)]
@@ -85,6 +98,35 @@ pub struct CowDelta<'a, B: Core> {
     #[doc(hidden)] pub _phantom: PhantomData<&'a B>
 }
 
+impl<'a, B: Core> DeltaOps for CowDelta<'a, B> {
+    fn is_empty(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl<'a, B: Core> Compose for CowDelta<'a, B>
+where B::Delta: Compose
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(CowDelta {
+            inner: match (&self.inner, &next.inner) {
+                (Some(lhs), Some(rhs)) => Some(lhs.compose(rhs)?),
+                (lhs, rhs) => rhs.as_ref().or(lhs.as_ref()).cloned(),
+            },
+            _phantom: PhantomData,
+        })
+    }
+}
+
+impl<'a, B: Core + ToOwned> Invert<Cow<'a, B>> for CowDelta<'a, B>
+where Cow<'a, B>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &Cow<'a, B>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 impl<'a, B: Core> std::fmt::Debug for CowDelta<'a, B> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         match &self.inner {
@@ -3,7 +3,10 @@
 //!
 //! [`Box`]: https://doc.rust-lang.org/std/boxed/struct.Box.html
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de;
 use serde::ser::SerializeMap;
@@ -72,6 +75,16 @@ where T: Clone + Debug + PartialEq + IntoDelta
     }
 }
 
+impl<T> EmptyDelta for Box<T>
+where T: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn identity_delta() -> Self::Delta {
+        BoxDelta(None)
+    }
+}
+
 
 
 
@@ -80,6 +93,32 @@ pub struct BoxDelta<T: Core>(
     #[doc(hidden)] pub Option<Box<T::Delta>>
 );
 
+impl<T: Core> DeltaOps for BoxDelta<T> {
+    fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl<T: Core> Compose for BoxDelta<T>
+where T::Delta: Compose
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(BoxDelta(match (&self.0, &next.0) {
+            (Some(lhs), Some(rhs)) => Some(Box::new(lhs.compose(rhs)?)),
+            (lhs, rhs) => rhs.as_ref().or(lhs.as_ref()).cloned(),
+        }))
+    }
+}
+
+impl<T: Core> Invert<Box<T>> for BoxDelta<T>
+where Box<T>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &Box<T>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 impl<T: Core> std::fmt::Debug for BoxDelta<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         match &self.0 {
@@ -210,4 +249,22 @@ mod tests {
         assert_eq!(box1, box2);
         Ok(())
     }
+
+    #[test]
+    fn DeltaOps__is_empty__Box() -> DeltaResult<()> {
+        let (box0, box1): (Box<u8>, Box<u8>) = (Box::new(1), Box::new(2));
+        assert!(box0.delta(&box0)?.is_empty());
+        assert!(!box0.delta(&box1)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn BoxDelta__compose() -> DeltaResult<()> {
+        let (box0, box1, box2) = (Box::new(1u8), Box::new(2u8), Box::new(3u8));
+        let delta = box0.delta(&box1)?.compose(&box1.delta(&box2)?)?;
+        assert_eq!(delta, box0.delta(&box2)?);
+        let delta = box0.delta(&box1)?.compose(&box1.delta(&box1)?)?;
+        assert_eq!(delta, box0.delta(&box1)?);
+        Ok(())
+    }
 }
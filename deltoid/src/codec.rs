@@ -0,0 +1,64 @@
+//! Compact binary encoding of deltas using [`postcard`], e.g. for
+//! transporting deltas to and from embedded devices.
+//!
+//! [`postcard`]: https://docs.rs/postcard
+
+use crate::{DeltaError, DeltaResult};
+use serde::{Deserialize, Serialize};
+
+
+/// Encoding to and decoding from `postcard` bytes.  This is implemented
+/// for every de/serializable type, which includes all delta types.
+pub trait PostcardCodec: Sized {
+    /// Encode `self` as `postcard` bytes.
+    fn to_postcard(&self) -> DeltaResult<Vec<u8>>;
+
+    /// Decode a value of type `Self` from `postcard` `bytes`.
+    fn from_postcard(bytes: &[u8]) -> DeltaResult<Self>;
+}
+
+impl<T> PostcardCodec for T
+where T: Serialize + for<'de> Deserialize<'de> {
+    fn to_postcard(&self) -> DeltaResult<Vec<u8>> {
+        postcard::to_allocvec(self)
+            .map_err(|err| DeltaError::Codec(format!("{}", err)))
+    }
+
+    fn from_postcard(bytes: &[u8]) -> DeltaResult<Self> {
+        postcard::from_bytes(bytes)
+            .map_err(|err| DeltaError::Codec(format!("{}", err)))
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Delta, StringDelta, VecDelta};
+
+    #[test]
+    fn PostcardCodec__malformed_bytes() -> DeltaResult<()> {
+        let delta: VecDelta<u32> = vec![1u32, 2].delta(&vec![1, 3, 4])?;
+        let bytes: Vec<u8> = delta.to_postcard()?;
+        assert_eq!(VecDelta::<u32>::from_postcard(&bytes)?, delta);
+        let result = VecDelta::<u32>::from_postcard(&bytes[.. bytes.len() - 1]);
+        assert!(matches!(result, Err(DeltaError::Codec(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn PostcardCodec__StringDelta() -> DeltaResult<()> {
+        let s0 = String::from("hello world");
+        for s1 in &["hello world!!", "bye"] {
+            let delta: StringDelta = s0.delta(&s1.to_string())?;
+            let bytes: Vec<u8> = delta.to_postcard()?;
+            assert_eq!(StringDelta::from_postcard(&bytes)?, delta);
+        }
+        let delta = StringDelta::line_diff("foo\nbar", "foo\nbaz\n");
+        let bytes: Vec<u8> = delta.to_postcard()?;
+        assert_eq!(StringDelta::from_postcard(&bytes)?, delta);
+        Ok(())
+    }
+}
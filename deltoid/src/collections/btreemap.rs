@@ -3,10 +3,14 @@
 //!
 //! [`BtreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
 
-use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Core, Delta, DeltaError, DeltaOps, DeltaResult,
+    EmptyDelta, FromDelta, IntoDelta, Invert,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeSet, BTreeMap};
 use std::fmt::Debug;
+use std::ops::RangeBounds;
 
 
 impl<K, V> Core for BTreeMap<K, V>
@@ -30,19 +34,37 @@ where K: Clone + Debug + PartialEq + Ord + Apply
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
         let mut new: Self = self.clone();
-        for change in delta.into_iter() { match change {
-            EntryDelta::Edit { key, value } => {
-                let place: &mut V = &mut *new.get_mut(&key)
-                    .ok_or_else(|| ExpectedValue!("BTreeMapDelta<K, V>"))?;
-                *place = <V>::from_delta(value)?;
-            },
-            EntryDelta::Add { key, value } => {
-                new.insert(key, <V>::from_delta(value)?);
-            },
-            EntryDelta::Remove { key } =>  { new.remove(&key); },
-        }}
+        new.apply_mut(delta)?;
         Ok(new)
     }
+
+    fn apply_mut(&mut self, delta: Self::Delta) -> DeltaResult<()> {
+        for change in delta.into_iter() {
+            apply_entry_delta(self, change)?;
+        }
+        Ok(())
+    }
+}
+
+fn apply_entry_delta<K, V>(
+    map: &mut BTreeMap<K, V>,
+    change: EntryDelta<K, V>,
+) -> DeltaResult<()>
+where K: Ord,
+      V: Apply + FromDelta,
+{
+    match change {
+        EntryDelta::Edit { key, value } => {
+            let place: &mut V = &mut *map.get_mut(&key)
+                .ok_or_else(|| ExpectedValue!("BTreeMapDelta<K, V>"))?;
+            place.apply_mut(value)?;
+        },
+        EntryDelta::Add { key, value } => {
+            map.insert(key, <V>::from_delta(value)?);
+        },
+        EntryDelta::Remove { key } =>  { map.remove(&key); },
+    }
+    Ok(())
 }
 
 impl<K, V> Delta for BTreeMap<K, V>
@@ -127,6 +149,19 @@ where K: Clone + Debug + PartialEq + Ord + IntoDelta
     }
 }
 
+impl<K, V> EmptyDelta for BTreeMap<K, V>
+where K: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn identity_delta() -> Self::Delta {
+        BTreeMapDelta(None)
+    }
+}
+
 
 
 
@@ -165,6 +200,65 @@ where K: Clone + Debug + PartialEq + Ord + Core
             None => 0,
         }
     }
+
+    /// Apply only those entry deltas in `delta` whose keys fall within
+    /// `range`, leaving the entries of `base` outside of `range` untouched.
+    /// What happens to out-of-range entry deltas is determined by `mode`.
+    pub fn apply_delta_range<R>(
+        base: &BTreeMap<K, V>,
+        delta: Self,
+        range: R,
+        mode: RangeMode,
+    ) -> DeltaResult<BTreeMap<K, V>>
+    where R: RangeBounds<K>,
+          V: Apply + FromDelta,
+    {
+        let mut new: BTreeMap<K, V> = base.clone();
+        for change in delta.into_iter() {
+            let key: &K = match &change {
+                EntryDelta::Edit { key, .. } => key,
+                EntryDelta::Add { key, .. } => key,
+                EntryDelta::Remove { key } => key,
+            };
+            if range.contains(key) {
+                apply_entry_delta(&mut new, change)?;
+            } else if mode == RangeMode::Strict {
+                return Err(DeltaError::FailedToApplyDelta {
+                    reason: format!("Key {:?} is out of range", key),
+                });
+            }
+        }
+        Ok(new)
+    }
+}
+
+/// Determines how `BTreeMapDelta::apply_delta_range()` treats
+/// entry deltas with keys that fall outside of the applied range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RangeMode {
+    /// Skip out-of-range entry deltas.
+    Lenient,
+    /// Fail upon encountering an out-of-range entry delta.
+    Strict,
+}
+
+impl<K: Core, V: Core> DeltaOps for BTreeMapDelta<K, V> {
+    fn is_empty(&self) -> bool {
+        match &self.0 {
+            Some(changes) => changes.is_empty(),
+            None => true,
+        }
+    }
+}
+
+impl<K, V> Invert<BTreeMap<K, V>> for BTreeMapDelta<K, V>
+where K: Core, V: Core,
+      BTreeMap<K, V>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &BTreeMap<K, V>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
 }
 
 impl<K, V> std::fmt::Debug for BTreeMapDelta<K, V>
@@ -348,4 +442,95 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn BTreeMapDelta__apply_delta_range() -> DeltaResult<()> {
+        let map0: BTreeMap<String, usize> = map! {
+            "bar".into()     => 300usize,
+            "foo".into()     =>  42usize,
+            "floozie".into() =>  0usize,
+            "quux".into()    => 16000usize,
+        };
+        let map1: BTreeMap<String, usize> = map! {
+            "bar".into()  =>   350usize,
+            "baz".into()  =>  9000usize,
+            "foo".into()  =>    42usize,
+            "quux".into() =>    10usize,
+            "zed".into()  =>     1usize,
+        };
+        let delta = map0.delta(&map1)?;
+        let range = String::from("a") .. String::from("m");
+        let map2 = BTreeMapDelta::apply_delta_range(
+            &map0, delta.clone(), range.clone(), RangeMode::Lenient
+        )?;
+        assert_eq!(map2, map! {
+            "bar".into()  =>   350usize,
+            "baz".into()  =>  9000usize,
+            "foo".into()  =>    42usize,
+            "quux".into() => 16000usize,
+        });
+
+        let result = BTreeMapDelta::apply_delta_range(
+            &map0, delta, range, RangeMode::Strict
+        );
+        assert!(matches!(result, Err(DeltaError::FailedToApplyDelta { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn BTreeMap__delta__each_kind_of_change() -> DeltaResult<()> {
+        let map0: BTreeMap<String, usize> = map! {
+            "bar".into() => 300usize,
+            "foo".into() =>  42usize,
+        };
+        // NOTE: A changed value without a change in the set of keys:
+        let map1: BTreeMap<String, usize> = map! {
+            "bar".into() => 350usize,
+            "foo".into() =>  42usize,
+        };
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta, BTreeMapDelta(Some(vec![
+            EntryDelta::Edit { key: "bar".into(), value: 350usize.into_delta()? },
+        ])));
+        assert_eq!(map0.apply(delta)?, map1);
+        // NOTE: A removed key:
+        let map2: BTreeMap<String, usize> = map! { "foo".into() => 42usize };
+        let delta = map0.delta(&map2)?;
+        assert_eq!(delta, BTreeMapDelta(Some(vec![
+            EntryDelta::Remove { key: "bar".into() },
+        ])));
+        assert_eq!(map0.apply(delta)?, map2);
+        // NOTE: An added key:
+        let delta = map2.delta(&map0)?;
+        assert_eq!(delta, BTreeMapDelta(Some(vec![
+            EntryDelta::Add { key: "bar".into(), value: 300usize.into_delta()? },
+        ])));
+        assert_eq!(map2.apply(delta)?, map0);
+        Ok(())
+    }
+
+    #[test]
+    fn BTreeMap__apply__string_edit() -> DeltaResult<()> {
+        let map0: BTreeMap<String, String> = map! {
+            "fox".into() => "The quick brown fox jumps".into(),
+            "dog".into() => "over the lazy dog".into(),
+        };
+        let map1: BTreeMap<String, String> = map! {
+            "fox".into() => "The quick brown fox jumps!".into(),
+            "dog".into() => "over the lazy dog".into(),
+        };
+        let delta = map0.delta(&map1)?;
+        // NOTE: The value delta edits the string rather than replacing it:
+        assert_eq!(delta, BTreeMapDelta(Some(vec![EntryDelta::Edit {
+            key: "fox".into(),
+            value: crate::StringDelta::Edit(vec![
+                crate::StringOp::Retain(25),
+                crate::StringOp::Insert("!".into()),
+            ]),
+        }])));
+        assert_eq!(map0.apply(delta.clone())?, map1);
+        let mut map2: BTreeMap<String, String> = map0.clone();
+        map2.apply_mut(delta)?;
+        assert_eq!(map2, map1);
+        Ok(())
+    }
 }
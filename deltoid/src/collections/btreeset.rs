@@ -3,8 +3,12 @@
 //!
 //! [`BTreeSet`]: https://doc.rust-lang.org/std/collections/struct.BTreeSet.html
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Core, Delta, DeltaError, DeltaOps, DeltaResult,
+    EmptyDelta, FromDelta, IntoDelta, Invert,
+};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::fmt::Debug;
 
@@ -23,19 +27,34 @@ where T: Clone + Debug + PartialEq + Ord + Apply + FromDelta
     + Serialize,
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        let mut new: Self = self.clone();
+        new.apply_mut(delta)?;
+        Ok(new)
+    }
+
+    fn apply_mut(&mut self, delta: Self::Delta) -> DeltaResult<()> {
         match delta.0 {
-            None => Ok(self.clone()),
+            None => Ok(()),
             Some(entry_deltas) => {
-                let mut new: Self = self.clone();
                 for entry_delta in entry_deltas { match entry_delta {
                     EntryDelta::Add { item } => {
-                        new.insert(<T>::from_delta(item)?);
+                        let item: T = <T>::from_delta(item)?;
+                        if self.contains(&item) {
+                            return Err(DeltaError::Conflict {
+                                index: None,
+                                key: Some(format!("{:?}", item)),
+                            });
+                        }
+                        self.insert(item);
                     },
                     EntryDelta::Remove { item } => {
-                        new.remove(&(<T>::from_delta(item)?));
+                        let item: T = <T>::from_delta(item)?;
+                        if !self.remove(&item) {
+                            return Err(DeltaError::KeyNotFound(format!("{:?}", item)));
+                        }
                     },
                 }}
-                Ok(new)
+                Ok(())
             },
         }
     }
@@ -47,19 +66,36 @@ where T: Clone + Debug + PartialEq + Ord + Delta + IntoDelta
     + Serialize,
 {
     fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
-        Ok(BTreeSetDelta(if self == rhs {
+        // NOTE: Both sets iterate in sorted order, so a single merge walk
+        //       finds the symmetric difference in O(n + m), and yields the
+        //       additions and removals each in sorted order:
+        let mut additions: Vec<EntryDelta<T>> = vec![];
+        let mut removals: Vec<EntryDelta<T>> = vec![];
+        let (mut lhs, mut rhs) = (self.iter().peekable(), rhs.iter().peekable());
+        loop {
+            let ordering = match (lhs.peek(), rhs.peek()) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(l), Some(r)) => l.cmp(r),
+            };
+            match ordering {
+                Ordering::Less => {
+                    let item = lhs.next().unwrap(/*peeked*/).clone().into_delta()?;
+                    removals.push(EntryDelta::Remove { item });
+                },
+                Ordering::Greater => {
+                    let item = rhs.next().unwrap(/*peeked*/).clone().into_delta()?;
+                    additions.push(EntryDelta::Add { item });
+                },
+                Ordering::Equal => { lhs.next(); rhs.next(); },
+            }
+        }
+        additions.extend(removals);
+        Ok(BTreeSetDelta(if additions.is_empty() {
             None
         } else {
-            let mut entry_deltas: Vec<EntryDelta<T>> = vec![];
-            for addition in rhs.difference(&self) {
-                let addition = addition.clone().into_delta()?;
-                entry_deltas.push(EntryDelta::Add { item: addition });
-            }
-            for removal in self.difference(&rhs) {
-                let removal = removal.clone().into_delta()?;
-                entry_deltas.push(EntryDelta::Remove { item: removal });
-            }
-            Some(entry_deltas)
+            Some(additions)
         }))
     }
 }
@@ -104,6 +140,16 @@ where T: Clone + Debug + PartialEq + Ord + IntoDelta
     }
 }
 
+impl<T> EmptyDelta for BTreeSet<T>
+where T: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn identity_delta() -> Self::Delta {
+        BTreeSetDelta(None)
+    }
+}
+
 
 
 
@@ -141,6 +187,25 @@ where T: Clone + Debug + PartialEq + Ord + Core
     }
 }
 
+impl<T: Core> DeltaOps for BTreeSetDelta<T> {
+    fn is_empty(&self) -> bool {
+        match &self.0 {
+            Some(changes) => changes.is_empty(),
+            None => true,
+        }
+    }
+}
+
+impl<T> Invert<BTreeSet<T>> for BTreeSetDelta<T>
+where T: Core,
+      BTreeSet<T>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &BTreeSet<T>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 impl<T> std::fmt::Debug for BTreeSetDelta<T>
 where T: std::fmt::Debug + Core {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
@@ -302,4 +367,65 @@ mod tests {
         assert_eq!(set1, set2);
         Ok(())
     }
+
+    #[test]
+    fn BTreeSet__delta__disjoint_values() -> DeltaResult<()> {
+        let set0: BTreeSet<u32> = set! { 5, 1, 3 };
+        let set1: BTreeSet<u32> = set! { 4, 2, 6 };
+        let delta = set0.delta(&set1)?;
+        assert_eq!(delta, BTreeSetDelta(Some(vec![
+            EntryDelta::Add { item: 2u32.into_delta()? },
+            EntryDelta::Add { item: 4u32.into_delta()? },
+            EntryDelta::Add { item: 6u32.into_delta()? },
+            EntryDelta::Remove { item: 1u32.into_delta()? },
+            EntryDelta::Remove { item: 3u32.into_delta()? },
+            EntryDelta::Remove { item: 5u32.into_delta()? },
+        ])));
+        assert_eq!(set0.apply(delta)?, set1);
+        Ok(())
+    }
+
+    #[test]
+    fn BTreeSet__delta__identical_values() -> DeltaResult<()> {
+        let set0: BTreeSet<u32> = (0 .. 100).collect();
+        let delta = set0.delta(&set0.clone())?;
+        assert_eq!(delta, BTreeSetDelta(None));
+        assert_eq!(delta.len(), 0);
+        assert_eq!(set0.apply(delta)?, set0);
+        Ok(())
+    }
+
+    #[test]
+    fn BTreeSet__delta__replaced_values() -> DeltaResult<()> {
+        let set0: BTreeSet<u32> = (0 .. 50).collect();
+        let set1: BTreeSet<u32> = (50 .. 100).rev().collect();
+        let delta = set0.delta(&set1)?;
+        assert_eq!(delta.len(), 100);
+        let json = serde_json::to_string(&delta).expect("failed to serialize");
+        let expected: BTreeSetDelta<u32> = BTreeSetDelta(Some(
+            (50u32 .. 100).map(|n| EntryDelta::Add { item: n.into_delta().unwrap() })
+                .chain((0u32 .. 50).map(|n| EntryDelta::Remove { item: n.into_delta().unwrap() }))
+                .collect()
+        ));
+        assert_eq!(json, serde_json::to_string(&expected).expect("failed to serialize"));
+        assert_eq!(set0.apply(delta.clone())?, set1);
+
+        // NOTE: Applying the delta again is invalid, since
+        //       the removed items are gone and the added ones present:
+        assert_eq!(
+            set1.apply(delta),
+            Err(DeltaError::Conflict {
+                index: None,
+                key: Some(format!("{:?}", 50u32)),
+            }),
+        );
+        let delta = BTreeSetDelta(Some(vec![
+            EntryDelta::<u32>::Remove { item: 7u32.into_delta()? },
+        ]));
+        assert_eq!(
+            set1.apply(delta),
+            Err(DeltaError::KeyNotFound(format!("{:?}", 7u32))),
+        );
+        Ok(())
+    }
 }
@@ -3,11 +3,17 @@
 //!
 //! [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
 
-use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
-use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
-use std::fmt::Debug;
+use crate::{
+    Apply, Compose, Core, Delta, DeltaError, DeltaOps, DeltaResult,
+    EmptyDelta, FromDelta, IntoDelta, Invert,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde::de::{self, DeserializeSeed, EnumAccess, IgnoredAny, MapAccess};
+use serde::de::{SeqAccess, VariantAccess, Visitor};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{self, Debug};
 use std::hash::Hash;
+use std::sync::mpsc::{Receiver, SendError, Sender};
 
 
 impl<K, V> Core for HashMap<K, V>
@@ -31,19 +37,131 @@ where K: Clone + Debug + PartialEq + Ord + Hash + Apply
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
         let mut new: Self = self.clone();
-        for change in delta.into_iter() { match change {
-            EntryDelta::Edit { key, value } => {
-                let place: &mut V = &mut *new.get_mut(&key)
-                    .ok_or_else(|| ExpectedValue!("HashMapDelta<K, V>"))?;
-                *place = <V>::from_delta(value)?;
-            },
-            EntryDelta::Add { key, value } => {
-                new.insert(key, <V>::from_delta(value)?);
-            },
-            EntryDelta::Remove { key } =>  { new.remove(&key); },
-        }}
+        new.apply_mut(delta)?;
         Ok(new)
     }
+
+    fn apply_mut(&mut self, delta: Self::Delta) -> DeltaResult<()> {
+        if removes_all_entries(self, &delta) {
+            // NOTE: Clearing `self` up front keeps its allocation, which
+            //       the additions can then refill without reallocating:
+            self.clear();
+            for change in delta.into_iter() {
+                if let EntryDelta::Remove { .. } = change { continue }
+                apply_entry_delta(self, change)?;
+            }
+            return Ok(());
+        }
+        // NOTE: Otherwise applying the removals first still frees up room
+        //       in the allocation of `self` for the additions, as long as
+        //       that doesn't change the result:
+        let (removals, changes): (Vec<_>, Vec<_>) = if removals_commute(&delta) {
+            delta.into_iter()
+                .partition(|change| matches!(change, EntryDelta::Remove { .. }))
+        } else {
+            (vec![], delta.into_iter().collect())
+        };
+        for change in removals.into_iter().chain(changes) {
+            apply_entry_delta(self, change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Map the normalized form of each key of `map` to that key, as
+/// needed by `HashMapDelta::delta_with_key_norm()`.
+fn normalized_keys<'m, K, V, F>(
+    map: &'m HashMap<K, V>,
+    norm: &F,
+) -> DeltaResult<BTreeMap<K, &'m K>>
+where K: Debug + Ord,
+      F: Fn(&K) -> K,
+{
+    let mut keys: BTreeMap<K, &K> = BTreeMap::new();
+    for key in map.keys() {
+        if let Some(other) = keys.insert(norm(key), key) {
+            let (a, b) = if other < key { (other, key) } else { (key, other) };
+            return Err(DeltaError::Conflict {
+                index: None,
+                key: Some(format!("{:?} {:?}", a, b)),
+            });
+        }
+    }
+    Ok(keys)
+}
+
+/// Returns `true` if `delta` removes every entry of `map`, and touches
+/// none of the removed keys otherwise.  Applying such a `delta` in place
+/// is equivalent to clearing `map` and then applying the non-`Remove`
+/// changes of `delta`.
+fn removes_all_entries<K, V>(
+    map: &HashMap<K, V>,
+    delta: &HashMapDelta<K, V>,
+) -> bool
+where K: Clone + Debug + PartialEq + Ord + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    if map.is_empty() { return false }
+    let mut removed: HashSet<&K> = HashSet::new();
+    for change in delta.iter() {
+        if let EntryDelta::Remove { key } = change {
+            if !map.contains_key(key) { return false }
+            removed.insert(key);
+        }
+    }
+    removed.len() == map.len() && removals_commute(delta)
+}
+
+/// Returns `true` if none of the keys that `delta` removes is touched by
+/// any of its other changes.  Applying the removals of such a `delta`
+/// before its other changes then yields the same result.
+fn removals_commute<K, V>(delta: &HashMapDelta<K, V>) -> bool
+where K: Clone + Debug + PartialEq + Ord + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    let removed: HashSet<&K> = delta.iter()
+        .filter(|change| matches!(change, EntryDelta::Remove { .. }))
+        .map(|change| change.key())
+        .collect();
+    delta.iter().all(|change| match change {
+        EntryDelta::Remove { .. } => true,
+        change => !removed.contains(change.key()),
+    })
+}
+
+fn apply_entry_delta<K, V>(
+    map: &mut HashMap<K, V>,
+    change: EntryDelta<K, V>,
+) -> DeltaResult<()>
+where K: Debug + Eq + Hash,
+      V: Apply + FromDelta,
+{
+    match change {
+        EntryDelta::Edit { key, value } => {
+            let place: &mut V = map.get_mut(&key)
+                .ok_or_else(|| DeltaError::KeyNotFound(format!("{:?}", key)))?;
+            // NOTE: Applying `value` rather than converting it to a `V`
+            //       lets e.g. an `Arc<_>` value that is edited with an
+            //       empty delta keep its identity:
+            place.apply_mut(value)?;
+        },
+        EntryDelta::Add { key, value } => {
+            map.insert(key, <V>::from_delta(value)?);
+        },
+        EntryDelta::Remove { key } => {
+            map.remove(&key)
+                .ok_or_else(|| DeltaError::KeyNotFound(format!("{:?}", key)))?;
+        },
+    }
+    Ok(())
 }
 
 impl<K, V> Delta for HashMap<K, V>
@@ -55,17 +173,57 @@ where K: Clone + Debug + PartialEq + Ord + Hash + Delta
     + Serialize,
 {
     fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
-        let lkeys: HashSet<&K> = self.keys().collect();
-        let rkeys: HashSet<&K> =  rhs.keys().collect();
-        let edited_keys = lkeys.intersection(&rkeys)
-            .filter(|key| self[key] != rhs[key]);
+        HashMapDelta::delta_with_eq(self, rhs, |lhs, rhs| lhs == rhs)
+    }
+}
+
+impl<K, V> HashMapDelta<K, V>
+where K: Clone + Debug + PartialEq + Ord + Hash + Delta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    /// Calculate `lhs --[delta]--> rhs`, using `eq` rather than `V`'s
+    /// `PartialEq` impl to decide whether the value of a key that is
+    /// present in both `lhs` and `rhs` has changed.  This is useful when
+    /// e.g. some part of the values, such as a cached timestamp, should
+    /// not count as a change by itself.
+    pub fn delta_with_eq<F>(
+        lhs: &HashMap<K, V>,
+        rhs: &HashMap<K, V>,
+        eq: F,
+    ) -> DeltaResult<Self>
+    where F: Fn(&V, &V) -> bool {
+        Self::delta_with(lhs, rhs, |lhs_val, rhs_val| {
+            if eq(lhs_val, rhs_val) { return Ok(None) }
+            lhs_val.delta(rhs_val).map(Some)
+        })
+    }
+
+    /// Calculate `lhs --[delta]--> rhs`, using `value_delta` to calculate
+    /// the delta between the values of a key that is present in both `lhs`
+    /// and `rhs`, or `None` if that value is considered unchanged.  Unlike
+    /// `delta_with_eq()`, this also controls the contents of the deltas,
+    /// so that e.g. a noisy field of the values can be ignored entirely.
+    pub fn delta_with<F>(
+        lhs: &HashMap<K, V>,
+        rhs: &HashMap<K, V>,
+        value_delta: F,
+    ) -> DeltaResult<Self>
+    where F: Fn(&V, &V) -> DeltaResult<Option<<V as Core>::Delta>> {
+        // NOTE: Walking the keys in sorted order rather than in `HashMap`
+        //       iteration order makes the delta reproducible across runs:
+        let lkeys: BTreeSet<&K> = lhs.keys().collect();
+        let rkeys: BTreeSet<&K> =  rhs.keys().collect();
         let removed_keys = lkeys.difference(&rkeys);
         let added_keys = rkeys.difference(&lkeys);
         let mut changes: Vec<EntryDelta<K, V>> = vec![];
-        for key in edited_keys {
-            let (lhs_val, rhs_val): (&V, &V) = (&self[key], &rhs[key]);
-            let delta: <V as Core>::Delta = lhs_val.delta(rhs_val)?;
-            changes.push(EntryDelta::Edit { key: (*key).clone(), value: delta });
+        for key in lkeys.intersection(&rkeys) {
+            if let Some(delta) = value_delta(&lhs[key], &rhs[key])? {
+                changes.push(EntryDelta::Edit { key: (*key).clone(), value: delta });
+            }
         }
         for key in added_keys {
             changes.push(EntryDelta::Add {
@@ -82,6 +240,125 @@ where K: Clone + Debug + PartialEq + Ord + Hash + Delta
             None
         }))
     }
+
+    /// Calculate `lhs --[delta]--> rhs`, where keys are compared after
+    /// normalizing them with `norm`, e.g. by lowercasing them for maps with
+    /// case-insensitive keys.  Keys that only differ in ways that `norm`
+    /// erases thus don't produce a remove and an add.  The resulting delta
+    /// refers to the keys as they are in `lhs`, except for added keys,
+    /// which are as they are in `rhs`.  If 2 keys of the same map have the
+    /// same normalized form, a `DeltaError::Conflict` is returned.
+    pub fn delta_with_key_norm<F>(
+        lhs: &HashMap<K, V>,
+        rhs: &HashMap<K, V>,
+        norm: F,
+    ) -> DeltaResult<Self>
+    where F: Fn(&K) -> K {
+        let lkeys: BTreeMap<K, &K> = normalized_keys(lhs, &norm)?;
+        let rkeys: BTreeMap<K, &K> = normalized_keys(rhs, &norm)?;
+        let (mut edits, mut adds, mut removes) = (vec![], vec![], vec![]);
+        for (normed, lkey) in lkeys.iter() {
+            match rkeys.get(normed) {
+                Some(rkey) => if lhs[*lkey] != rhs[*rkey] {
+                    let value = lhs[*lkey].delta(&rhs[*rkey])?;
+                    edits.push(EntryDelta::Edit { key: (*lkey).clone(), value });
+                },
+                None => removes.push(EntryDelta::Remove { key: (*lkey).clone() }),
+            }
+        }
+        for (normed, rkey) in rkeys.iter() {
+            if lkeys.contains_key(normed) { continue }
+            let value = rhs[*rkey].clone().into_delta()?;
+            adds.push(EntryDelta::Add { key: (*rkey).clone(), value });
+        }
+        let changes: Vec<EntryDelta<K, V>> = edits.into_iter()
+            .chain(adds)
+            .chain(removes)
+            .collect();
+        Ok(HashMapDelta(if !changes.is_empty() {
+            Some(changes)
+        } else {
+            None
+        }))
+    }
+
+    /// Calculate `lhs --[delta]--> rhs` lazily, one `EntryDelta` at a time,
+    /// where `lhs` and `rhs` yield the entries of 2 maps in ascending key
+    /// order, e.g. as `BTreeMap::iter()` or a sorted on-disk index does.
+    /// Both are walked in lockstep, once for each kind of entry delta, so
+    /// that besides 2 clones of each iterator no auxiliary memory is
+    /// needed, however large the maps are.  The entry deltas are yielded
+    /// in the same order as those of `lhs.delta(rhs)`.  If the keys of
+    /// either side aren't strictly ascending, an error is yielded instead.
+    pub fn diff_streaming<'m, L, R>(
+        lhs: L,
+        rhs: R,
+    ) -> StreamingMapDiff<'m, K, V, L::IntoIter, R::IntoIter>
+    where L: IntoIterator<Item = (&'m K, &'m V)>,
+          L::IntoIter: Clone,
+          R: IntoIterator<Item = (&'m K, &'m V)>,
+          R::IntoIter: Clone,
+    {
+        let (lhs, rhs) = (lhs.into_iter(), rhs.into_iter());
+        StreamingMapDiff {
+            lpass: lhs.clone(),
+            rpass: rhs.clone(),
+            lhs, rhs,
+            lnext: None,
+            rnext: None,
+            lprev: None,
+            rprev: None,
+            pass: Some(EntryKind::Edit),
+        }
+    }
+
+    /// Calculate a self-contained `lhs --[delta]--> rhs`, in which every
+    /// entry of `rhs` is stored as an `EntryDelta::Add` of its full value,
+    /// preceded by an `EntryDelta::Remove` for each key that is only
+    /// present in `lhs`.  Applying the delta to `lhs` yields `rhs` as
+    /// usual, but since it doesn't depend on the values in `lhs`, it can
+    /// also be turned into `rhs` with `FromDelta` without any base at all.
+    pub fn delta_self_contained(
+        lhs: &HashMap<K, V>,
+        rhs: &HashMap<K, V>,
+    ) -> DeltaResult<Self> {
+        let rkeys: BTreeSet<&K> = rhs.keys().collect();
+        let mut removed_keys: Vec<&K> = lhs.keys()
+            .filter(|key| !rhs.contains_key(key))
+            .collect();
+        removed_keys.sort();
+        let mut changes: Vec<EntryDelta<K, V>> = vec![];
+        for key in removed_keys {
+            changes.push(EntryDelta::Remove { key: key.clone() });
+        }
+        for key in rkeys {
+            changes.push(EntryDelta::Add {
+                key: key.clone(),
+                value: rhs[key].clone().into_delta()?,
+            });
+        }
+        Ok(HashMapDelta(if !changes.is_empty() {
+            Some(changes)
+        } else {
+            None
+        }))
+    }
+
+    /// Calculate `lhs --[delta]--> rhs` where the values of `lhs` have some
+    /// older type `W`, by first converting them to `V`.  The resulting delta
+    /// thus applies to the converted `lhs`, which is useful when e.g. the
+    /// schema of the values changed in between.
+    pub fn delta_converted<W>(
+        lhs: &HashMap<K, W>,
+        rhs: &HashMap<K, V>,
+    ) -> DeltaResult<Self>
+    where W: Clone,
+          V: From<W> {
+        let lhs: HashMap<K, V> = lhs.iter()
+            .map(|(key, value)| (key.clone(), V::from(value.clone())))
+            .collect();
+        lhs.delta(rhs)
+    }
 }
 
 impl<K, V> FromDelta for HashMap<K, V>
@@ -99,6 +376,10 @@ where K: Clone + Debug + PartialEq + Ord + Hash + FromDelta
                 match element {
                     EntryDelta::Add { key, value } =>
                         map.insert(key, <V>::from_delta(value)?),
+                    // NOTE: Removing a key from an empty base is a no-op,
+                    //       which makes self-contained deltas (see
+                    //       `HashMapDelta::delta_self_contained()`) valid:
+                    EntryDelta::Remove { key } => map.remove(&key),
                     _ => return Err(DeltaError::IllegalDelta { index })?,
                 };
             }
@@ -128,6 +409,19 @@ where K: Clone + Debug + PartialEq + Ord + Hash + IntoDelta
     }
 }
 
+impl<K, V> EmptyDelta for HashMap<K, V>
+where K: Clone + Debug + PartialEq + Ord + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn identity_delta() -> Self::Delta {
+        HashMapDelta::empty()
+    }
+}
+
 
 #[derive(Clone, PartialEq)]
 #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
@@ -165,182 +459,1960 @@ where K: Clone + Debug + PartialEq + Ord + Hash + Core
             None => 0,
         }
     }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Return the empty delta, which leaves any map unchanged.  This is
+    /// the identity of `HashMapDelta::compose()`.
+    pub fn empty() -> Self { HashMapDelta(None) }
+
+    /// Return an iterator over the keys of every entry that was added,
+    /// removed or modified by `self`, e.g. for invalidating caches.
+    pub fn changed_keys<'d>(&'d self) -> impl Iterator<Item = &'d K> + 'd {
+        self.iter().map(EntryDelta::key)
+    }
+
+    /// Group the keys of the entry deltas in `self` that apply the same
+    /// change, so that e.g. a value delta shared by many keys is stored
+    /// only once.  Since value deltas can only be compared for equality,
+    /// this takes time quadratic in the number of distinct changes.
+    pub fn grouped(&self) -> GroupedHashMapDelta<K, V> {
+        use GroupedEntryDelta as G;
+        let mut groups: Vec<GroupedEntryDelta<K, V>> = vec![];
+        for change in self.iter() {
+            let group = groups.iter_mut().find(|group| match (&**group, change) {
+                (G::Edit { value: lhs, .. }, EntryDelta::Edit { value: rhs, .. }) |
+                (G::Add  { value: lhs, .. }, EntryDelta::Add  { value: rhs, .. }) =>
+                    lhs == rhs,
+                (G::Remove { .. }, EntryDelta::Remove { .. }) => true,
+                _ => false,
+            });
+            match group {
+                Some(G::Edit { keys, .. }) |
+                Some(G::Add { keys, .. }) |
+                Some(G::Remove { keys }) => keys.push(change.key().clone()),
+                None => groups.push(match change.clone() {
+                    EntryDelta::Edit { key, value } =>
+                        G::Edit { keys: vec![key], value },
+                    EntryDelta::Add { key, value } =>
+                        G::Add { keys: vec![key], value },
+                    EntryDelta::Remove { key } =>
+                        G::Remove { keys: vec![key] },
+                }),
+            }
+        }
+        GroupedHashMapDelta(groups)
+    }
 }
 
-impl<K, V> std::fmt::Debug for HashMapDelta<K, V>
-where K: std::fmt::Debug + Core,
-      V: std::fmt::Debug + Core
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        write!(f, "HashMapDelta ")?;
-        let mut buf = f.debug_list();
-        if let Some(d) = &self.0 {
-            buf.entries(d.iter());
-        } else {
-            buf.entries(std::iter::empty::<Vec<EntryDelta<K, V>>>());
+impl<K: Core, V: Core> HashMapDelta<K, V> {
+    /// Order the entry deltas in `self` by their keys using `cmp`.
+    /// Since no two entry deltas in `self` share a key, this doesn't
+    /// affect the result of applying `self`, but it does make e.g. its
+    /// serialized form deterministic.  Unlike most methods, this doesn't
+    /// require `K: Ord`.
+    pub fn sort_keys_by<F>(&mut self, mut cmp: F)
+    where F: FnMut(&K, &K) -> std::cmp::Ordering {
+        if let Some(changes) = &mut self.0 {
+            changes.sort_by(|lhs, rhs| cmp(lhs.key(), rhs.key()));
         }
-        buf.finish()
     }
 }
 
+/// An `Iterator` over the `EntryDelta`s of `lhs --[delta]--> rhs`,
+/// as returned by `HashMapDelta::diff_streaming()`.
+pub struct StreamingMapDiff<'m, K, V, L, R> {
+    /// The sorted entries of `lhs` and `rhs`, from which each pass starts.
+    lhs: L,
+    rhs: R,
+    /// The remaining entries of `lhs` and `rhs` in the current pass.
+    lpass: L,
+    rpass: R,
+    /// The entries of `lhs` and `rhs` that have been taken from `lpass`
+    /// and `rpass`, but not processed yet.
+    lnext: Option<(&'m K, &'m V)>,
+    rnext: Option<(&'m K, &'m V)>,
+    /// The last keys of `lhs` and `rhs` processed in the current pass.
+    lprev: Option<&'m K>,
+    rprev: Option<&'m K>,
+    /// The kind of `EntryDelta`s yielded by the current pass over the
+    /// entries, or `None` if all passes are done.
+    pass: Option<EntryKind>,
+}
 
-#[derive(Clone, PartialEq)]
-#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
-pub enum EntryDelta<K, V: Core> {
-    /// Edit a `value` of a given `key`
-    Edit { key: K, value: <V as Core>::Delta },
-    /// Add a given `key` and `value` entry.
-    Add { key: K, value: <V as Core>::Delta },
-    /// Remove the entry with a given `key` from the map.
-    Remove { key: K },
+impl<'m, K, V, L, R> StreamingMapDiff<'m, K, V, L, R>
+where K: Ord,
+      L: Iterator<Item = (&'m K, &'m V)>,
+      R: Iterator<Item = (&'m K, &'m V)>,
+{
+    /// Take the next entry from `pass` into `next` if that's empty, and
+    /// ensure that its key is greater than the key in `prev`.
+    fn peek<I>(
+        pass: &mut I,
+        next: &mut Option<(&'m K, &'m V)>,
+        prev: Option<&'m K>,
+    ) -> DeltaResult<Option<(&'m K, &'m V)>>
+    where I: Iterator<Item = (&'m K, &'m V)> {
+        if next.is_none() {
+            *next = pass.next();
+        }
+        if let (Some((key, _)), Some(prev)) = (*next, prev) {
+            ensure!(prev < key, "the keys must be strictly ascending")?;
+        }
+        Ok(*next)
+    }
 }
 
-impl<K, V> std::fmt::Debug for EntryDelta<K, V>
-where K: std::fmt::Debug,
-      V: std::fmt::Debug + Core
+impl<'m, K, V, L, R> Iterator for StreamingMapDiff<'m, K, V, L, R>
+where K: Clone + Debug + PartialEq + Ord + Hash + Delta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      L: Iterator<Item = (&'m K, &'m V)> + Clone,
+      R: Iterator<Item = (&'m K, &'m V)> + Clone,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        match &self {
-            Self::Edit { key, value } => f.debug_struct("Edit")
-                .field("key", key)
-                .field("value", value)
-                .finish(),
-            Self::Add { key, value } => f.debug_struct("Add")
-                .field("key", key)
-                .field("value", value)
-                .finish(),
-            Self::Remove { key } => f.debug_struct("Remove")
-                .field("key", key)
-                .finish(),
+    type Item = DeltaResult<EntryDelta<K, V>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use std::cmp::Ordering;
+        loop {
+            let pass: EntryKind = self.pass?;
+            let lentry = Self::peek(&mut self.lpass, &mut self.lnext, self.lprev);
+            let rentry = Self::peek(&mut self.rpass, &mut self.rnext, self.rprev);
+            let (lentry, rentry) = match (lentry, rentry) {
+                (Ok(lentry), Ok(rentry)) => (lentry, rentry),
+                (Err(err), _) | (_, Err(err)) => {
+                    self.pass = None;
+                    return Some(Err(err));
+                },
+            };
+            let ordering: Ordering = match (lentry, rentry) {
+                (None, None) => {
+                    // NOTE: Start the next pass, if any:
+                    self.pass = match pass {
+                        EntryKind::Edit => Some(EntryKind::Add),
+                        EntryKind::Add => Some(EntryKind::Remove),
+                        EntryKind::Remove => None,
+                    };
+                    self.lpass = self.lhs.clone();
+                    self.rpass = self.rhs.clone();
+                    self.lprev = None;
+                    self.rprev = None;
+                    continue;
+                },
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some((lkey, _)), Some((rkey, _))) => lkey.cmp(rkey),
+            };
+            if ordering != Ordering::Greater {
+                self.lprev = self.lnext.take().map(|(key, _)| key);
+            }
+            if ordering != Ordering::Less {
+                self.rprev = self.rnext.take().map(|(key, _)| key);
+            }
+            match (ordering, lentry, rentry) {
+                (Ordering::Equal, Some((key, lval)), Some((_, rval))) => {
+                    if pass == EntryKind::Edit && lval != rval {
+                        return Some(lval.delta(rval).map(|value| {
+                            EntryDelta::Edit { key: key.clone(), value }
+                        }));
+                    }
+                },
+                (Ordering::Less, Some((key, _)), _) => {
+                    if pass == EntryKind::Remove {
+                        return Some(Ok(EntryDelta::Remove { key: key.clone() }));
+                    }
+                },
+                (Ordering::Greater, _, Some((key, rval))) => {
+                    if pass == EntryKind::Add {
+                        return Some(rval.clone().into_delta().map(|value| {
+                            EntryDelta::Add { key: key.clone(), value }
+                        }));
+                    }
+                },
+                _ => return Some(bug_detected!()),
+            }
         }
     }
 }
 
+/// A `HashMapDelta` in which the keys that receive the same change share
+/// a single entry, as returned by `HashMapDelta::grouped()`.
+#[derive(Clone, Debug, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct GroupedHashMapDelta<K, V: Core>(pub Vec<GroupedEntryDelta<K, V>>);
 
+#[derive(Clone, Debug, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub enum GroupedEntryDelta<K, V: Core> {
+    /// Edit the values of the given `keys` with the same `value` delta.
+    Edit { keys: Vec<K>, value: <V as Core>::Delta },
+    /// Add an entry with the same `value` for each of the given `keys`.
+    Add { keys: Vec<K>, value: <V as Core>::Delta },
+    /// Remove the entries with the given `keys` from the map.
+    Remove { keys: Vec<K> },
+}
 
-#[allow(non_snake_case)]
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::HashMap;
+impl<K, V> GroupedHashMapDelta<K, V>
+where K: Clone + Debug + PartialEq + Ord + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    /// Expand `self` back into a `HashMapDelta` with one
+    /// entry delta per key, e.g. to apply it.
+    pub fn ungrouped(self) -> HashMapDelta<K, V> {
+        let mut changes: Vec<EntryDelta<K, V>> = vec![];
+        for group in self.0 { match group {
+            GroupedEntryDelta::Edit { keys, value } => changes.extend(
+                keys.into_iter()
+                    .map(|key| EntryDelta::Edit { key, value: value.clone() })
+            ),
+            GroupedEntryDelta::Add { keys, value } => changes.extend(
+                keys.into_iter()
+                    .map(|key| EntryDelta::Add { key, value: value.clone() })
+            ),
+            GroupedEntryDelta::Remove { keys } => changes.extend(
+                keys.into_iter().map(|key| EntryDelta::Remove { key })
+            ),
+        }}
+        HashMapDelta(if !changes.is_empty() {
+            Some(changes)
+        } else {
+            None
+        })
+    }
+}
 
-    macro_rules! map {
-        ($($key:expr => $val:expr),* $(,)?) => {{
-            let mut map = HashMap::new();
-            $( map.insert($key, $val); )*
-                map
+impl<K, V> HashMapDelta<K, V>
+where K: Clone + Debug + PartialEq + Ord + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    /// Apply `delta` to `base` such that re-applying an already-applied
+    /// `delta` is a no-op rather than an error, which makes it suitable
+    /// for at-least-once delivery. Specifically:
+    /// - Edits are applied only if applying them to their own result
+    ///   leaves it unchanged, e.g. replacing a value, which is what makes
+    ///   re-applying them a no-op.  Other edits, e.g. appending to a
+    ///   `String` or pushing onto a `Vec`, result in a
+    ///   `DeltaError::FailedToApplyDelta`.
+    /// - Adds of a key that is already present with an equal value are skipped.
+    /// - Removes of an absent key are skipped.
+    ///
+    /// An edit of an absent key is still an error, since no number of
+    /// applications of `delta` can make that key present.
+    pub fn apply_delta_idempotent(
+        base: &HashMap<K, V>,
+        delta: Self,
+    ) -> DeltaResult<HashMap<K, V>> {
+        let mut new: HashMap<K, V> = base.clone();
+        for change in delta.into_iter() { match change {
+            EntryDelta::Edit { key, value } => {
+                let place: &mut V = new.get_mut(&key)
+                    .ok_or_else(|| DeltaError::KeyNotFound(format!("{:?}", key)))?;
+                let new: V = place.apply(value.clone())?;
+                if new.apply(value).as_ref() != Ok(&new) {
+                    return Err(DeltaError::FailedToApplyDelta {
+                        reason: format!("The edit of key {:?} isn't idempotent", key),
+                    });
+                }
+                *place = new;
+            },
+            EntryDelta::Add { key, value } => {
+                let value: V = <V>::from_delta(value)?;
+                if new.get(&key) != Some(&value) { new.insert(key, value); }
+            },
+            EntryDelta::Remove { key } => {
+                new.remove(&key);
+            },
         }}
+        Ok(new)
     }
 
-    #[test]
-    fn HashMap__delta__same_values() -> DeltaResult<()> {
-        let map0: HashMap<String, usize> = map! {
-            "bar".into()     => 300usize,
-            "foo".into()     =>  42usize,
-            "floozie".into() =>  0usize,
-            "quux".into()    => 16000usize,
-        };
-        let map1: HashMap<String, usize> = map! {
-            "bar".into()     => 300usize,
-            "foo".into()     =>  42usize,
-            "floozie".into() =>  0usize,
-            "quux".into()    => 16000usize,
-        };
-        let delta = map0.delta(&map1)?;
-        let expected = HashMapDelta(None);
-        assert_eq!(delta, expected);
-        let map2 = map0.apply(delta)?;
-        assert_eq!(map0, map2);
-        assert_eq!(map1, map2);
+    /// Apply `delta` to `base`, and also return statistics on the number
+    /// of entries that were added, removed and modified in the process.
+    /// An `EntryDelta::Add` of a key that is already present in `base`
+    /// replaces its value, and thus counts as a modification.
+    pub fn apply_delta_stats(
+        base: &HashMap<K, V>,
+        delta: Self,
+    ) -> DeltaResult<(HashMap<K, V>, MapApplyStats)> {
+        let mut new: HashMap<K, V> = base.clone();
+        let mut stats = MapApplyStats::default();
+        for change in delta.into_iter() {
+            match &change {
+                EntryDelta::Edit { .. } => stats.modified += 1,
+                EntryDelta::Add { key, .. } if new.contains_key(key) =>
+                    stats.modified += 1,
+                EntryDelta::Add { .. } => stats.added += 1,
+                EntryDelta::Remove { .. } => stats.removed += 1,
+            }
+            apply_entry_delta(&mut new, change)?;
+        }
+        Ok((new, stats))
+    }
 
-        let delta = map1.delta(&map0)?;
-        assert_eq!(delta, HashMapDelta(None));
-        let map3 = map1.apply(delta)?;
-        assert_eq!(map0, map3);
-        assert_eq!(map1, map3);
+    /// Apply `delta` to `base`, calling `on_change` with the key and
+    /// the kind of each change right after it has been applied, e.g. to
+    /// invalidate cached data derived from the entry with that key.  Like
+    /// in `apply_delta_stats()`, an `EntryDelta::Add` of a key that is
+    /// already present in `base` counts as a modification.
+    pub fn apply_delta_with<F>(
+        base: &HashMap<K, V>,
+        delta: Self,
+        mut on_change: F,
+    ) -> DeltaResult<HashMap<K, V>>
+    where F: FnMut(&K, ChangeKind) {
+        let mut new: HashMap<K, V> = base.clone();
+        for change in delta.into_iter() {
+            let key: K = change.key().clone();
+            let kind: ChangeKind = match &change {
+                EntryDelta::Edit { .. } => ChangeKind::Modified,
+                EntryDelta::Add { key, .. } if new.contains_key(key) =>
+                    ChangeKind::Modified,
+                EntryDelta::Add { .. } => ChangeKind::Added,
+                EntryDelta::Remove { .. } => ChangeKind::Removed,
+            };
+            apply_entry_delta(&mut new, change)?;
+            on_change(&key, kind);
+        }
+        Ok(new)
+    }
 
+    /// Apply as much of `delta` to `base` as possible, for best-effort
+    /// synchronization.  Rather than aborting at the first change that
+    /// fails to apply, e.g. an edit of an absent key, such a change is
+    /// skipped and its error collected, while all other changes apply.
+    /// A skipped change leaves its entry exactly as it was.
+    pub fn apply_delta_lenient(
+        base: &HashMap<K, V>,
+        delta: Self,
+    ) -> (HashMap<K, V>, Vec<DeltaError>) {
+        let mut new: HashMap<K, V> = base.clone();
+        let mut errors: Vec<DeltaError> = vec![];
+        for change in delta.into_iter() {
+            let result: DeltaResult<()> = match change {
+                // NOTE: Applying an edit in place may leave the value partly
+                //       updated on error, so the edited value only replaces
+                //       it once the whole edit succeeded:
+                EntryDelta::Edit { key, value } => match new.get_mut(&key) {
+                    Some(place) => place.apply(value).map(|value| *place = value),
+                    None => Err(DeltaError::KeyNotFound(format!("{:?}", key))),
+                },
+                change => apply_entry_delta(&mut new, change),
+            };
+            if let Err(error) = result { errors.push(error); }
+        }
+        (new, errors)
+    }
+
+    /// Send the changes in `self` over `sender` one by one, e.g. to
+    /// another thread that applies them with `apply_ops_from()`.  This
+    /// fails if the receiving side hung up, returning the unsent change.
+    pub fn send_ops(
+        self,
+        sender: &Sender<EntryDelta<K, V>>,
+    ) -> Result<(), SendError<EntryDelta<K, V>>> {
+        for change in self.into_iter() {
+            sender.send(change)?;
+        }
         Ok(())
     }
 
-    #[test]
-    fn HashMap__delta__different_values() -> DeltaResult<()> {
-        let map0: HashMap<String, usize> = map! {
-            "bar".into()     => 300usize,
-            "foo".into()     =>  42usize,
-            "floozie".into() =>  0usize,
-            "quux".into()    => 16000usize,
-        };
-        let map1: HashMap<String, usize> = map! {
-            "bar".into()  =>   350usize,
-            "baz".into()  =>  9000usize,
-            "foo".into()  =>    42usize,
-            "quux".into() => 16000usize,
-        };
-        let delta0 = map0.delta(&map1)?;
-        let expected = HashMapDelta(Some(vec![
-            EntryDelta::Edit { key: "bar".into(),  value:   350usize.into_delta()? },
-            EntryDelta::Add  { key: "baz".into(),  value:  9000usize.into_delta()? },
-            EntryDelta::Remove { key: "floozie".into() },
-        ]));
+    /// Apply the changes received over `receiver` to `base` as they come
+    /// in, until all senders have hung up.  This is the receiving side
+    /// of `send_ops()`, and yields the same result as applying the sent
+    /// delta to `base` directly.
+    pub fn apply_ops_from(
+        receiver: &Receiver<EntryDelta<K, V>>,
+        base: &HashMap<K, V>,
+    ) -> DeltaResult<HashMap<K, V>> {
+        let mut new: HashMap<K, V> = base.clone();
+        for change in receiver.iter() {
+            apply_entry_delta(&mut new, change)?;
+        }
+        Ok(new)
+    }
+
+    /// Rebase `self`, a delta calculated against `original_base`, onto
+    /// `new_base`, e.g. to merge concurrent edits of the same map.  A change
+    /// to a key whose entry is the same in both bases is kept as-is, and
+    /// one whose result is already present in `new_base` is dropped.  Any
+    /// other change targets an entry that diverged between the two bases,
+    /// which results in a `DeltaError::Conflict` for its key.  So does an
+    /// edit of an entry that's missing from either base, as there's no
+    /// value in it to edit.
+    pub fn rebase_onto(
+        &self,
+        original_base: &HashMap<K, V>,
+        new_base: &HashMap<K, V>,
+    ) -> DeltaResult<Self> {
+        let mut changes: Vec<EntryDelta<K, V>> = vec![];
+        for change in self.iter() {
+            let key: &K = change.key();
+            let (original, current) = (original_base.get(key), new_base.get(key));
+            let conflict = || DeltaError::Conflict {
+                index: None,
+                key: Some(format!("{:?}", key)),
+            };
+            if let EntryDelta::Edit { .. } = change {
+                if current.is_none() { return Err(conflict()) }
+            }
+            if original == current {
+                changes.push(change.clone());
+                continue;
+            }
+            let target: Option<V> = match change {
+                EntryDelta::Edit { value, .. } =>
+                    Some(original.ok_or_else(conflict)?.apply(value.clone())?),
+                EntryDelta::Add { value, .. } => Some(<V>::from_delta(value.clone())?),
+                EntryDelta::Remove { .. } => None,
+            };
+            if current != target.as_ref() {
+                return Err(conflict());
+            }
+        }
+        Ok(HashMapDelta(if !changes.is_empty() {
+            Some(changes)
+        } else {
+            None
+        }))
+    }
+}
+
+/// The net effect on a single key of a sequence of `EntryDelta`s,
+/// as accumulated by `HashMapDelta::compose()`.
+enum NetChange<V: Core> {
+    /// The entry, which must be present beforehand, is edited by each
+    /// of the deltas, in order.
+    Edits(Vec<<V as Core>::Delta>),
+    /// The entry ends up with the given value.  If `replaces` is `true`,
+    /// the entry must be present beforehand.
+    Set { value: <V as Core>::Delta, replaces: bool },
+    /// The entry, which must be present beforehand, ends up removed.
+    Removed,
+    /// The entry is set to the given value, and then removed.
+    AddedThenRemoved(<V as Core>::Delta),
+}
+
+/// A `HashMapDelta` in which each entry delta is stamped with the
+/// generation (e.g. a logical timestamp) of the write that produced it,
+/// as returned by `HashMapDelta::stamped()`.  Concurrent stamped deltas
+/// can be merged with last-writer-wins semantics.
+#[derive(Clone, Debug, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct StampedHashMapDelta<K, V: Core>(pub Vec<StampedEntryDelta<K, V>>);
+
+#[derive(Clone, Debug, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct StampedEntryDelta<K, V: Core> {
+    pub generation: u64,
+    pub change: EntryDelta<K, V>,
+}
+
+impl<K, V> HashMapDelta<K, V>
+where K: Clone + Debug + PartialEq + Ord + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    /// Stamp each entry delta of `self` with `generation`.
+    pub fn stamped(self, generation: u64) -> StampedHashMapDelta<K, V> {
+        StampedHashMapDelta(self.into_iter()
+            .map(|change| StampedEntryDelta { generation, change })
+            .collect())
+    }
+}
+
+impl<K, V> StampedHashMapDelta<K, V>
+where K: Clone + Debug + PartialEq + Ord + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    /// Merge `self` and `other` using last-writer-wins semantics: for
+    /// each key changed by both, only the changes from the delta with the
+    /// highest generation for that key are kept.  Keys changed by only
+    /// one of them keep their changes.  If both have the same generation
+    /// for a key, `self` wins, so generations should be unique (e.g. by
+    /// combining a clock with a replica ID) for the result to not depend
+    /// on the order of the arguments.  The result is sorted by key.
+    pub fn merge(self, other: Self) -> Self {
+        let max_generations = |delta: &Self| {
+            let mut generations: HashMap<K, u64> = HashMap::new();
+            for stamped in delta.0.iter() {
+                let generation = generations.entry(stamped.change.key().clone())
+                    .or_insert(stamped.generation);
+                *generation = u64::max(*generation, stamped.generation);
+            }
+            generations
+        };
+        let (lgens, rgens) = (max_generations(&self), max_generations(&other));
+        let lchanges = self.0.into_iter().filter(|stamped| {
+            let key: &K = stamped.change.key();
+            match rgens.get(key) {
+                Some(rgen) => lgens[key] >= *rgen,
+                None => true,
+            }
+        });
+        let rchanges = other.0.into_iter().filter(|stamped| {
+            let key: &K = stamped.change.key();
+            match lgens.get(key) {
+                Some(lgen) => rgens[key] > *lgen,
+                None => true,
+            }
+        });
+        let mut changes: Vec<StampedEntryDelta<K, V>> =
+            lchanges.chain(rchanges).collect();
+        // NOTE: The sort is stable, so the changes to each key stay in order:
+        changes.sort_by(|l, r| l.change.key().cmp(r.change.key()));
+        StampedHashMapDelta(changes)
+    }
+
+    /// Strip the generations from `self`, e.g. to apply it.
+    pub fn unstamped(self) -> HashMapDelta<K, V> {
+        let changes: Vec<EntryDelta<K, V>> = self.0.into_iter()
+            .map(|stamped| stamped.change)
+            .collect();
+        HashMapDelta(if !changes.is_empty() {
+            Some(changes)
+        } else {
+            None
+        })
+    }
+}
+
+#[cfg(feature = "content-hash")]
+impl<K, V> HashMapDelta<K, V>
+where K: Clone + Debug + PartialEq + Ord + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    /// Calculate a hash of the contents of `self` that is stable across
+    /// runs, platforms and compiler versions, e.g. to address deltas in a
+    /// content-addressed store.  It is the 64-bit FNV-1a hash of the
+    /// postcard encoding of `self`.  Since the deltas calculated by
+    /// `delta()` are ordered by key, they hash the same regardless of the
+    /// order in which the entries of either map were inserted.
+    ///
+    /// This requires the `content-hash` feature, which enables the
+    /// `postcard` feature for the encoding.
+    pub fn content_hash(&self) -> DeltaResult<u64> {
+        use crate::PostcardCodec;
+        use crate::fnv::FnvHasher;
+        use std::hash::Hasher;
+        let mut hasher = FnvHasher::default();
+        hasher.write(&self.to_postcard()?);
+        Ok(hasher.finish())
+    }
+}
+
+/// The number of entries affected by applying a `HashMapDelta`,
+/// as returned by `HashMapDelta::apply_delta_stats()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MapApplyStats {
+    pub added: usize,
+    pub removed: usize,
+    pub modified: usize,
+}
+
+/// The kind of change made to an entry by applying a `HashMapDelta`,
+/// as reported by `HashMapDelta::apply_delta_with()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl<K, V> std::fmt::Display for HashMapDelta<K, V>
+where K: Core,
+      V: Core
+{
+    /// Summarize `self` as `+added -removed ~edited` entry counts.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let (mut added, mut removed, mut edited) = (0, 0, 0);
+        for change in self.0.iter().flatten() { match change {
+            EntryDelta::Edit { .. } => edited += 1,
+            EntryDelta::Add { .. } => added += 1,
+            EntryDelta::Remove { .. } => removed += 1,
+        }}
+        write!(f, "+{} -{} ~{}", added, removed, edited)
+    }
+}
+
+impl<K: Core, V: Core> DeltaOps for HashMapDelta<K, V> {
+    fn is_empty(&self) -> bool {
+        match &self.0 {
+            Some(changes) => changes.is_empty(),
+            None => true,
+        }
+    }
+}
+
+impl<K, V> Compose for HashMapDelta<K, V>
+where K: Clone + Debug + PartialEq + Ord + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Apply + FromDelta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    /// Combine `self` and `next` into a single delta that has the same
+    /// effect as applying `self` and then `next`, on any map that both
+    /// apply to in sequence.  The changes to each key are merged into at
+    /// most one edit, add or remove, except for consecutive edits (which
+    /// can't be merged without the value they apply to) and the few cases
+    /// where a key is both added and removed.  The result is ordered like
+    /// the deltas calculated by `delta()`, with the edits, then the adds
+    /// and then the removes each sorted by key.
+    ///
+    /// Together with `HashMapDelta::empty()`, this forms a monoid: `compose`
+    /// is associative, in that both groupings of 3 deltas have the same
+    /// effect on any map, and `empty()` is its identity for any delta that
+    /// is already merged, e.g. one calculated by `delta()`.  This means
+    /// that a stream of deltas can be folded into one.  Composing a delta that
+    /// can never be applied, e.g. an edit of a key that was just removed,
+    /// results in a `DeltaError::KeyNotFound`.
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        let mut net: BTreeMap<K, NetChange<V>> = BTreeMap::new();
+        for change in self.iter().chain(next.iter()).cloned() {
+            let key: K = change.key().clone();
+            let next: NetChange<V> = match (net.remove(&key), change) {
+                (None, EntryDelta::Edit { value, .. }) =>
+                    NetChange::Edits(vec![value]),
+                (Some(NetChange::Edits(mut values)), EntryDelta::Edit { value, .. }) => {
+                    values.push(value);
+                    NetChange::Edits(values)
+                },
+                (Some(NetChange::Set { value: set, replaces }), EntryDelta::Edit { value, .. }) => {
+                    let value = <V>::from_delta(set)?.apply(value)?.into_delta()?;
+                    NetChange::Set { value, replaces }
+                },
+                (None, EntryDelta::Add { value, .. })
+                    | (Some(NetChange::AddedThenRemoved(_)), EntryDelta::Add { value, .. }) =>
+                    NetChange::Set { value, replaces: false },
+                (Some(NetChange::Set { replaces, .. }), EntryDelta::Add { value, .. }) =>
+                    NetChange::Set { value, replaces },
+                (Some(NetChange::Edits(_)), EntryDelta::Add { value, .. })
+                    | (Some(NetChange::Removed), EntryDelta::Add { value, .. }) =>
+                    NetChange::Set { value, replaces: true },
+                (None, EntryDelta::Remove { .. })
+                    | (Some(NetChange::Edits(_)), EntryDelta::Remove { .. })
+                    | (Some(NetChange::Set { replaces: true, .. }), EntryDelta::Remove { .. }) =>
+                    NetChange::Removed,
+                (Some(NetChange::Set { value, replaces: false }), EntryDelta::Remove { .. }) =>
+                    NetChange::AddedThenRemoved(value),
+                (Some(NetChange::Removed), _)
+                    | (Some(NetChange::AddedThenRemoved(_)), _) =>
+                    return Err(DeltaError::KeyNotFound(format!("{:?}", key))),
+            };
+            net.insert(key, next);
+        }
+        let (mut edits, mut adds, mut removes) = (vec![], vec![], vec![]);
+        // NOTE: An entry that is replaced must be removed before it's added:
+        let mut replacements = vec![];
+        for (key, change) in net { match change {
+            NetChange::Edits(values) => edits.extend(values.into_iter()
+                .map(|value| EntryDelta::Edit { key: key.clone(), value })),
+            NetChange::Set { value, replaces: false } =>
+                adds.push(EntryDelta::Add { key, value }),
+            NetChange::Set { value, replaces: true } => {
+                removes.push(EntryDelta::Remove { key: key.clone() });
+                replacements.push(EntryDelta::Add { key, value });
+            },
+            NetChange::Removed => removes.push(EntryDelta::Remove { key }),
+            NetChange::AddedThenRemoved(value) => {
+                adds.push(EntryDelta::Add { key: key.clone(), value });
+                removes.push(EntryDelta::Remove { key });
+            },
+        }}
+        let changes: Vec<EntryDelta<K, V>> = edits.into_iter()
+            .chain(adds)
+            .chain(removes)
+            .chain(replacements)
+            .collect();
+        Ok(HashMapDelta(if !changes.is_empty() {
+            Some(changes)
+        } else {
+            None
+        }))
+    }
+}
+
+impl<K, V> Invert<HashMap<K, V>> for HashMapDelta<K, V>
+where K: Core, V: Core,
+      HashMap<K, V>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &HashMap<K, V>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
+impl<K, V> std::fmt::Debug for HashMapDelta<K, V>
+where K: std::fmt::Debug + Core,
+      V: std::fmt::Debug + Core
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "HashMapDelta ")?;
+        let mut buf = f.debug_list();
+        if let Some(d) = &self.0 {
+            buf.entries(d.iter());
+        } else {
+            buf.entries(std::iter::empty::<Vec<EntryDelta<K, V>>>());
+        }
+        buf.finish()
+    }
+}
+
+
+#[derive(Clone, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub enum EntryDelta<K, V: Core> {
+    /// Edit a `value` of a given `key`
+    Edit { key: K, value: <V as Core>::Delta },
+    /// Add a given `key` and `value` entry.
+    Add { key: K, value: <V as Core>::Delta },
+    /// Remove the entry with a given `key` from the map.
+    Remove { key: K },
+}
+
+impl<K, V: Core> EntryDelta<K, V> {
+    /// Return the key of the entry that `self` applies to.
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Edit { key, .. } => key,
+            Self::Add { key, .. } => key,
+            Self::Remove { key } => key,
+        }
+    }
+}
+
+impl<K, V> std::fmt::Debug for EntryDelta<K, V>
+where K: std::fmt::Debug,
+      V: std::fmt::Debug + Core
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match &self {
+            Self::Edit { key, value } => f.debug_struct("Edit")
+                .field("key", key)
+                .field("value", value)
+                .finish(),
+            Self::Add { key, value } => f.debug_struct("Add")
+                .field("key", key)
+                .field("value", value)
+                .finish(),
+            Self::Remove { key } => f.debug_struct("Remove")
+                .field("key", key)
+                .finish(),
+        }
+    }
+}
+
+
+
+impl<K, V> HashMapDelta<K, V>
+where K: Clone + Debug + PartialEq + Ord + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    /// Deserialize a `HashMapDelta` from `deserializer`, keeping only the
+    /// changes to the given `keys`.  The values of all other changes are
+    /// skipped rather than deserialized, which saves work when only a few
+    /// keys of a large delta are of interest.  Formats that aren't
+    /// self-describing can't skip a value without knowing its type, so
+    /// there the values of the other changes are still deserialized, but
+    /// then dropped rather than collected.
+    pub fn deserialize_keys<'de, D>(
+        deserializer: D,
+        keys: &[K],
+    ) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+        let keys: BTreeSet<&K> = keys.iter().collect();
+        deserializer.deserialize_newtype_struct(
+            "HashMapDelta",
+            KeysVisitor { keys: &keys, marker: std::marker::PhantomData },
+        )
+    }
+}
+
+/// The variants of a serialized `EntryDelta`.
+#[derive(Clone, Copy, PartialEq, serde_derive::Deserialize)]
+enum EntryKind { Edit, Add, Remove }
+
+/// The fields of a serialized `EntryDelta`.
+#[derive(serde_derive::Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum EntryField { Key, Value }
+
+/// Visits a serialized `HashMapDelta`, i.e. an optional sequence
+/// of `EntryDelta`s, keeping only the changes to `keys`.
+struct KeysVisitor<'k, K, V> {
+    keys: &'k BTreeSet<&'k K>,
+    marker: std::marker::PhantomData<V>,
+}
+
+impl<'k, 'de, K, V> Visitor<'de> for KeysVisitor<'k, K, V>
+where K: Ord + Core + Deserialize<'de>,
+      V: Core,
+{
+    type Value = HashMapDelta<K, V>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a HashMapDelta")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where D: Deserializer<'de> {
+        deserializer.deserialize_option(self)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(HashMapDelta(None))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(HashMapDelta(None))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where D: Deserializer<'de> {
+        deserializer.deserialize_seq(self)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where A: SeqAccess<'de> {
+        let mut changes: Vec<EntryDelta<K, V>> = vec![];
+        let seed = || EntrySeed { keys: self.keys, marker: std::marker::PhantomData };
+        while let Some(change) = seq.next_element_seed(seed())? {
+            changes.extend(change);
+        }
+        Ok(HashMapDelta(if !changes.is_empty() {
+            Some(changes)
+        } else {
+            None
+        }))
+    }
+}
+
+/// Deserializes a single `EntryDelta`, or `None` if its key isn't in `keys`.
+struct EntrySeed<'k, K, V> {
+    keys: &'k BTreeSet<&'k K>,
+    marker: std::marker::PhantomData<V>,
+}
+
+impl<'k, 'de, K, V> DeserializeSeed<'de> for EntrySeed<'k, K, V>
+where K: Ord + Deserialize<'de>,
+      V: Core,
+{
+    type Value = Option<EntryDelta<K, V>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where D: Deserializer<'de> {
+        const VARIANTS: &[&str] = &["Edit", "Add", "Remove"];
+        deserializer.deserialize_enum("EntryDelta", VARIANTS, self)
+    }
+}
+
+impl<'k, 'de, K, V> Visitor<'de> for EntrySeed<'k, K, V>
+where K: Ord + Deserialize<'de>,
+      V: Core,
+{
+    type Value = Option<EntryDelta<K, V>>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an EntryDelta")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where A: EnumAccess<'de> {
+        let (kind, variant): (EntryKind, _) = data.variant()?;
+        let fields: &[&str] = match kind {
+            EntryKind::Edit | EntryKind::Add => &["key", "value"],
+            EntryKind::Remove => &["key"],
+        };
+        let visitor = FieldsVisitor { keys: self.keys, kind, marker: self.marker };
+        variant.struct_variant(fields, visitor)
+    }
+}
+
+/// Visits the fields of a serialized `EntryDelta` of the given `kind`.
+struct FieldsVisitor<'k, K, V> {
+    keys: &'k BTreeSet<&'k K>,
+    kind: EntryKind,
+    marker: std::marker::PhantomData<V>,
+}
+
+impl<'k, K, V: Core> FieldsVisitor<'k, K, V> {
+    fn entry(self, key: K, value: Option<<V as Core>::Delta>) -> Option<EntryDelta<K, V>> {
+        match self.kind {
+            EntryKind::Edit => value.map(|value| EntryDelta::Edit { key, value }),
+            EntryKind::Add => value.map(|value| EntryDelta::Add { key, value }),
+            EntryKind::Remove => Some(EntryDelta::Remove { key }),
+        }
+    }
+}
+
+impl<'k, 'de, K, V> Visitor<'de> for FieldsVisitor<'k, K, V>
+where K: Ord + Deserialize<'de>,
+      V: Core,
+{
+    type Value = Option<EntryDelta<K, V>>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the fields of an EntryDelta")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where A: SeqAccess<'de> {
+        let key: K = seq.next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        if !self.keys.contains(&key) {
+            // NOTE: Struct variants are visited as a sequence by formats
+            //       that aren't self-describing, which can't skip values
+            //       with `IgnoredAny`:
+            if let EntryKind::Edit | EntryKind::Add = self.kind {
+                seq.next_element::<<V as Core>::Delta>()?;
+            }
+            return Ok(None);
+        }
+        let value: Option<<V as Core>::Delta> = match self.kind {
+            EntryKind::Edit | EntryKind::Add => Some(seq.next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?),
+            EntryKind::Remove => None,
+        };
+        Ok(self.entry(key, value))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where A: MapAccess<'de> {
+        let (mut key, mut value): (Option<K>, Option<<V as Core>::Delta>) = (None, None);
+        while let Some(field) = map.next_key::<EntryField>()? { match field {
+            EntryField::Key => key = Some(map.next_value()?),
+            // NOTE: Only skip the value once the key is known to be
+            //       uninteresting; otherwise it must be kept:
+            EntryField::Value => match &key {
+                Some(key) if !self.keys.contains(key) => {
+                    map.next_value::<IgnoredAny>()?;
+                },
+                _ => value = Some(map.next_value()?),
+            },
+        }}
+        let key: K = key.ok_or_else(|| de::Error::missing_field("key"))?;
+        if !self.keys.contains(&key) { return Ok(None) }
+        match (&self.kind, &value) {
+            (EntryKind::Edit, None) | (EntryKind::Add, None) =>
+                Err(de::Error::missing_field("value")),
+            _ => Ok(self.entry(key, value)),
+        }
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    macro_rules! map {
+        ($($key:expr => $val:expr),* $(,)?) => {{
+            let mut map = HashMap::new();
+            $( map.insert($key, $val); )*
+                map
+        }}
+    }
+
+    #[test]
+    fn HashMap__delta__same_values() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
+            "bar".into()     => 300usize,
+            "foo".into()     =>  42usize,
+            "floozie".into() =>  0usize,
+            "quux".into()    => 16000usize,
+        };
+        let map1: HashMap<String, usize> = map! {
+            "bar".into()     => 300usize,
+            "foo".into()     =>  42usize,
+            "floozie".into() =>  0usize,
+            "quux".into()    => 16000usize,
+        };
+        let delta = map0.delta(&map1)?;
+        let expected = HashMapDelta(None);
+        assert_eq!(delta, expected);
+        let map2 = map0.apply(delta)?;
+        assert_eq!(map0, map2);
+        assert_eq!(map1, map2);
+
+        let delta = map1.delta(&map0)?;
+        assert_eq!(delta, HashMapDelta(None));
+        let map3 = map1.apply(delta)?;
+        assert_eq!(map0, map3);
+        assert_eq!(map1, map3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn HashMap__delta__different_values() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
+            "bar".into()     => 300usize,
+            "foo".into()     =>  42usize,
+            "floozie".into() =>  0usize,
+            "quux".into()    => 16000usize,
+        };
+        let map1: HashMap<String, usize> = map! {
+            "bar".into()  =>   350usize,
+            "baz".into()  =>  9000usize,
+            "foo".into()  =>    42usize,
+            "quux".into() => 16000usize,
+        };
+        let delta0 = map0.delta(&map1)?;
+        let expected = HashMapDelta(Some(vec![
+            EntryDelta::Edit { key: "bar".into(),  value:   350usize.into_delta()? },
+            EntryDelta::Add  { key: "baz".into(),  value:  9000usize.into_delta()? },
+            EntryDelta::Remove { key: "floozie".into() },
+        ]));
         assert_eq!(delta0, expected);
         let map2 = map0.apply(delta0)?;
         assert_eq!(map1, map2);
 
-        let delta1 = map1.delta(&map0)?;
-        assert_eq!(delta1, HashMapDelta(Some(vec![
-            EntryDelta::Edit { key: "bar".into(),     value: 300usize.into_delta()? },
-            EntryDelta::Add  { key: "floozie".into(), value:   0usize.into_delta()? },
-            EntryDelta::Remove { key: "baz".into() },
-        ])));
-        let map3 = map1.apply(delta1)?;
-        assert_eq!(map0, map3);
+        let delta1 = map1.delta(&map0)?;
+        assert_eq!(delta1, HashMapDelta(Some(vec![
+            EntryDelta::Edit { key: "bar".into(),     value: 300usize.into_delta()? },
+            EntryDelta::Add  { key: "floozie".into(), value:   0usize.into_delta()? },
+            EntryDelta::Remove { key: "baz".into() },
+        ])));
+        let map3 = map1.apply(delta1)?;
+        assert_eq!(map0, map3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn HashMap__apply__same_values() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
+            "bar".into()     => 300usize,
+            "foo".into()     =>  42usize,
+            "floozie".into() =>  0usize,
+            "quux".into()    => 16000usize,
+        };
+        let map1: HashMap<String, usize> = map! {
+            "bar".into()     => 300usize,
+            "foo".into()     =>  42usize,
+            "floozie".into() =>  0usize,
+            "quux".into()    => 16000usize,
+        };
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta, HashMapDelta(None));
+        let map2 = map0.apply(delta)?;
+        assert_eq!(map1, map2);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMap__apply__different_values() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
+            "bar".into()     => 300usize,
+            "foo".into()     =>  42usize,
+            "floozie".into() =>  0usize,
+            "quux".into()    => 16000usize,
+        };
+        let map1: HashMap<String, usize> = map! {
+            "bar".into()  =>   350usize,
+            "baz".into()  =>  9000usize,
+            "foo".into()  =>    42usize,
+            "quux".into() => 16000usize,
+        };
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta, HashMapDelta(Some(vec![
+            EntryDelta::Edit { key: "bar".into(),  value:   350usize.into_delta()? },
+            EntryDelta::Add  { key: "baz".into(),  value:  9000usize.into_delta()? },
+            EntryDelta::Remove { key: "floozie".into() },
+        ])));
+        let map2 = map0.apply(delta)?;
+        assert_eq!(map1, map2);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__display() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
+            "bar".into()     => 300usize,
+            "foo".into()     =>  42usize,
+            "floozie".into() =>  0usize,
+            "quux".into()    => 16000usize,
+        };
+        let map1: HashMap<String, usize> = map! {
+            "bar".into()  =>   350usize,
+            "baz".into()  =>  9000usize,
+            "foo".into()  =>    43usize,
+            "quux".into() =>     1usize,
+            "zed".into()  =>     2usize,
+        };
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta.len(), 6);
+        assert!(!delta.is_empty());
+        assert_eq!(format!("{}", delta), "+2 -1 ~3");
 
+        let delta = map0.delta(&map0)?;
+        assert!(delta.is_empty());
+        assert_eq!(format!("{}", delta), "+0 -0 ~0");
         Ok(())
     }
 
     #[test]
-    fn HashMap__apply__same_values() -> DeltaResult<()> {
+    fn HashMap__apply__missing_key() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
+            "bar".into() => 300usize,
+        };
+        let delta = HashMapDelta(Some(vec![
+            EntryDelta::Edit { key: "foo".into(), value: 42usize.into_delta()? },
+        ]));
+        let result = map0.apply(delta);
+        assert_eq!(result, Err(DeltaError::KeyNotFound("\"foo\"".into())));
+
+        let delta = HashMapDelta(Some(vec![
+            EntryDelta::Remove { key: "foo".into() },
+        ]));
+        let result = map0.apply(delta);
+        assert_eq!(result, Err(DeltaError::KeyNotFound("\"foo\"".into())));
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__apply_delta_idempotent() -> DeltaResult<()> {
         let map0: HashMap<String, usize> = map! {
             "bar".into()     => 300usize,
             "foo".into()     =>  42usize,
             "floozie".into() =>  0usize,
-            "quux".into()    => 16000usize,
         };
         let map1: HashMap<String, usize> = map! {
+            "bar".into() =>  350usize,
+            "baz".into() => 9000usize,
+            "foo".into() =>   42usize,
+        };
+        let delta = map0.delta(&map1)?;
+        let map2 = HashMapDelta::apply_delta_idempotent(&map0, delta.clone())?;
+        assert_eq!(map1, map2);
+        let map3 = HashMapDelta::apply_delta_idempotent(&map2, delta.clone())?;
+        assert_eq!(map2, map3);
+        // Ordinary application rejects the redundant removal:
+        assert_eq!(
+            map2.apply(delta),
+            Err(DeltaError::KeyNotFound("\"floozie\"".into()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__apply_delta_idempotent__appending_edits() -> DeltaResult<()> {
+        let map0: HashMap<String, String> = map! {
+            "fox".into() => "The quick brown fox jumps".into(),
+        };
+        let map1: HashMap<String, String> = map! {
+            "fox".into() => "The quick brown fox jumps!".into(),
+        };
+        let delta = map0.delta(&map1)?;
+        assert_eq!(map0.apply(delta.clone())?, map1);
+        // NOTE: The edit's ops don't cover the value it was applied to:
+        assert_eq!(map1.apply(delta.clone()), Err(DeltaError::IllegalDelta { index: 25 }));
+        assert!(matches!(
+            HashMapDelta::apply_delta_idempotent(&map0, delta.clone()),
+            Err(DeltaError::FailedToApplyDelta { .. })
+        ));
+        assert_eq!(
+            HashMapDelta::apply_delta_idempotent(&map1, delta),
+            Err(DeltaError::IllegalDelta { index: 25 })
+        );
+
+        let map0: HashMap<String, Vec<u32>> = map! { "a".into() => vec![1, 2, 3] };
+        let map1: HashMap<String, Vec<u32>> = map! { "a".into() => vec![1, 2, 3, 4] };
+        let delta = map0.delta(&map1)?;
+        assert_eq!(map1.apply(delta.clone())?["a"], vec![1, 2, 3, 4, 4]);
+        assert!(matches!(
+            HashMapDelta::apply_delta_idempotent(&map0, delta.clone()),
+            Err(DeltaError::FailedToApplyDelta { .. })
+        ));
+        assert!(matches!(
+            HashMapDelta::apply_delta_idempotent(&map1, delta),
+            Err(DeltaError::FailedToApplyDelta { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__apply_delta_idempotent__vec_values() -> DeltaResult<()> {
+        let map0: HashMap<String, Vec<u32>> = map! {
+            "a".into() => vec![1, 2, 3],
+            "b".into() => vec![4],
+        };
+        let map1: HashMap<String, Vec<u32>> = map! {
+            "a".into() => vec![1, 9, 3],
+            "b".into() => vec![4],
+        };
+        let delta = map0.delta(&map1)?;
+        let map2 = HashMapDelta::apply_delta_idempotent(&map0, delta.clone())?;
+        assert_eq!(map2, map0.apply(delta.clone())?);
+        assert_eq!(map2, map1);
+        let map3 = HashMapDelta::apply_delta_idempotent(&map2, delta)?;
+        assert_eq!(map3, map1);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__apply_delta_stats() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
             "bar".into()     => 300usize,
             "foo".into()     =>  42usize,
             "floozie".into() =>  0usize,
             "quux".into()    => 16000usize,
         };
+        let map1: HashMap<String, usize> = map! {
+            "bar".into()  =>   350usize,
+            "baz".into()  =>  9000usize,
+            "foo".into()  =>    43usize,
+            "zed".into()  =>     2usize,
+        };
         let delta = map0.delta(&map1)?;
-        assert_eq!(delta, HashMapDelta(None));
-        let map2 = map0.apply(delta)?;
+        let (map2, stats) = HashMapDelta::apply_delta_stats(&map0, delta)?;
         assert_eq!(map1, map2);
+        assert_eq!(stats, MapApplyStats { added: 2, removed: 2, modified: 2 });
+
+        let delta = HashMapDelta(Some(vec![
+            EntryDelta::Add { key: "bar".into(), value: 1usize.into_delta()? },
+        ]));
+        let (_, stats) = HashMapDelta::apply_delta_stats(&map0, delta)?;
+        assert_eq!(stats, MapApplyStats { added: 0, removed: 0, modified: 1 });
         Ok(())
     }
 
     #[test]
-    fn HashMap__apply__different_values() -> DeltaResult<()> {
+    fn HashMapDelta__apply_delta_with() -> DeltaResult<()> {
         let map0: HashMap<String, usize> = map! {
             "bar".into()     => 300usize,
             "foo".into()     =>  42usize,
             "floozie".into() =>  0usize,
-            "quux".into()    => 16000usize,
         };
         let map1: HashMap<String, usize> = map! {
             "bar".into()  =>   350usize,
             "baz".into()  =>  9000usize,
             "foo".into()  =>    42usize,
-            "quux".into() => 16000usize,
         };
         let delta = map0.delta(&map1)?;
+        let expected: Vec<(String, ChangeKind)> = delta.iter()
+            .map(|change| (change.key().clone(), match change {
+                EntryDelta::Edit { .. } => ChangeKind::Modified,
+                EntryDelta::Add { .. } => ChangeKind::Added,
+                EntryDelta::Remove { .. } => ChangeKind::Removed,
+            }))
+            .collect();
+        let mut changes: Vec<(String, ChangeKind)> = vec![];
+        let map2 = HashMapDelta::apply_delta_with(&map0, delta, |key, kind| {
+            changes.push((key.clone(), kind));
+        })?;
+        assert_eq!(map2, map1);
+        assert_eq!(changes, expected);
+        assert_eq!(changes, vec![
+            ("bar".to_string(), ChangeKind::Modified),
+            ("baz".to_string(), ChangeKind::Added),
+            ("floozie".to_string(), ChangeKind::Removed),
+        ]);
+
+        // NOTE: The callback isn't invoked for a change that fails to apply:
+        let delta = HashMapDelta(Some(vec![
+            EntryDelta::Add { key: "bar".into(), value: 1usize.into_delta()? },
+            EntryDelta::Remove { key: "qux".into() },
+        ]));
+        let mut changes: Vec<(String, ChangeKind)> = vec![];
+        let result = HashMapDelta::apply_delta_with(&map0, delta, |key, kind| {
+            changes.push((key.clone(), kind));
+        });
+        assert!(result.is_err());
+        assert_eq!(changes, vec![("bar".to_string(), ChangeKind::Modified)]);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__deserialize_keys() -> DeltaResult<()> {
+        let map0: HashMap<u32, String> = (0 .. 1000)
+            .map(|n| (n, format!("value {}", n)))
+            .collect();
+        let map1: HashMap<u32, String> = (500 .. 1500)
+            .map(|n| (n, format!("value {}", n % 900)))
+            .collect();
+        let delta = map0.delta(&map1)?;
+        let json = serde_json::to_string(&delta).expect("failed to serialize");
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let partial = HashMapDelta::<u32, String>::deserialize_keys(
+            &mut deserializer,
+            &[7, 950],
+        ).expect("failed to deserialize");
+        assert_eq!(partial, HashMapDelta(Some(vec![
+            EntryDelta::Edit {
+                key: 950,
+                value: "value 950".to_string().delta(&"value 50".to_string())?,
+            },
+            EntryDelta::Remove { key: 7 },
+        ])));
+        let expected: Vec<&EntryDelta<u32, String>> = delta.iter()
+            .filter(|change| [7, 950].contains(change.key()))
+            .collect();
+        assert_eq!(partial.iter().collect::<Vec<_>>(), expected);
+
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let partial = HashMapDelta::<u32, String>::deserialize_keys(
+            &mut deserializer,
+            &[5000],
+        ).expect("failed to deserialize");
+        assert_eq!(partial, HashMapDelta(None));
+
+        #[cfg(feature = "postcard")] {
+            let bytes: Vec<u8> = postcard::to_allocvec(&delta)
+                .expect("failed to serialize");
+            let mut deserializer = postcard::Deserializer::from_bytes(&bytes);
+            let partial = HashMapDelta::<u32, String>::deserialize_keys(
+                &mut deserializer,
+                &[7, 950],
+            ).expect("failed to deserialize");
+            assert_eq!(partial.iter().collect::<Vec<_>>(), expected);
+        }
+        Ok(())
+    }
+
+    /// Return a pseudo-random map with keys below `max_key`, where
+    /// `state` is the state of a linear congruential generator.
+    fn random_map(state: &mut u64, max_key: u64) -> HashMap<u64, u64> {
+        let mut next = || {
+            *state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            *state >> 33
+        };
+        let len = next() % max_key;
+        (0 .. len).map(|_| (next() % max_key, next() % 4)).collect()
+    }
+
+    #[test]
+    fn HashMapDelta__compose__monoid_laws() -> DeltaResult<()> {
+        let mut state: u64 = 42;
+        for _ in 0 .. 100 {
+            let maps: Vec<HashMap<u64, u64>> = (0 .. 4)
+                .map(|_| random_map(&mut state, 12))
+                .collect();
+            let (a, b, c) = (
+                maps[0].delta(&maps[1])?,
+                maps[1].delta(&maps[2])?,
+                maps[2].delta(&maps[3])?,
+            );
+            // NOTE: Identity:
+            assert_eq!(Compose::compose(&HashMapDelta::empty(), &a)?, a);
+            assert_eq!(Compose::compose(&a, &HashMapDelta::empty())?, a);
+            // NOTE: Associativity:
+            let ab_c = Compose::compose(&Compose::compose(&a, &b)?, &c)?;
+            let a_bc = Compose::compose(&a, &Compose::compose(&b, &c)?)?;
+            // NOTE: The value of an entry that's added and then removed
+            //       again depends on the grouping, but it never affects
+            //       the result, so compare the effect of the deltas:
+            let other: HashMap<u64, u64> = random_map(&mut state, 12);
+            for map in maps.iter().chain(std::iter::once(&other)) {
+                assert_eq!(map.apply(ab_c.clone()).ok(), map.apply(a_bc.clone()).ok());
+            }
+            // NOTE: Folding equals sequential application:
+            let folded = [&a, &b, &c].iter()
+                .try_fold(HashMapDelta::empty(), |acc, next| acc.compose(next))?;
+            assert_eq!(folded, ab_c);
+            let sequential = maps[0].apply(a)?.apply(b)?.apply(c)?;
+            assert_eq!(sequential, maps[3]);
+            assert_eq!(maps[0].apply(folded)?, maps[3]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__compose__merges_changes() -> DeltaResult<()> {
+        let delta0: HashMapDelta<String, u32> = HashMapDelta(Some(vec![
+            EntryDelta::Add { key: "foo".into(), value: 1u32.into_delta()? },
+            EntryDelta::Add { key: "bar".into(), value: 2u32.into_delta()? },
+        ]));
+        let delta1: HashMapDelta<String, u32> = HashMapDelta(Some(vec![
+            EntryDelta::Edit { key: "foo".into(), value: 10u32.into_delta()? },
+            EntryDelta::Remove { key: "bar".into() },
+            EntryDelta::Remove { key: "baz".into() },
+        ]));
+        assert_eq!(delta0.compose(&delta1)?, HashMapDelta(Some(vec![
+            EntryDelta::Add { key: "bar".into(), value: 2u32.into_delta()? },
+            EntryDelta::Add { key: "foo".into(), value: 10u32.into_delta()? },
+            EntryDelta::Remove { key: "bar".into() },
+            EntryDelta::Remove { key: "baz".into() },
+        ])));
+        let delta2: HashMapDelta<String, u32> = HashMapDelta(Some(vec![
+            EntryDelta::Remove { key: "foo".into() },
+        ]));
+        let delta3: HashMapDelta<String, u32> = HashMapDelta(Some(vec![
+            EntryDelta::Edit { key: "foo".into(), value: 10u32.into_delta()? },
+        ]));
+        assert_eq!(
+            delta2.compose(&delta3),
+            Err(DeltaError::KeyNotFound("\"foo\"".into())),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__compose__later_edit_wins() -> DeltaResult<()> {
+        let map0: HashMap<String, u32> = vec![
+            ("x".to_string(), 1),
+            ("y".to_string(), 2),
+        ].into_iter().collect();
+        let mut map1 = map0.clone();
+        map1.insert("x".into(), 10);
+        let mut map2 = map1.clone();
+        map2.insert("x".into(), 20);
+        let delta0: HashMapDelta<String, u32> = map0.delta(&map1)?;
+        let delta1: HashMapDelta<String, u32> = map1.delta(&map2)?;
+        let composed = delta0.compose(&delta1)?;
+        let replayed: HashMap<String, u32> = map0.apply(composed)?;
+        assert_eq!(replayed, map0.apply(delta0)?.apply(delta1)?);
+        assert_eq!(replayed, map2);
+        assert_eq!(replayed["x"], 20);
+        Ok(())
+    }
+
+    #[cfg(feature = "content-hash")]
+    #[test]
+    fn HashMapDelta__content_hash() -> DeltaResult<()> {
+        let entries0: Vec<(String, u32)> = (0 .. 100)
+            .map(|n| (format!("key {}", n), n))
+            .collect();
+        let entries1: Vec<(String, u32)> = (50 .. 150)
+            .map(|n| (format!("key {}", n), n % 70))
+            .collect();
+        // NOTE: Build equal maps with different capacities and insertion orders:
+        let map0a: HashMap<String, u32> = entries0.iter().cloned().collect();
+        let map1a: HashMap<String, u32> = entries1.iter().cloned().collect();
+        let mut map0b: HashMap<String, u32> = HashMap::with_capacity(1000);
+        map0b.extend(entries0.iter().rev().cloned());
+        let mut map1b: HashMap<String, u32> = HashMap::with_capacity(1000);
+        map1b.extend(entries1.iter().rev().cloned());
+        let delta_a: HashMapDelta<String, u32> = map0a.delta(&map1a)?;
+        let delta_b: HashMapDelta<String, u32> = map0b.delta(&map1b)?;
+        assert_eq!(delta_a, delta_b);
+        assert_eq!(delta_a.content_hash()?, delta_b.content_hash()?);
+
+        let mut map1c: HashMap<String, u32> = map1a.clone();
+        map1c.insert("key 60".into(), 61);
+        let delta_c: HashMapDelta<String, u32> = map0a.delta(&map1c)?;
+        assert_ne!(delta_a.content_hash()?, delta_c.content_hash()?);
+        assert_ne!(
+            delta_a.content_hash()?,
+            HashMapDelta::<String, u32>(None).content_hash()?,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn StampedHashMapDelta__merge__last_writer_wins() -> DeltaResult<()> {
+        let map0: HashMap<String, u32> = vec![
+            ("foo".to_string(), 1),
+            ("bar".to_string(), 2),
+        ].into_iter().collect();
+        let mut map1 = map0.clone();
+        map1.insert("foo".into(), 10);
+        map1.insert("baz".into(), 3);
+        let mut map2 = map0.clone();
+        map2.insert("foo".into(), 20);
+        map2.remove("bar");
+        let delta1 = map0.delta(&map1)?.stamped(5);
+        let delta2 = map0.delta(&map2)?.stamped(7);
+        let merged = delta1.clone().merge(delta2.clone());
+        assert_eq!(merged, delta2.clone().merge(delta1.clone()));
+        let expected: HashMap<String, u32> = vec![
+            ("foo".to_string(), 20),
+            ("baz".to_string(), 3),
+        ].into_iter().collect();
+        assert_eq!(map0.apply(merged.unstamped())?, expected);
+
+        // NOTE: With equal generations, `self` wins:
+        let merged = map0.delta(&map1)?.stamped(7).merge(delta2);
+        assert_eq!(map0.apply(merged.unstamped())?["foo"], 10);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__diff_streaming() -> DeltaResult<()> {
+        let map0: HashMap<u32, u32> = (0 .. 10_000).map(|n| (n, n)).collect();
+        let map1: HashMap<u32, u32> = (5_000 .. 15_000)
+            .map(|n| (n, if n % 3 == 0 { n + 1 } else { n }))
+            .collect();
+        let sorted0: BTreeMap<u32, u32> = map0.clone().into_iter().collect();
+        let sorted1: BTreeMap<u32, u32> = map1.clone().into_iter().collect();
+        for (lhs, rhs, sorted_lhs, sorted_rhs) in [
+            (&map0, &map1, &sorted0, &sorted1),
+            (&map1, &map0, &sorted1, &sorted0),
+            (&map0, &map0, &sorted0, &sorted0),
+        ] {
+            let diff = HashMapDelta::diff_streaming(sorted_lhs, sorted_rhs);
+            let changes = diff.collect::<DeltaResult<Vec<_>>>()?;
+            let delta = HashMapDelta(Some(changes).filter(|c| !c.is_empty()));
+            assert_eq!(delta, lhs.delta(rhs)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__diff_streaming__lazy() -> DeltaResult<()> {
+        use std::cell::Cell;
+        let keys: Vec<u32> = (0 .. 200_000).collect();
+        let vals: Vec<u32> = keys.iter()
+            .map(|key| if key % 1000 == 0 { key + 1 } else { *key })
+            .collect();
+        // NOTE: `pulled` counts the entries taken from either side:
+        let pulled: Cell<usize> = Cell::new(0);
+        let count = |_: &(&u32, &u32)| pulled.set(pulled.get() + 1);
+        let lhs = keys[.. 150_000].iter().zip(&keys[.. 150_000]).inspect(count);
+        let rhs = keys[50_000 ..].iter().zip(&vals[50_000 ..]).inspect(count);
+        let mut diff = HashMapDelta::<u32, u32>::diff_streaming(lhs, rhs);
+        // NOTE: The first edit is found without reading any further:
+        assert!(matches!(diff.next(), Some(Ok(EntryDelta::Edit { key: 50_000, .. }))));
+        assert_eq!(pulled.get(), 50_001 + 1);
+        let (mut edits, mut adds, mut removes) = (1, 0, 0);
+        for change in diff {
+            match change? {
+                EntryDelta::Edit { .. } => edits += 1,
+                EntryDelta::Add { .. } => adds += 1,
+                EntryDelta::Remove { .. } => removes += 1,
+            }
+        }
+        assert_eq!((edits, adds, removes), (100, 50_000, 50_000));
+        // NOTE: Each pass reads each entry once, and nothing is buffered:
+        assert_eq!(pulled.get(), 3 * 300_000);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__diff_streaming__unsorted() {
+        let keys: Vec<u32> = vec![1, 0];
+        let entries = keys.iter().map(|key| (key, key));
+        let mut diff = HashMapDelta::<u32, u32>::diff_streaming(entries.clone(), entries);
+        assert!(diff.next().expect("an error").is_err());
+        assert!(diff.next().is_none());
+    }
+
+    #[test]
+    fn HashMapDelta__delta_with_key_norm() -> DeltaResult<()> {
+        let map0: HashMap<String, u32> = vec![
+            ("Host".to_string(), 1),
+            ("PORT".to_string(), 2),
+            ("user".to_string(), 3),
+        ].into_iter().collect();
+        let map1: HashMap<String, u32> = vec![
+            ("host".to_string(), 1),
+            ("Port".to_string(), 2),
+            ("USER".to_string(), 3),
+        ].into_iter().collect();
+        let lowercase = |key: &String| key.to_lowercase();
+        let delta = HashMapDelta::delta_with_key_norm(&map0, &map1, lowercase)?;
+        assert_eq!(delta, HashMapDelta(None));
+
+        let mut map2 = map1.clone();
+        map2.insert("Port".into(), 20);
+        map2.remove("USER");
+        map2.insert("Timeout".into(), 30);
+        let delta = HashMapDelta::delta_with_key_norm(&map0, &map2, lowercase)?;
         assert_eq!(delta, HashMapDelta(Some(vec![
-            EntryDelta::Edit { key: "bar".into(),  value:   350usize.into_delta()? },
-            EntryDelta::Add  { key: "baz".into(),  value:  9000usize.into_delta()? },
-            EntryDelta::Remove { key: "floozie".into() },
+            EntryDelta::Edit { key: "PORT".into(), value: 20u32.into_delta()? },
+            EntryDelta::Add { key: "Timeout".into(), value: 30u32.into_delta()? },
+            EntryDelta::Remove { key: "user".into() },
         ])));
+
+        let mut map3 = map0.clone();
+        map3.insert("HOST".into(), 4);
+        assert_eq!(
+            HashMapDelta::delta_with_key_norm(&map3, &map0, lowercase),
+            Err(DeltaError::Conflict {
+                index: None,
+                key: Some(r#""HOST" "Host""#.into()),
+            }),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn HashMap__apply__preserves_arc_identity() -> DeltaResult<()> {
+        use std::sync::Arc;
+        let map0: HashMap<String, Arc<Vec<u8>>> = map! {
+            "bar".into() => Arc::new(vec![1, 2, 3]),
+            "foo".into() => Arc::new(vec![4, 5]),
+            "quux".into() => Arc::new(vec![6]),
+        };
+        let mut map1: HashMap<String, Arc<Vec<u8>>> = map0.clone();
+        map1.insert("foo".into(), Arc::new(vec![4, 5, 7]));
+        let delta = map0.delta(&map1)?;
         let map2 = map0.apply(delta)?;
         assert_eq!(map1, map2);
+        assert!(Arc::ptr_eq(&map0["bar"], &map2["bar"]));
+        assert!(Arc::ptr_eq(&map0["quux"], &map2["quux"]));
+        assert!(!Arc::ptr_eq(&map0["foo"], &map2["foo"]));
+
+        // NOTE: An edit with an empty delta leaves the `Arc` untouched:
+        let delta = HashMapDelta(Some(vec![EntryDelta::Edit {
+            key: "bar".into(),
+            value: crate::ArcDelta(None),
+        }]));
+        let map3 = map0.apply(delta)?;
+        assert!(Arc::ptr_eq(&map0["bar"], &map3["bar"]));
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__delta_with_eq() -> DeltaResult<()> {
+        // NOTE: The values are (name, last_seen) pairs, where `last_seen`
+        //       is considered irrelevant to whether a value changed:
+        let eq = |lhs: &(String, u64), rhs: &(String, u64)| lhs.0 == rhs.0;
+        let map0: HashMap<String, (String, u64)> = map! {
+            "bar".into() => ("alice".into(), 100),
+            "foo".into() => ("bob".into(),   200),
+        };
+        let map1: HashMap<String, (String, u64)> = map! {
+            "bar".into() => ("alice".into(), 150),
+            "foo".into() => ("carol".into(), 250),
+        };
+        let delta = HashMapDelta::delta_with_eq(&map0, &map1, eq)?;
+        assert_eq!(delta.len(), 1);
+        assert!(matches!(
+            delta.iter().next(),
+            Some(EntryDelta::Edit { key, .. }) if key == "foo"
+        ));
+        assert_eq!(map0.delta(&map1)?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__rebase_onto() -> DeltaResult<()> {
+        let base_a: HashMap<String, usize> = map! {
+            "bar".into() => 300usize,
+            "foo".into() => 100usize,
+        };
+        let mut base_b: HashMap<String, usize> = base_a.clone();
+        base_b.insert("baz".into(), 500);
+        // NOTE: A change that doesn't touch any key that diverged:
+        let mut map1: HashMap<String, usize> = base_a.clone();
+        map1.insert("foo".into(), 200);
+        map1.insert("quux".into(), 400);
+        let delta = base_a.delta(&map1)?;
+        let rebased = delta.rebase_onto(&base_a, &base_b)?;
+        let expected: HashMap<String, usize> = map! {
+            "bar".into()  => 300usize,
+            "baz".into()  => 500usize,
+            "foo".into()  => 200usize,
+            "quux".into() => 400usize,
+        };
+        assert_eq!(base_b.apply(rebased)?, expected);
+        // NOTE: A change to a key that diverged:
+        base_b.insert("foo".into(), 150);
+        let result = delta.rebase_onto(&base_a, &base_b);
+        assert_eq!(result, Err(DeltaError::Conflict {
+            index: None,
+            key: Some("\"foo\"".into()),
+        }));
+        // NOTE: Unless the diverged key already has the changed value:
+        base_b.insert("foo".into(), 200);
+        let rebased = delta.rebase_onto(&base_a, &base_b)?;
+        assert_eq!(rebased.len(), 1);
+        assert_eq!(base_b.apply(rebased)?, expected);
+        // NOTE: An edit of a key that's missing from either base:
+        let delta: HashMapDelta<String, usize> = HashMapDelta(Some(vec![
+            EntryDelta::Edit { key: "foo".into(), value: 250usize.into_delta()? },
+        ]));
+        let conflict = Err(DeltaError::Conflict {
+            index: None,
+            key: Some("\"foo\"".into()),
+        });
+        let base_c: HashMap<String, usize> = map! { "bar".into() => 300usize };
+        assert_eq!(delta.rebase_onto(&base_a, &base_c), conflict);
+        assert_eq!(delta.rebase_onto(&base_c, &base_b), conflict);
+        assert_eq!(delta.rebase_onto(&base_c, &base_c), conflict);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__delta_converted() -> DeltaResult<()> {
+        let map0: HashMap<String, u32> = map! {
+            "bar".into() => 300u32,
+            "foo".into() => 100u32,
+        };
+        let map1: HashMap<String, u64> = map! {
+            "bar".into() => 300u64,
+            "foo".into() => 5_000_000_000u64,
+        };
+        let delta: HashMapDelta<String, u64> =
+            HashMapDelta::delta_converted(&map0, &map1)?;
+        assert_eq!(delta.len(), 1);
+        let base: HashMap<String, u64> = map0.iter()
+            .map(|(key, &value)| (key.clone(), u64::from(value)))
+            .collect();
+        assert_eq!(base.apply(delta)?, map1);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__apply_delta_lenient() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
+            "bar".into() => 300usize,
+            "foo".into() => 100usize,
+        };
+        let delta: HashMapDelta<String, usize> = HashMapDelta(Some(vec![
+            EntryDelta::Edit { key: "foo".into(), value: 200usize.into_delta()? },
+            EntryDelta::Edit { key: "floozie".into(), value: 1usize.into_delta()? },
+            EntryDelta::Remove { key: "bar".into() },
+            EntryDelta::Add { key: "quux".into(), value: 400usize.into_delta()? },
+        ]));
+        let (map1, errors) = HashMapDelta::apply_delta_lenient(&map0, delta);
+        let expected: HashMap<String, usize> = map! {
+            "foo".into()  => 200usize,
+            "quux".into() => 400usize,
+        };
+        assert_eq!(map1, expected);
+        assert_eq!(errors, vec![DeltaError::KeyNotFound("\"floozie\"".into())]);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__apply_delta_lenient__partial_edit() -> DeltaResult<()> {
+        use crate::vec::{EltDelta, VecDelta};
+        let map0: HashMap<String, Vec<u8>> = map! {
+            "a".into() => vec![1u8, 2],
+            "b".into() => vec![3u8],
+        };
+        // NOTE: The edit of "a" fails after it already pushed a value:
+        let delta: HashMapDelta<String, Vec<u8>> = HashMapDelta(Some(vec![
+            EntryDelta::Edit { key: "a".into(), value: VecDelta(vec![
+                EltDelta::Add(3u8.into_delta()?),
+                EltDelta::Remove { count: 9 },
+            ])},
+            EntryDelta::Edit { key: "b".into(), value: VecDelta(vec![
+                EltDelta::Add(4u8.into_delta()?),
+            ])},
+        ]));
+        let (map1, errors) = HashMapDelta::apply_delta_lenient(&map0, delta);
+        assert_eq!(map1["a"], vec![1, 2]);
+        assert_eq!(map1["b"], vec![3, 4]);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__send_ops__apply_ops_from() -> DeltaResult<()> {
+        let map0: HashMap<u32, u32> = (0 .. 100).map(|n| (n, n)).collect();
+        let map1: HashMap<u32, u32> = (50 .. 150).map(|n| (n, n * 2)).collect();
+        let delta = map0.delta(&map1)?;
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let sending = delta.clone();
+        let handle = std::thread::spawn(move || sending.send_ops(&sender));
+        let map2 = HashMapDelta::apply_ops_from(&receiver, &map0)?;
+        handle.join().expect("sender thread panicked").expect("send failed");
+        assert_eq!(map2, map0.apply(delta)?);
+        assert_eq!(map2, map1);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__grouped() -> DeltaResult<()> {
+        let map0: HashMap<u32, String> = (0 .. 100)
+            .map(|n| (n, "old".to_string()))
+            .collect();
+        let map1: HashMap<u32, String> = (0 .. 100)
+            .map(|n| (n, "new".to_string()))
+            .collect();
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta.len(), 100);
+        let grouped = delta.grouped();
+        match grouped.0.as_slice() {
+            [GroupedEntryDelta::Edit { keys, value }] => {
+                let mut keys: Vec<u32> = keys.clone();
+                keys.sort_unstable();
+                assert_eq!(keys, (0 .. 100).collect::<Vec<u32>>());
+                assert_eq!(value, &"new".to_string().into_delta()?);
+            },
+            groups => panic!("expected a single group, got {:?}", groups),
+        }
+        assert_eq!(map0.apply(grouped.ungrouped())?, map1);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMap__delta__reproducible() -> DeltaResult<()> {
+        let map0: HashMap<u64, u64> = (0 .. 100).map(|n| (n, n)).collect();
+        let map1: HashMap<u64, u64> = (50 .. 150).rev().map(|n| (n, n % 7)).collect();
+        let delta = map0.delta(&map1)?;
+        // NOTE: Equal maps with a different capacity and insertion order
+        //       iterate differently, yet produce the same delta:
+        let mut map2: HashMap<u64, u64> = HashMap::with_capacity(1000);
+        map2.extend((0 .. 100).rev().map(|n| (n, n)));
+        let mut map3: HashMap<u64, u64> = HashMap::with_capacity(1000);
+        map3.extend((50 .. 150).map(|n| (n, n % 7)));
+        let json0 = serde_json::to_string(&delta).expect("failed to serialize");
+        let json1 = serde_json::to_string(&map2.delta(&map3)?)
+            .expect("failed to serialize");
+        assert_eq!(json0, json1);
+        let keys: Vec<u64> = delta.changed_keys().copied().collect();
+        let edited: Vec<u64> = (50 .. 100).filter(|n| n % 7 != *n).collect();
+        let expected: Vec<u64> = edited.into_iter()
+            .chain(100 .. 150)
+            .chain(0 .. 50)
+            .collect();
+        assert_eq!(keys, expected);
+        assert_eq!(map0.apply(delta)?, map1);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__delta_self_contained() -> DeltaResult<()> {
+        let map0: HashMap<String, u32> = vec![
+            ("foo".to_string(), 1),
+            ("bar".to_string(), 2),
+            ("baz".to_string(), 3),
+        ].into_iter().collect();
+        let map1: HashMap<String, u32> = vec![
+            ("foo".to_string(), 1),
+            ("bar".to_string(), 20),
+            ("qux".to_string(), 4),
+        ].into_iter().collect();
+        let delta = HashMapDelta::delta_self_contained(&map0, &map1)?;
+        assert!(delta.iter().all(|change| !matches!(change, EntryDelta::Edit { .. })));
+        assert_eq!(map0.apply(delta.clone())?, map1);
+        assert_eq!(HashMap::<String, u32>::from_delta(delta)?, map1);
+
+        // NOTE: A regular delta only stores what changed, and so can't:
+        let delta = map0.delta(&map1)?;
+        assert!(HashMap::<String, u32>::from_delta(delta).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn HashMap__apply_mut__reuses_allocation() -> DeltaResult<()> {
+        let map0: HashMap<u32, u32> = (0 .. 1000).map(|n| (n, n)).collect();
+        let map1: HashMap<u32, u32> = (1000 .. 2000).map(|n| (n, n)).collect();
+        let delta = map0.delta(&map1)?;
+        let mut map2: HashMap<u32, u32> = map0.clone();
+        let capacity: usize = map2.capacity();
+        map2.apply_mut(delta)?;
+        assert_eq!(map2, map1);
+        assert_eq!(map2.capacity(), capacity);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMap__apply_mut__reuses_allocation__partial_removal() -> DeltaResult<()> {
+        let map0: HashMap<u32, u32> = (1000 .. 2000).map(|n| (n, n)).collect();
+        // NOTE: Most entries are replaced by ones whose keys sort first:
+        let map1: HashMap<u32, u32> = (0 .. 900).chain(1900 .. 2000)
+            .map(|n| (n, n))
+            .collect();
+        let delta = map0.delta(&map1)?;
+        let mut map2: HashMap<u32, u32> = map0.clone();
+        let capacity: usize = map2.capacity();
+        map2.apply_mut(delta)?;
+        assert_eq!(map2, map1);
+        // NOTE: The removed entries leave tombstones behind that count
+        //       against the capacity until the map is next rehashed, but
+        //       the map mustn't have grown to fit the additions:
+        assert!(map2.capacity() <= capacity);
+        assert!(map2.capacity() >= map2.len());
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__changed_keys() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
+            "bar".into()  => 300usize,
+            "baz".into()  => 500usize,
+            "foo".into()  => 100usize,
+        };
+        let map1: HashMap<String, usize> = map! {
+            "bar".into()  => 300usize,
+            "foo".into()  => 200usize,
+            "quux".into() => 400usize,
+        };
+        let delta = map0.delta(&map1)?;
+        let mut keys: Vec<&String> = delta.changed_keys().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["baz", "foo", "quux"]);
+        assert_eq!(map0.delta(&map0)?.changed_keys().count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__sort_keys_by__unordered_keys() -> DeltaResult<()> {
+        // NOTE: `f64` keys are not `Ord`:
+        let mut delta: HashMapDelta<f64, usize> = HashMapDelta(Some(vec![
+            EntryDelta::Remove { key: 2.5 },
+            EntryDelta::Add { key: -1.0, value: 100usize.into_delta()? },
+            EntryDelta::Remove { key: 0.5 },
+        ]));
+        delta.sort_keys_by(f64::total_cmp);
+        let keys: Vec<f64> = delta.0.iter().flatten().map(|c| *c.key()).collect();
+        assert_eq!(keys, vec![-1.0, 0.5, 2.5]);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__DeltaOps__is_empty() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
+            "bar".into() => 300usize,
+            "foo".into() => 100usize,
+        };
+        assert!(DeltaOps::is_empty(&map0.delta(&map0)?));
+        assert!(DeltaOps::is_empty(&HashMapDelta::<String, usize>(None)));
+        let map1: HashMap<String, usize> = map! { "bar".into() => 300usize };
+        assert!(!DeltaOps::is_empty(&map0.delta(&map1)?));
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__identity_delta() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
+            "bar".into() => 300usize,
+            "foo".into() => 100usize,
+        };
+        let delta = HashMap::<String, usize>::identity_delta();
+        assert!(DeltaOps::is_empty(&delta));
+        assert_eq!(map0.apply(delta)?, map0);
+        Ok(())
+    }
+
+    #[test]
+    fn HashMapDelta__invert() -> DeltaResult<()> {
+        let map0: HashMap<String, usize> = map! {
+            "bar".into() => 300usize,
+            "foo".into() => 100usize,
+        };
+        let map1: HashMap<String, usize> = map! {
+            "foo".into() => 200usize,
+            "quux".into() => 500usize,
+        };
+        let delta = map0.delta(&map1)?;
+        let inverse = delta.invert(&map0)?;
+        assert_eq!(map0.apply(delta)?.apply(inverse)?, map0);
         Ok(())
     }
 }
@@ -3,7 +3,10 @@
 //!
 //! [`HashSet`]: https://doc.rust-lang.org/std/collections/struct.HashSet.html
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Core, Delta, DeltaError, DeltaOps, DeltaResult,
+    EmptyDelta, FromDelta, IntoDelta, Invert,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -24,19 +27,27 @@ where T: Clone + Debug + PartialEq + Ord + Hash + Apply + FromDelta
     + Serialize,
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        let mut new: Self = self.clone();
+        new.apply_mut(delta)?;
+        Ok(new)
+    }
+
+    fn apply_mut(&mut self, delta: Self::Delta) -> DeltaResult<()> {
         match delta.0 {
-            None => Ok(self.clone()),
+            None => Ok(()),
             Some(entry_deltas) => {
-                let mut new: Self = self.clone();
                 for entry_delta in entry_deltas { match entry_delta {
                     EntryDelta::Add { item } => {
-                        new.insert(<T>::from_delta(item)?);
+                        self.insert(<T>::from_delta(item)?);
                     },
                     EntryDelta::Remove { item } => {
-                        new.remove(&(<T>::from_delta(item)?));
+                        let item: T = <T>::from_delta(item)?;
+                        if !self.remove(&item) {
+                            return Err(DeltaError::KeyNotFound(format!("{:?}", item)));
+                        }
                     },
                 }}
-                Ok(new)
+                Ok(())
             },
         }
     }
@@ -105,6 +116,16 @@ where T: Clone + Debug + PartialEq + Ord + IntoDelta
     }
 }
 
+impl<T> EmptyDelta for HashSet<T>
+where T: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn identity_delta() -> Self::Delta {
+        HashSetDelta(None)
+    }
+}
+
 
 
 
@@ -140,6 +161,71 @@ where T: Clone + Debug + PartialEq + Ord + Core
             None => 0,
         }
     }
+
+    /// Return the items that `self` inserts into a set.
+    pub fn inserted(&self) -> Vec<&<T as Core>::Delta> {
+        self.iter()
+            .filter_map(|change| match change {
+                EntryDelta::Add { item } => Some(item),
+                EntryDelta::Remove { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Return the items that `self` removes from a set.
+    pub fn removed(&self) -> Vec<&<T as Core>::Delta> {
+        self.iter()
+            .filter_map(|change| match change {
+                EntryDelta::Add { .. } => None,
+                EntryDelta::Remove { item } => Some(item),
+            })
+            .collect()
+    }
+}
+
+impl<T> HashSetDelta<T>
+where T: Clone + Debug + PartialEq + Ord + Hash + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    /// Apply `delta` to `base` such that re-applying an already-applied
+    /// `delta` is a no-op rather than an error, which makes it suitable
+    /// for at-least-once delivery.  Unlike `base.apply(delta)`, removing
+    /// an item that is absent from `base` is skipped rather than an error.
+    pub fn apply_delta_idempotent(
+        base: &HashSet<T>,
+        delta: Self,
+    ) -> DeltaResult<HashSet<T>> {
+        let mut new: HashSet<T> = base.clone();
+        for change in delta.into_iter() { match change {
+            EntryDelta::Add { item } => {
+                new.insert(<T>::from_delta(item)?);
+            },
+            EntryDelta::Remove { item } => {
+                new.remove(&(<T>::from_delta(item)?));
+            },
+        }}
+        Ok(new)
+    }
+}
+
+impl<T: Core> DeltaOps for HashSetDelta<T> {
+    fn is_empty(&self) -> bool {
+        match &self.0 {
+            Some(changes) => changes.is_empty(),
+            None => true,
+        }
+    }
+}
+
+impl<T> Invert<HashSet<T>> for HashSetDelta<T>
+where T: Core,
+      HashSet<T>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &HashSet<T>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
 }
 
 impl<T> std::fmt::Debug for HashSetDelta<T>
@@ -304,4 +390,35 @@ mod tests {
         assert_eq!(set1, set2);
         Ok(())
     }
+
+    #[test]
+    fn HashSet__apply__overlapping_values() -> DeltaResult<()> {
+        let set0: HashSet<String> = set! {
+            "bar".into(),
+            "foo".into(),
+            "floozie".into(),
+        };
+        let set1: HashSet<String> = set! {
+            "bar".into(),
+            "baz".into(),
+            "foo".into(),
+        };
+        let delta = set0.delta(&set1)?;
+        assert_eq!(delta.inserted(), vec![&"baz".to_string().into_delta()?]);
+        assert_eq!(delta.removed(), vec![&"floozie".to_string().into_delta()?]);
+        let set2 = set0.apply(delta.clone())?;
+        assert_eq!(set2, set1);
+
+        // NOTE: `set2` already contains the inserted item, which is fine,
+        //       but no longer contains the removed one, which isn't:
+        assert_eq!(
+            set2.apply(delta.clone()),
+            Err(DeltaError::KeyNotFound(format!("{:?}", "floozie"))),
+        );
+        let set3 = HashSetDelta::apply_delta_idempotent(&set2, delta.clone())?;
+        assert_eq!(set3, set1);
+        let set4 = HashSetDelta::apply_delta_idempotent(&set0, delta)?;
+        assert_eq!(set4, set1);
+        Ok(())
+    }
 }
@@ -2,7 +2,7 @@
 
 mod btreemap;
 mod btreeset;
-mod hashmap;
+pub(crate) mod hashmap;
 mod hashset;
 mod vecdeque;
 
@@ -3,7 +3,10 @@
 //!
 //! [`VecDeque`]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html
 
-use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaError, DeltaOps, DeltaResult,
+    EmptyDelta, FromDelta, IntoDelta, Invert,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::fmt::Debug;
@@ -25,20 +28,31 @@ where T: Clone + Debug + PartialEq + Ord + Apply + FromDelta
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
         let mut new: Self = self.clone();
+        new.apply_mut(delta)?;
+        Ok(new)
+    }
+
+    fn apply_mut(&mut self, delta: Self::Delta) -> DeltaResult<()> {
         for change in delta.into_iter() { match change {
             EltDelta::Edit { index, item } => {
                 // NOTE: If self.len() == 0, the Edit should have been an Add:
                 ensure_gt![self.len(), 0]?;
                 // NOTE: Ensure index is within bounds:
                 ensure_lt![index, self.len()]?;
-                new[index] = self[index].apply(item)?;
+                self[index].apply_mut(item)?;
             },
-            EltDelta::Add(delta) =>  new.push_back(<T>::from_delta(delta)?),
+            EltDelta::Add(delta) =>  self.push_back(<T>::from_delta(delta)?),
             EltDelta::Remove { count } =>  for _ in 0 .. count {
-                new.pop_back().ok_or_else(|| ExpectedValue!("VecDelta<T>"))?;
+                self.pop_back().ok_or_else(|| ExpectedValue!("VecDelta<T>"))?;
+            },
+            EltDelta::Prepend(items) => for item in items.into_iter().rev() {
+                self.push_front(<T>::from_delta(item)?);
+            },
+            EltDelta::RemoveFront { count } =>  for _ in 0 .. count {
+                self.pop_front().ok_or_else(|| ExpectedValue!("VecDelta<T>"))?;
             },
         }}
-        Ok(new)
+        Ok(())
     }
 }
 
@@ -47,27 +61,71 @@ where T: Clone + Debug + PartialEq + Ord + Delta + IntoDelta
     + for<'de> Deserialize<'de>
     + Serialize,
 {
+    /// Calculate `self --[delta]--> rhs` by logical index.  Values that
+    /// were pushed to or popped from the front of `self` are detected as
+    /// long as the first value of one deque also occurs in the other, in
+    /// which case they produce a single `EltDelta::Prepend` or
+    /// `EltDelta::RemoveFront` rather than an edit of every value after
+    /// them.  Of the alignments considered, the one that results in the
+    /// fewest operations is chosen.
     fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
-        let (lhs_len, rhs_len) = (self.len(), rhs.len());
-        let max_len = usize::max(lhs_len, rhs_len);
-        let mut changes: VecDeque<EltDelta<T>> = VecDeque::new();
-        for index in 0 .. max_len { match (self.get(index), rhs.get(index)) {
+        let mut best: VecDeque<EltDelta<T>> = delta_aligned(self, rhs, 0, 0)?;
+        let prepended: Option<usize> = self.front()
+            .and_then(|first| rhs.iter().position(|r| r == first));
+        let popped: Option<usize> = rhs.front()
+            .and_then(|first| self.iter().position(|l| l == first));
+        let candidates = prepended.map(|count| (0, count)).into_iter()
+            .chain(popped.map(|count| (count, 0)))
+            .filter(|&(popped, prepended)| popped + prepended > 0);
+        for (popped, prepended) in candidates {
+            let changes = delta_aligned(self, rhs, popped, prepended)?;
+            if changes.len() < best.len() { best = changes; }
+        }
+        Ok(VecDequeDelta(best))
+    }
+}
+
+/// Calculate `lhs --[delta]--> rhs`, assuming that `popped` values were
+/// removed from the front of `lhs` and `prepended` values were pushed
+/// to its front, after which the values are compared by index.
+fn delta_aligned<T>(
+    lhs: &VecDeque<T>,
+    rhs: &VecDeque<T>,
+    popped: usize,
+    prepended: usize,
+) -> DeltaResult<VecDeque<EltDelta<T>>>
+where T: Clone + Debug + PartialEq + Ord + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    let mut changes: VecDeque<EltDelta<T>> = VecDeque::new();
+    if popped > 0 {
+        changes.push_back(EltDelta::RemoveFront { count: popped });
+    }
+    if prepended > 0 {
+        let items = rhs.iter().take(prepended)
+            .map(|r| r.clone().into_delta())
+            .collect::<DeltaResult<_>>()?;
+        changes.push_back(EltDelta::Prepend(items));
+    }
+    let (lhs_len, rhs_len) = (lhs.len() - popped, rhs.len() - prepended);
+    let max_len = usize::max(lhs_len, rhs_len);
+    for pos in 0 .. max_len {
+        let index = prepended + pos;
+        match (lhs.get(popped + pos), rhs.get(index)) {
             (None, None) => return bug_detected!(),
             (Some(l), Some(r)) if l == r => {/*NOP*/},
             (Some(l), Some(r)) =>
                 changes.push_back(EltDelta::Edit { index, item: l.delta(r)? }),
             (None, Some(r)) =>
                 changes.push_back(EltDelta::Add(r.clone().into_delta()?)),
-            (Some(_),   None) => {
-                let last_change_idx = changes.len() - 1;
-                match changes.get_mut(last_change_idx) {
-                    Some(EltDelta::Remove { ref mut count }) => *count += 1,
-                    _ => changes.push_back(EltDelta::Remove { count: 1 }),
-                }
+            (Some(_), None) => match changes.back_mut() {
+                Some(EltDelta::Remove { ref mut count }) => *count += 1,
+                _ => changes.push_back(EltDelta::Remove { count: 1 }),
             },
-        }}
-        Ok(VecDequeDelta(changes))
+        }
     }
+    Ok(changes)
 }
 
 impl<T> FromDelta for VecDeque<T>
@@ -101,6 +159,16 @@ where T: Clone + Debug + PartialEq + Ord + IntoDelta
     }
 }
 
+impl<T> EmptyDelta for VecDeque<T>
+where T: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn identity_delta() -> Self::Delta {
+        VecDequeDelta(VecDeque::new())
+    }
+}
+
 
 
 
@@ -130,6 +198,30 @@ impl<T: Core> VecDequeDelta<T> {
     pub fn len(&self) -> usize { self.0.len() }
 }
 
+impl<T: Core> DeltaOps for VecDequeDelta<T> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T: Core + Clone> Compose for VecDequeDelta<T> {
+    /// Like `VecDelta::compose()`, the changes of `next` follow those of
+    /// `self`.
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(Self(self.0.iter().chain(next.0.iter()).cloned().collect()))
+    }
+}
+
+impl<T> Invert<VecDeque<T>> for VecDequeDelta<T>
+where T: Core,
+      VecDeque<T>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &VecDeque<T>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 impl<T> std::fmt::Debug for VecDequeDelta<T>
 where T: std::fmt::Debug + Core {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
@@ -154,6 +246,10 @@ pub enum EltDelta<T: Core> {
     Remove { count: usize },
     /// Add a value.
     Add(<T as Core>::Delta),
+    /// Push values to the front of the VecDeque, keeping their order.
+    Prepend(Vec<<T as Core>::Delta>),
+    /// Remove `count` elements from the front of the VecDeque.
+    RemoveFront { count: usize },
 }
 
 impl<T: Core> std::fmt::Debug for EltDelta<T> {
@@ -167,6 +263,10 @@ impl<T: Core> std::fmt::Debug for EltDelta<T> {
                 .field("count", count)
                 .finish(),
             Self::Add(delta) => write!(f, "Add({:#?})", delta),
+            Self::Prepend(items) => write!(f, "Prepend({:#?})", items),
+            Self::RemoveFront { count } => f.debug_struct("RemoveFront")
+                .field("count", count)
+                .finish(),
         }
     }
 }
@@ -306,4 +406,49 @@ mod tests {
         Ok(())
     }
 
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDeque__delta__both_ends() -> DeltaResult<()> {
+        let vecdeque0: VecDeque<u32> = (10 .. 20).collect();
+        let mut vecdeque1: VecDeque<u32> = vecdeque0.clone();
+        vecdeque1.push_front(2);
+        vecdeque1.push_front(1);
+        vecdeque1.push_back(20);
+        vecdeque1[7] = 0;
+        let delta = vecdeque0.delta(&vecdeque1)?;
+        assert_eq!(delta, VecDequeDelta(vecdeque![
+            EltDelta::Prepend(vec![1u32.into_delta()?, 2u32.into_delta()?]),
+            EltDelta::Edit { index: 7, item: 0u32.into_delta()? },
+            EltDelta::Add(20u32.into_delta()?),
+        ]));
+        let vecdeque2 = vecdeque0.apply(delta)?;
+        assert_eq!(vecdeque2, vecdeque1);
+
+        // NOTE: The reverse pops the values from the front again:
+        let delta = vecdeque1.delta(&vecdeque0)?;
+        assert_eq!(delta, VecDequeDelta(vecdeque![
+            EltDelta::RemoveFront { count: 2 },
+            EltDelta::Edit { index: 5, item: 15u32.into_delta()? },
+            EltDelta::Remove { count: 1 },
+        ]));
+        let vecdeque3 = vecdeque1.apply(delta)?;
+        assert_eq!(vecdeque3, vecdeque0);
+
+        let delta = vecdeque0.delta(&vecdeque![10])?;
+        assert_eq!(delta, VecDequeDelta(vecdeque![EltDelta::Remove { count: 9 }]));
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDeque__apply_mut__matches_apply() -> DeltaResult<()> {
+        let vecdeque0: VecDeque<u32> = vecdeque![1, 2, 3, 4];
+        let vecdeque1: VecDeque<u32> = vecdeque![0, 1, 2, 30];
+        let delta = vecdeque0.delta(&vecdeque1)?;
+        let mut vecdeque2 = vecdeque0.clone();
+        vecdeque2.apply_mut(delta.clone())?;
+        assert_eq!(vecdeque2, vecdeque0.apply(delta)?);
+        assert_eq!(vecdeque2, vecdeque1);
+        Ok(())
+    }
 }
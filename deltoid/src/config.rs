@@ -0,0 +1,28 @@
+//! Configuration for calculating deltas.
+
+use crate::vec::VecStrategy;
+
+/// Tunes how deltas are calculated for types that support
+/// more than one way of calculating them.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DeltaConfig {
+    /// The algorithm used to calculate a `VecDelta`.
+    pub vec_strategy: VecStrategy,
+    /// The maximum number of operations in a `VecDelta`, as counted by
+    /// `VecDelta::len()`.  Once a diff would exceed it, diffing is abandoned
+    /// in favour of a single `EltDelta::SetAll` that replaces the entire
+    /// `Vec`.  With `VecStrategy::Myers`, this also bounds the time spent
+    /// diffing, as only edit distances up to `2 * max_ops` plus the number
+    /// of elements by which the old `Vec` is longer than the new one are
+    /// searched for.  `None` means that there is no limit.
+    pub max_ops: Option<usize>,
+}
+
+impl Default for DeltaConfig {
+    fn default() -> Self {
+        Self {
+            vec_strategy: VecStrategy::Positional,
+            max_ops: None,
+        }
+    }
+}
@@ -17,6 +17,15 @@ pub trait Apply: Core + Clone + Debug + PartialEq {
     /// i.e. calculate `self --[delta]--> other`.
     ///                                   ^^^^^
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self>;
+
+    /// Apply `delta` to `self` in place.  By default this is equivalent
+    /// to `*self = self.apply(delta)?`, but types that can avoid copying
+    /// themselves (e.g. maps) override it to only touch the changed parts.
+    /// If an error is returned, `self` may have been partially updated.
+    fn apply_mut(&mut self, delta: Self::Delta) -> DeltaResult<()> {
+        *self = self.apply(delta)?;
+        Ok(())
+    }
 }
 
 pub trait Delta: Core + Clone + Debug + PartialEq {
@@ -37,6 +46,80 @@ pub trait IntoDelta: Core {
     fn into_delta(self) -> DeltaResult<Self::Delta>;
 }
 
+/// Construction of the identity delta of a type, without needing
+/// 2 equal values to calculate it from.
+pub trait EmptyDelta: Core {
+    /// Return the delta that leaves any value of `Self` it's applied to
+    /// unchanged, e.g. as the initial value of an accumulator of deltas.
+    fn identity_delta() -> Self::Delta;
+}
+
+/// Operations on delta types themselves, rather than on the types they
+/// describe the changes of.
+pub trait DeltaOps {
+    /// Returns `true` if applying `self` is known to be a no-op, e.g. if
+    /// it's a delta between 2 equal values, which therefore needn't be
+    /// stored or transmitted.  A `false` result means that applying it
+    /// may change a value.
+    fn is_empty(&self) -> bool;
+}
+
+/// Composition of sequential deltas into one.
+pub trait Compose: Sized {
+    /// Combine `self` and the delta `next` that follows it into a single
+    /// delta, i.e. if `a --[self]--> b` and `b --[next]--> c`, calculate
+    /// `a --[delta]--> c`.  Returns an error if the deltas are incompatible
+    /// in that `next` can never be applied after `self`, or if they can't
+    /// be combined without the value they apply to.
+    fn compose(&self, next: &Self) -> DeltaResult<Self>;
+}
+
+/// `Compose` by reference, for use in derived `Compose` impls.  These bound
+/// the delta types of their fields with `for<'a> &'a D: ComposeRef<D>`
+/// rather than `D: Compose`, so that the bound is taken as given while
+/// checking the impl.  Otherwise the bound of a field of a (mutually)
+/// recursive type would be cyclic, which is a hard error rather than a
+/// missing impl.
+#[doc(hidden)]
+pub trait ComposeRef<D> {
+    fn compose_ref(self, next: Self) -> DeltaResult<D>;
+}
+
+impl<D: Compose> ComposeRef<D> for &D {
+    fn compose_ref(self, next: Self) -> DeltaResult<D> {
+        self.compose(next)
+    }
+}
+
+/// Inversion of a delta, given the value of type `T` it applies to.
+pub trait Invert<T>: Sized {
+    /// Calculate the delta that undoes `self`, i.e. if `base --[self]--> b`,
+    /// calculate `b --[delta]--> base`.  Unlike `b.delta(base)` this doesn't
+    /// need `b`, only `base`.
+    fn invert(&self, base: &T) -> DeltaResult<Self>;
+}
+
+/// A delta type for values of type `T` other than `T::Delta`.  A field
+/// of type `T` in a derived type that's marked with
+/// `#[delta(delta_type = "D")]` uses `D` as its delta type, which allows
+/// plugging in a specialized delta encoding without changing `T`.
+pub trait FieldDelta<T>: Sized + Clone + Debug + PartialEq
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Calculate `lhs --[delta]--> rhs`.
+    fn delta(lhs: &T, rhs: &T) -> DeltaResult<Self>;
+
+    /// Calculate `base --[delta]--> new`, i.e. the new value.
+    fn apply(base: &T, delta: Self) -> DeltaResult<T>;
+
+    /// Convert `delta` to a value of type `T`.
+    fn from_delta(delta: Self) -> DeltaResult<T>;
+
+    /// Convert `value` to a delta.
+    fn into_delta(value: T) -> DeltaResult<Self>;
+}
+
 
 macro_rules! impl_delta_trait_for_primitive_types {
     ( $($type:ty => $delta:ident $(: $($traits:ident),+)?);* $(;)? ) => {
@@ -50,16 +133,21 @@ macro_rules! impl_delta_trait_for_primitive_types {
             }
 
             impl Apply for $type {
+                /// Applying an empty delta leaves `self` unchanged.
                 #[inline(always)]
                 fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
-                    Self::from_delta(delta)
+                    Ok(delta.0.unwrap_or(*self))
                 }
             }
 
             impl Delta for $type {
+                /// The delta of equal values is empty.
                 #[inline(always)]
                 fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
-                    rhs.clone().into_delta()
+                    // NOTE: Comparing deltas rather than values tells apart
+                    //       the signed zeros, and treats all NaNs as equal:
+                    let rhs: Self::Delta = rhs.clone().into_delta()?;
+                    Ok(if $delta(Some(*self)) == rhs { $delta(None) } else { rhs })
                 }
             }
 
@@ -83,6 +171,30 @@ macro_rules! impl_delta_trait_for_primitive_types {
                 }
             }
 
+            impl EmptyDelta for $type {
+                fn identity_delta() -> Self::Delta {
+                    $delta(None)
+                }
+            }
+
+            impl DeltaOps for $delta {
+                fn is_empty(&self) -> bool {
+                    self.0.is_none()
+                }
+            }
+
+            impl Compose for $delta {
+                fn compose(&self, next: &Self) -> DeltaResult<Self> {
+                    Ok($delta(next.0.or(self.0)))
+                }
+            }
+
+            impl Invert<$type> for $delta {
+                fn invert(&self, base: &$type) -> DeltaResult<Self> {
+                    Ok($delta(self.0.map(|_| *base)))
+                }
+            }
+
             impl std::fmt::Debug for $delta {
                 fn fmt(&self, f: &mut std::fmt::Formatter)
                        -> Result<(), std::fmt::Error>
@@ -99,6 +211,8 @@ macro_rules! impl_delta_trait_for_primitive_types {
     };
 }
 
+
+
 impl_delta_trait_for_primitive_types! {
     i8    => I8Delta:    Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash;
     i16   => I16Delta:   Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash;
@@ -114,9 +228,138 @@ impl_delta_trait_for_primitive_types! {
     u128  => U128Delta:  Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash;
     usize => UsizeDelta: Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash;
 
-    f32   => F32Delta:   Clone, Copy, PartialEq,     PartialOrd           ;
-    f64   => F64Delta:   Clone, Copy, PartialEq,     PartialOrd           ;
+    f32   => F32Delta:   Clone, Copy                                      ;
+    f64   => F64Delta:   Clone, Copy                                      ;
     bool  => BoolDelta:  Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash;
     char  => CharDelta:  Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash;
     ()    => UnitDelta:  Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash;
 }
+
+
+/// Implements `PartialEq` and `PartialOrd` for float deltas such that
+/// 2 deltas are equal iff applying them produces the same value, where
+/// all NaNs count as the same value.  Hence unlike the floats themselves,
+/// a delta to NaN equals itself, while deltas to `+0.0` and `-0.0` differ,
+/// as applying them preserves the sign of zero.  Infinities are compared
+/// like any other value.  Deltas are ordered like their values, except
+/// that `-0.0 < +0.0`, and a NaN is unordered relative to anything else.
+macro_rules! impl_partial_eq_for_float_deltas {
+    ( $($delta:ident),* $(,)? ) => {
+        $(
+            impl PartialEq for $delta {
+                fn eq(&self, rhs: &Self) -> bool {
+                    self.partial_cmp(rhs) == Some(std::cmp::Ordering::Equal)
+                }
+            }
+
+            impl PartialOrd for $delta {
+                fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
+                    use std::cmp::Ordering;
+                    match (self.0, rhs.0) {
+                        (None, None) => Some(Ordering::Equal),
+                        (None, Some(_)) => Some(Ordering::Less),
+                        (Some(_), None) => Some(Ordering::Greater),
+                        (Some(l), Some(r)) if l.is_nan() && r.is_nan() =>
+                            Some(Ordering::Equal),
+                        (Some(l), Some(r)) if l.is_nan() || r.is_nan() => None,
+                        (Some(l), Some(r)) => Some(l.total_cmp(&r)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_partial_eq_for_float_deltas!(F32Delta, F64Delta);
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f64__delta__nan() -> DeltaResult<()> {
+        let (x0, x1): (f64, f64) = (1.0, f64::NAN);
+        let delta = x0.delta(&x1)?;
+        assert_eq!(delta, F64Delta(Some(f64::NAN)));
+        assert_eq!(delta, x0.delta(&x1)?);
+        assert!(x0.apply(delta)?.is_nan());
+        assert_eq!(x1.delta(&x1)?, x1.delta(&x1)?);
+        assert_ne!(delta, F64Delta(Some(1.0)));
+        assert_eq!(delta.partial_cmp(&F64Delta(Some(1.0))), None);
+        let delta = 1.0f32.delta(&f32::NAN)?;
+        assert_eq!(delta, F32Delta(Some(-f32::NAN)));
+        Ok(())
+    }
+
+    #[test]
+    fn f64__delta__infinity() -> DeltaResult<()> {
+        for x1 in [f64::INFINITY, f64::NEG_INFINITY] {
+            let delta = 0.0f64.delta(&x1)?;
+            assert_eq!(delta, F64Delta(Some(x1)));
+            assert_eq!(0.0f64.apply(delta)?, x1);
+        }
+        assert_ne!(F64Delta(Some(f64::INFINITY)), F64Delta(Some(f64::NEG_INFINITY)));
+        assert!(F64Delta(Some(f64::NEG_INFINITY)) < F64Delta(Some(f64::MIN)));
+        Ok(())
+    }
+
+    #[test]
+    fn f64__delta__signed_zero() -> DeltaResult<()> {
+        let (x0, x1): (f64, f64) = (0.0, -0.0);
+        let delta = x0.delta(&x1)?;
+        assert_ne!(delta, F64Delta(Some(0.0)));
+        assert!(delta < F64Delta(Some(0.0)));
+        let x2: f64 = x0.apply(delta)?;
+        assert_eq!(x2.to_bits(), x1.to_bits());
+        assert_eq!(x1.apply(x1.delta(&x0)?)?.to_bits(), x0.to_bits());
+        Ok(())
+    }
+
+    #[test]
+    fn DeltaOps__is_empty__primitives() -> DeltaResult<()> {
+        assert!(BoolDelta(None).is_empty());
+        assert!(true.delta(&true)?.is_empty());
+        assert!(42u64.delta(&42)?.is_empty());
+        assert!(!true.delta(&false)?.is_empty());
+        assert!(f64::NAN.delta(&f64::NAN)?.is_empty());
+        assert!(!0.0f64.delta(&-0.0)?.is_empty());
+        assert!(!CharDelta(Some('a')).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn EmptyDelta__identity_delta__primitives() -> DeltaResult<()> {
+        assert_eq!(42u8.apply(u8::identity_delta())?, 42);
+        assert!(true.apply(bool::identity_delta())?);
+        assert_eq!('a'.apply(char::identity_delta())?, 'a');
+        assert_eq!(1.5f64.apply(f64::identity_delta())?, 1.5);
+        assert!(u8::identity_delta().is_empty());
+        // NOTE: There's still no value to build from an empty delta:
+        assert!(u8::from_delta(u8::identity_delta()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn Compose__compose__primitives() -> DeltaResult<()> {
+        let (c0, c1, c2): (char, char, char) = ('a', 'b', 'c');
+        let delta = c0.delta(&c1)?.compose(&c1.delta(&c2)?)?;
+        assert_eq!(delta, c0.delta(&c2)?);
+        assert_eq!(c0.apply(delta)?, c2);
+        assert_eq!(CharDelta(Some('a')).compose(&CharDelta(None))?, CharDelta(Some('a')));
+        Ok(())
+    }
+
+    #[test]
+    fn Invert__invert__primitives() -> DeltaResult<()> {
+        let (c0, c1): (char, char) = ('a', 'b');
+        let delta = c0.delta(&c1)?;
+        assert_eq!(c0.apply(delta)?.apply(delta.invert(&c0)?)?, c0);
+        assert_eq!(CharDelta(None).invert(&c0)?, CharDelta(None));
+        let delta = 42u64.delta(&7)?;
+        assert_eq!(7u64.apply(delta.invert(&42)?)?, 42);
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+//! Delta support for [`DateTime<Utc>`], available with the `chrono` feature
+//! (which is also enabled by the `snapshot` feature).
+//!
+//! [`DateTime<Utc>`]: https://docs.rs/chrono/0.4/chrono/struct.DateTime.html
+
+use chrono::{DateTime, Utc};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
+
+
+impl Core for DateTime<Utc> {
+    type Delta = DateTimeDelta;
+}
+
+impl Apply for DateTime<Utc> {
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        Ok(delta.0.unwrap_or(*self))
+    }
+}
+
+impl Delta for DateTime<Utc> {
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        Ok(DateTimeDelta(if self == rhs { None } else { Some(*rhs) }))
+    }
+}
+
+impl FromDelta for DateTime<Utc> {
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        delta.0.ok_or_else(|| ExpectedValue!("DateTimeDelta"))
+    }
+}
+
+impl IntoDelta for DateTime<Utc> {
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        Ok(DateTimeDelta(Some(self)))
+    }
+}
+
+impl EmptyDelta for DateTime<Utc> {
+    fn identity_delta() -> Self::Delta {
+        DateTimeDelta(None)
+    }
+}
+
+
+
+/// The replacement timestamp of a `DateTime<Utc>`, if it changed.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct DateTimeDelta(#[doc(hidden)] pub Option<DateTime<Utc>>);
+
+impl DeltaOps for DateTimeDelta {
+    fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl Compose for DateTimeDelta {
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(DateTimeDelta(next.0.or(self.0)))
+    }
+}
+
+impl Invert<DateTime<Utc>> for DateTimeDelta {
+    fn invert(&self, base: &DateTime<Utc>) -> DeltaResult<Self> {
+        Ok(DateTimeDelta(self.0.map(|_| *base)))
+    }
+}
+
+impl std::fmt::Debug for DateTimeDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match &self.0 {
+            Some(datetime) => write!(f, "DateTimeDelta({})", datetime),
+            None => write!(f, "DateTimeDelta(None)"),
+        }
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn DateTime__delta__same_values() -> DeltaResult<()> {
+        let datetime0: DateTime<Utc> = Utc.timestamp_opt(1_600_000_000, 123).unwrap();
+        let datetime1: DateTime<Utc> = Utc.timestamp_opt(1_600_000_000, 123).unwrap();
+        let delta = datetime0.delta(&datetime1)?;
+        assert_eq!(delta, DateTimeDelta(None));
+        assert_eq!(datetime0.apply(delta)?, datetime0);
+        Ok(())
+    }
+
+    #[test]
+    fn DateTime__delta__different_values() -> DeltaResult<()> {
+        let datetime0: DateTime<Utc> = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+        let datetime1: DateTime<Utc> = Utc.timestamp_opt(1_600_000_042, 5).unwrap();
+        let delta = datetime0.delta(&datetime1)?;
+        let json: String = serde_json::to_string(&delta)
+            .expect("failed to serialize");
+        assert_eq!(json, r#""2020-09-13T12:27:22.000000005Z""#);
+        let delta: DateTimeDelta = serde_json::from_str(&json)
+            .expect("failed to deserialize");
+        assert_eq!(datetime0.apply(delta)?, datetime1);
+        assert_eq!(DateTime::<Utc>::from_delta(datetime1.into_delta()?)?, datetime1);
+        assert!(DateTime::<Utc>::from_delta(DateTimeDelta(None)).is_err());
+        Ok(())
+    }
+}
@@ -151,6 +151,10 @@ pub enum DeltaError {
         line: u32,
         column: u32
     },
+    Codec(String),
+    /// A delta conflicts with the value it's applied to, either at an
+    /// index of a sequence or at a key of a map or set.
+    Conflict { index: Option<usize>, key: Option<String> },
     ExpectedValue {
         type_name: String,
         file: String,
@@ -167,6 +171,11 @@ pub enum DeltaError {
     FailedToApplyDelta { reason: String },
     FailedToConvertFromDelta { reason: String },
     IllegalDelta { index: usize },
+    KeyNotFound(String),
+    /// An integer delta could not be calculated or applied without
+    /// overflowing the named integer type.
+    Overflow(String),
+    ParseError { line: usize, reason: String },
     RwLockAccessWouldBlock,
     RwLockPoisoned(String)
 }
@@ -0,0 +1,142 @@
+//! Approximate deltas for `f32` and `f64`, which ignore differences that
+//! are within some epsilon, e.g. floating point noise.
+//!
+//! NaN is never within any epsilon of a number, so a change from or to
+//! NaN always produces a non-empty delta.  A NaN that stays NaN, however,
+//! is considered unchanged, regardless of its payload or sign.  Likewise,
+//! an infinity that stays the same infinity is unchanged, even though
+//! `inf - inf` is NaN, while any other change from or to an infinity
+//! produces a non-empty delta for any finite or relative epsilon.
+
+use crate::{Apply, Delta, DeltaResult, F32Delta, F64Delta};
+
+
+/// Extension methods for calculating and applying approximate deltas.
+pub trait ApproxDelta: Apply + Delta + Copy {
+    /// Calculate `self --[delta]--> rhs`, where the delta is empty if
+    /// `|self - rhs| <= eps`.
+    fn delta_with_epsilon(&self, rhs: &Self, eps: Self) -> DeltaResult<Self::Delta>;
+
+    /// Calculate `self --[delta]--> rhs`, where the delta is empty if
+    /// `|self - rhs| <= rel_eps * max(|self|, |rhs|)`, i.e. if the values
+    /// differ by at most a fraction `rel_eps` of the larger of the two.
+    fn delta_with_relative_epsilon(
+        &self,
+        rhs: &Self,
+        rel_eps: Self,
+    ) -> DeltaResult<Self::Delta>;
+
+    /// Apply a delta calculated by one of the methods above, where an
+    /// empty delta leaves `self` unchanged.  This is equivalent to
+    /// `Apply::apply()`.
+    fn apply_approx(&self, delta: Self::Delta) -> DeltaResult<Self>;
+}
+
+macro_rules! impl_approx_delta_for_float_types {
+    ( $($type:ty => $delta:ident);* $(;)? ) => {
+        $(
+            impl ApproxDelta for $type {
+                fn delta_with_epsilon(
+                    &self,
+                    rhs: &Self,
+                    eps: Self,
+                ) -> DeltaResult<Self::Delta> {
+                    let unchanged = self == rhs
+                        || (self.is_nan() && rhs.is_nan())
+                        || (self - rhs).abs() <= eps;
+                    Ok($delta(if unchanged { None } else { Some(*rhs) }))
+                }
+
+                fn delta_with_relative_epsilon(
+                    &self,
+                    rhs: &Self,
+                    rel_eps: Self,
+                ) -> DeltaResult<Self::Delta> {
+                    // NOTE: Scaling by an infinity would make every change
+                    //       from or to it fall within the epsilon.
+                    let eps = if self.is_infinite() || rhs.is_infinite() {
+                        0.0
+                    } else {
+                        rel_eps * <$type>::max(self.abs(), rhs.abs())
+                    };
+                    self.delta_with_epsilon(rhs, eps)
+                }
+
+                fn apply_approx(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    Ok(delta.0.unwrap_or(*self))
+                }
+            }
+        )*
+    };
+}
+
+impl_approx_delta_for_float_types! {
+    f32 => F32Delta;
+    f64 => F64Delta;
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DeltaOps;
+
+    #[test]
+    fn f64__delta_with_epsilon() -> DeltaResult<()> {
+        let (x0, x1): (f64, f64) = (1.0, 1.0 + 1e-12);
+        let delta = x0.delta_with_epsilon(&x1, 1e-9)?;
+        assert_eq!(delta, F64Delta(None));
+        assert_eq!(x0.apply_approx(delta)?, x0);
+
+        let x2: f64 = 1.0 + 1e-6;
+        let delta = x0.delta_with_epsilon(&x2, 1e-9)?;
+        assert_eq!(delta, F64Delta(Some(x2)));
+        assert_eq!(x0.apply_approx(delta)?, x2);
+        assert_eq!(x0.apply(delta)?, x2);
+        Ok(())
+    }
+
+    #[test]
+    fn f32__delta_with_relative_epsilon() -> DeltaResult<()> {
+        let (x0, x1): (f32, f32) = (1.0e6, 1.0e6 + 50.0);
+        assert_eq!(x0.delta_with_relative_epsilon(&x1, 1e-4)?, F32Delta(None));
+        assert_eq!(x0.delta_with_relative_epsilon(&x1, 1e-5)?, F32Delta(Some(x1)));
+        // NOTE: The same absolute difference is large for small values:
+        let (x2, x3): (f32, f32) = (1.0, 51.0);
+        assert_eq!(x2.delta_with_relative_epsilon(&x3, 1e-4)?, F32Delta(Some(x3)));
+        Ok(())
+    }
+
+    #[test]
+    fn f64__delta_with_epsilon__nan() -> DeltaResult<()> {
+        let nan: f64 = f64::NAN;
+        assert_eq!(nan.delta_with_epsilon(&nan, 0.0)?, F64Delta(None));
+        let delta = 1.0f64.delta_with_epsilon(&nan, f64::INFINITY)?;
+        assert!(delta.0.unwrap().is_nan());
+        let delta = nan.delta_with_epsilon(&1.0, f64::INFINITY)?;
+        assert_eq!(delta, F64Delta(Some(1.0)));
+        Ok(())
+    }
+
+    #[test]
+    fn f64__delta_with_epsilon__infinity() -> DeltaResult<()> {
+        let (inf, neg_inf): (f64, f64) = (f64::INFINITY, f64::NEG_INFINITY);
+        assert_eq!(inf.delta_with_epsilon(&inf, 1e-9)?, F64Delta(None));
+        assert_eq!(neg_inf.delta_with_epsilon(&neg_inf, 1e-9)?, F64Delta(None));
+        assert_eq!(inf.delta_with_relative_epsilon(&inf, 1e-9)?, F64Delta(None));
+        assert_eq!(inf.delta_with_relative_epsilon(&inf, 0.0)?, F64Delta(None));
+        assert_eq!(inf.delta_with_epsilon(&neg_inf, 1e-9)?, F64Delta(Some(neg_inf)));
+        assert_eq!(1.0f64.delta_with_epsilon(&inf, 1e-9)?, F64Delta(Some(inf)));
+        assert_eq!(inf.delta_with_relative_epsilon(&1.0, 1e-9)?, F64Delta(Some(1.0)));
+        Ok(())
+    }
+
+    #[test]
+    fn DeltaOps__is_empty__approx() -> DeltaResult<()> {
+        assert!(1.0f64.delta_with_epsilon(&(1.0 + 1e-12), 1e-9)?.is_empty());
+        assert!(!1.0f32.delta_with_relative_epsilon(&2.0, 1e-4)?.is_empty());
+        Ok(())
+    }
+}
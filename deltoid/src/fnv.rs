@@ -0,0 +1,73 @@
+//! A 64-bit FNV-1a `Hasher`, for hashes that have to stay the same
+//! across runs, platforms and compiler versions.
+//!
+//! Unlike `std::collections::hash_map::DefaultHasher`, whose algorithm
+//! is explicitly unspecified and may change between Rust releases, the
+//! FNV-1a algorithm is fixed.  Integers are fed to it in little-endian
+//! order, and `usize`s and `isize`s are widened to 64 bits, so that the
+//! hash of a value doesn't depend on the platform either.  The result
+//! is only as stable as the `Hash` impl of the hashed value, though.
+
+use std::hash::Hasher;
+
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self { Self(FNV_OFFSET_BASIS) }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 { self.0 }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 = bytes.iter().fold(self.0, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+        });
+    }
+
+    fn write_u16(&mut self, n: u16) { self.write(&n.to_le_bytes()) }
+    fn write_u32(&mut self, n: u32) { self.write(&n.to_le_bytes()) }
+    fn write_u64(&mut self, n: u64) { self.write(&n.to_le_bytes()) }
+    fn write_u128(&mut self, n: u128) { self.write(&n.to_le_bytes()) }
+    fn write_usize(&mut self, n: usize) { self.write_u64(n as u64) }
+    fn write_i16(&mut self, n: i16) { self.write_u16(n as u16) }
+    fn write_i32(&mut self, n: i32) { self.write_u32(n as u32) }
+    fn write_i64(&mut self, n: i64) { self.write_u64(n as u64) }
+    fn write_i128(&mut self, n: i128) { self.write_u128(n as u128) }
+    fn write_isize(&mut self, n: isize) { self.write_i64(n as i64) }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::Hash;
+
+    #[test]
+    fn FnvHasher__known_values() {
+        let hash = |bytes: &[u8]| {
+            let mut hasher = FnvHasher::default();
+            hasher.write(bytes);
+            hasher.finish()
+        };
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn FnvHasher__platform_independent_integers() {
+        let mut lhs = FnvHasher::default();
+        42usize.hash(&mut lhs);
+        let mut rhs = FnvHasher::default();
+        rhs.write(&42u64.to_le_bytes());
+        assert_eq!(lhs.finish(), rhs.finish());
+    }
+}
@@ -0,0 +1,165 @@
+//! Conversion of deltas to [JSON Patch] documents.
+//!
+//! [JSON Patch]: https://tools.ietf.org/html/rfc6902
+
+use crate::{Apply, Core, DeltaError, DeltaResult, FromDelta};
+use crate::collections::hashmap::{EntryDelta, HashMapDelta};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+
+/// A single JSON Patch operation.
+#[derive(Clone, Debug, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+impl PatchOp {
+    /// Return the JSON Pointer that `self` targets.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Add { path, .. } => path,
+            Self::Remove { path } => path,
+            Self::Replace { path, .. } => path,
+        }
+    }
+}
+
+/// Escape `token` for use as a single reference token in a JSON Pointer,
+/// i.e. replace `~` with `~0` and `/` with `~1`.
+pub fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn to_value<T: Serialize>(value: &T) -> DeltaResult<Value> {
+    serde_json::to_value(value)
+        .map_err(|err| DeltaError::Codec(format!("{}", err)))
+}
+
+/// Return the reference token for a map `key`.  String keys are used as-is,
+/// while any other key is represented by its JSON serialization.
+fn key_token<K: Serialize>(key: &K) -> DeltaResult<String> {
+    Ok(escape_pointer_token(&match to_value(key)? {
+        Value::String(key) => key,
+        key => key.to_string(),
+    }))
+}
+
+/// Push the operations that transform `old` into `new` to `ops`, recursing
+/// into JSON objects so that only the members that changed are replaced.
+fn diff_values(path: &str, old: &Value, new: &Value, ops: &mut Vec<PatchOp>) {
+    match (old, new) {
+        (old, new) if old == new => {/*NOP*/},
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let path = format!("{}/{}", path, escape_pointer_token(key));
+                match new.get(key) {
+                    Some(new_value) => diff_values(&path, old_value, new_value, ops),
+                    None => ops.push(PatchOp::Remove { path }),
+                }
+            }
+            for (key, new_value) in new {
+                if old.contains_key(key) { continue }
+                let path = format!("{}/{}", path, escape_pointer_token(key));
+                ops.push(PatchOp::Add { path, value: new_value.clone() });
+            }
+        },
+        (_, new) => ops.push(PatchOp::Replace {
+            path: path.to_string(),
+            value: new.clone(),
+        }),
+    }
+}
+
+impl<K, V> HashMapDelta<K, V>
+where K: Clone + Debug + PartialEq + Ord + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Ord + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    /// Convert `self` to a sequence of JSON Patch operations that can be
+    /// applied to the JSON serialization of `base`.  An edited entry
+    /// produces operations on the members of its value that changed e.g.
+    /// `/user/name`, rather than a replacement of the entire value.
+    pub fn to_json_patch(&self, base: &HashMap<K, V>) -> DeltaResult<Vec<PatchOp>> {
+        let mut ops: Vec<PatchOp> = vec![];
+        for change in self.iter() { match change {
+            EntryDelta::Edit { key, value } => {
+                let old: &V = base.get(key)
+                    .ok_or_else(|| DeltaError::KeyNotFound(format!("{:?}", key)))?;
+                let new: V = old.apply(value.clone())?;
+                let path = format!("/{}", key_token(key)?);
+                diff_values(&path, &to_value(old)?, &to_value(&new)?, &mut ops);
+            },
+            EntryDelta::Add { key, value } => {
+                let value: V = <V>::from_delta(value.clone())?;
+                ops.push(PatchOp::Add {
+                    path: format!("/{}", key_token(key)?),
+                    value: to_value(&value)?,
+                });
+            },
+            EntryDelta::Remove { key } => ops.push(PatchOp::Remove {
+                path: format!("/{}", key_token(key)?),
+            }),
+        }}
+        Ok(ops)
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Delta;
+
+    #[test]
+    fn escape_pointer_token__special_chars() {
+        assert_eq!(escape_pointer_token("a/b~c"), "a~1b~0c");
+        assert_eq!(escape_pointer_token("~1"), "~01");
+    }
+
+    #[test]
+    fn HashMapDelta__to_json_patch() -> DeltaResult<()> {
+        use std::collections::BTreeMap;
+        let user = |name: &str, age: &str| -> BTreeMap<String, String> {
+            let mut user = BTreeMap::new();
+            user.insert("name".to_string(), name.to_string());
+            user.insert("age".to_string(), age.to_string());
+            user
+        };
+        let mut map0: HashMap<String, BTreeMap<String, String>> = HashMap::new();
+        map0.insert("users/admin".into(), user("root", "42"));
+        map0.insert("stale".into(), user("old", "1"));
+        let mut map1: HashMap<String, BTreeMap<String, String>> = HashMap::new();
+        map1.insert("users/admin".into(), user("admin", "42"));
+        map1.insert("new~key".into(), user("new", "2"));
+        let delta = map0.delta(&map1)?;
+        let mut ops: Vec<PatchOp> = delta.to_json_patch(&map0)?;
+        ops.sort_by(|lhs, rhs| lhs.path().cmp(rhs.path()));
+        assert_eq!(ops, vec![
+            PatchOp::Add {
+                path: "/new~0key".into(),
+                value: serde_json::json!({ "name": "new", "age": "2" }),
+            },
+            PatchOp::Remove { path: "/stale".into() },
+            PatchOp::Replace {
+                path: "/users~1admin/name".into(),
+                value: serde_json::json!("admin"),
+            },
+        ]);
+        let json = serde_json::to_string(&ops[1]).expect("Failed to serialize");
+        assert_eq!(json, r#"{"op":"remove","path":"/stale"}"#);
+        Ok(())
+    }
+}
@@ -6,31 +6,59 @@
 
 #[macro_use] pub mod error;
 #[macro_use] pub mod snapshot;
+#[cfg(feature = "postcard")] pub mod codec;
+pub mod config;
 pub mod core;
+#[cfg(feature = "chrono")] pub mod datetime;
+#[cfg(feature = "json-patch")] pub mod json_patch;
+#[cfg(feature = "numeric-offset")] pub mod offset;
 
 pub mod arrays;
 pub mod borrow;
 pub mod boxed;
 pub mod collections;
+pub mod float;
+mod fnv;
+pub mod marker;
+pub mod net;
+pub mod num;
 pub mod option;
+pub mod path;
 pub mod range;
 pub mod result;
 pub mod rc;
 pub mod string;
 pub mod sync;
+pub mod time;
 pub mod tuple;
 pub mod vec;
+pub mod wrapping;
 
 
 pub use crate::core::*;
+#[cfg(feature = "postcard")] pub use crate::codec::PostcardCodec;
+pub use crate::config::DeltaConfig;
+#[cfg(feature = "chrono")] pub use crate::datetime::DateTimeDelta;
 pub use crate::borrow::CowDelta;
 pub use crate::boxed::*;
 pub use crate::collections::*;
 pub use crate::error::{DeltaError, DeltaResult};
-pub use crate::option::OptionDelta;
+pub use crate::float::ApproxDelta;
+pub use crate::marker::PhantomDataDelta;
+pub use crate::net::{IpAddrDelta, Ipv4AddrDelta, Ipv6AddrDelta};
+pub use crate::num::*;
+#[cfg(feature = "numeric-offset")] pub use crate::offset::*;
+pub use crate::option::{OptionBoxDelta, OptionDelta};
+pub use crate::path::PathBufDelta;
 pub use crate::range::RangeDelta;
 pub use crate::rc::*;
-pub use crate::string::{Str, StringDelta};
+pub use crate::string::{LineHunk, Str, StringDelta, StringOp};
 pub use crate::sync::*;
+pub use crate::time::DurationDelta;
 pub use crate::tuple::*;
-pub use crate::vec::{EltDelta, VecDelta};
+pub use crate::vec::{
+    CheckedVecDelta, ElementChange, EltDelta, IndexedSource, ReversibleVecDelta,
+    SelectedStrategy, VecDelta, VecDeltaBuilder, VecDeltaSeed, VecDiffer,
+    VecStrategy
+};
+pub use crate::wrapping::{WrappingDelta, WrappingOps};
@@ -0,0 +1,90 @@
+//! Delta support for [`PhantomData`].  A `PhantomData<T>` holds no data,
+//! so any 2 values are equal and every delta between them is empty.
+//! This allows e.g. derived types to hold a `PhantomData<&'a T>` field to
+//! use a lifetime parameter.
+//!
+//! [`PhantomData`]: https://doc.rust-lang.org/std/marker/struct.PhantomData.html
+
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult,
+    EmptyDelta, FromDelta, IntoDelta, Invert,
+};
+use std::marker::PhantomData;
+
+
+impl<T: ?Sized> Core for PhantomData<T> {
+    type Delta = PhantomDataDelta;
+}
+
+impl<T: ?Sized> Apply for PhantomData<T> {
+    fn apply(&self, _delta: Self::Delta) -> DeltaResult<Self> {
+        Ok(PhantomData)
+    }
+}
+
+impl<T: ?Sized> Delta for PhantomData<T> {
+    fn delta(&self, _rhs: &Self) -> DeltaResult<Self::Delta> {
+        Ok(PhantomDataDelta)
+    }
+}
+
+impl<T: ?Sized> FromDelta for PhantomData<T> {
+    fn from_delta(_delta: Self::Delta) -> DeltaResult<Self> {
+        Ok(PhantomData)
+    }
+}
+
+impl<T: ?Sized> IntoDelta for PhantomData<T> {
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        Ok(PhantomDataDelta)
+    }
+}
+
+impl<T: ?Sized> EmptyDelta for PhantomData<T> {
+    fn identity_delta() -> Self::Delta {
+        PhantomDataDelta
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct PhantomDataDelta;
+
+impl DeltaOps for PhantomDataDelta {
+    fn is_empty(&self) -> bool { true }
+}
+
+impl Compose for PhantomDataDelta {
+    fn compose(&self, _next: &Self) -> DeltaResult<Self> {
+        Ok(PhantomDataDelta)
+    }
+}
+
+impl<T: ?Sized> Invert<PhantomData<T>> for PhantomDataDelta {
+    fn invert(&self, _base: &PhantomData<T>) -> DeltaResult<Self> {
+        Ok(PhantomDataDelta)
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn PhantomData__delta() -> DeltaResult<()> {
+        let marker0: PhantomData<&'static str> = PhantomData;
+        let delta = marker0.delta(&PhantomData)?;
+        assert!(delta.is_empty());
+        assert_eq!(marker0.apply(delta)?, marker0);
+        let json = serde_json::to_string(&delta).expect("failed to serialize");
+        assert_eq!(json, "null");
+        let delta1: PhantomDataDelta = serde_json::from_str(&json)
+            .expect("failed to deserialize");
+        assert_eq!(delta1, delta);
+        Ok(())
+    }
+}
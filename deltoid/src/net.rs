@@ -0,0 +1,150 @@
+//! Delta support for [`IpAddr`], [`Ipv4Addr`] and [`Ipv6Addr`].
+//!
+//! An address is replaced as a whole when it changes, so a change from
+//! an IPv4 to an IPv6 address (or vice versa) is a plain replacement too.
+//!
+//! [`IpAddr`]: https://doc.rust-lang.org/std/net/enum.IpAddr.html
+//! [`Ipv4Addr`]: https://doc.rust-lang.org/std/net/struct.Ipv4Addr.html
+//! [`Ipv6Addr`]: https://doc.rust-lang.org/std/net/struct.Ipv6Addr.html
+
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+
+macro_rules! impl_delta_trait_for_address_types {
+    ( $($type:ty => $delta:ident);* $(;)? ) => {
+        $(
+            /// The replacement address, if it changed.
+            #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+            #[serde(transparent)]
+            pub struct $delta(#[doc(hidden)] pub Option<$type>);
+
+            impl Core for $type {
+                type Delta = $delta;
+            }
+
+            impl Apply for $type {
+                fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    Ok(delta.0.unwrap_or(*self))
+                }
+            }
+
+            impl Delta for $type {
+                fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+                    Ok($delta(if self == rhs { None } else { Some(*rhs) }))
+                }
+            }
+
+            impl FromDelta for $type {
+                fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+                    delta.0.ok_or_else(|| ExpectedValue!(stringify!($delta)))
+                }
+            }
+
+            impl IntoDelta for $type {
+                fn into_delta(self) -> DeltaResult<Self::Delta> {
+                    Ok($delta(Some(self)))
+                }
+            }
+
+            impl EmptyDelta for $type {
+                fn identity_delta() -> Self::Delta {
+                    $delta(None)
+                }
+            }
+
+            impl DeltaOps for $delta {
+                fn is_empty(&self) -> bool {
+                    self.0.is_none()
+                }
+            }
+
+            impl Compose for $delta {
+                fn compose(&self, next: &Self) -> DeltaResult<Self> {
+                    Ok($delta(next.0.or(self.0)))
+                }
+            }
+
+            impl Invert<$type> for $delta {
+                fn invert(&self, base: &$type) -> DeltaResult<Self> {
+                    Ok($delta(self.0.map(|_| *base)))
+                }
+            }
+
+            impl std::fmt::Debug for $delta {
+                fn fmt(&self, f: &mut std::fmt::Formatter)
+                       -> Result<(), std::fmt::Error>
+                {
+                    match self.0 {
+                        None =>
+                            write!(f, "{}(None)", stringify!($delta)),
+                        Some(addr) =>
+                            write!(f, "{}({})", stringify!($delta), addr),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_trait_for_address_types! {
+    IpAddr   => IpAddrDelta;
+    Ipv4Addr => Ipv4AddrDelta;
+    Ipv6Addr => Ipv6AddrDelta;
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn IpAddr__delta__same_values() -> DeltaResult<()> {
+        let addr0: IpAddr = Ipv4Addr::new(192, 168, 0, 1).into();
+        let addr1: IpAddr = Ipv4Addr::new(192, 168, 0, 1).into();
+        let delta = addr0.delta(&addr1)?;
+        assert_eq!(delta, IpAddrDelta(None));
+        let json = serde_json::to_string(&delta).expect("failed to serialize");
+        assert_eq!(json, "null");
+        assert_eq!(addr0.apply(delta)?, addr1);
+        let addr2 = Ipv6Addr::LOCALHOST;
+        assert_eq!(addr2.delta(&addr2)?, Ipv6AddrDelta(None));
+        Ok(())
+    }
+
+    #[test]
+    fn IpAddr__delta__v4_to_v6() -> DeltaResult<()> {
+        let addr0: IpAddr = Ipv4Addr::new(10, 0, 0, 1).into();
+        let addr1: IpAddr = "fe80::1".parse::<Ipv6Addr>().unwrap().into();
+        let delta = addr0.delta(&addr1)?;
+        assert_eq!(delta, IpAddrDelta(Some(addr1)));
+        let json = serde_json::to_string(&delta).expect("failed to serialize");
+        assert_eq!(json, r#""fe80::1""#);
+        let delta: IpAddrDelta = serde_json::from_str(&json)
+            .expect("failed to deserialize");
+        assert_eq!(addr0.apply(delta)?, addr1);
+        assert_eq!(addr1.apply(addr1.delta(&addr0)?)?, addr0);
+        assert_eq!(IpAddr::from_delta(addr1.into_delta()?)?, addr1);
+        Ok(())
+    }
+
+    #[test]
+    fn Ipv4Addr__delta__serde_round_trip() -> DeltaResult<()> {
+        let addr0 = Ipv4Addr::new(127, 0, 0, 1);
+        let addr1 = Ipv4Addr::new(8, 8, 8, 8);
+        let delta = addr0.delta(&addr1)?;
+        let json = serde_json::to_string(&delta).expect("failed to serialize");
+        assert_eq!(json, r#""8.8.8.8""#);
+        let delta: Ipv4AddrDelta = serde_json::from_str(&json)
+            .expect("failed to deserialize");
+        assert_eq!(addr0.apply(delta)?, addr1);
+        assert!(Ipv4Addr::from_delta(Ipv4AddrDelta(None)).is_err());
+        Ok(())
+    }
+}
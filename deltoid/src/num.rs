@@ -0,0 +1,167 @@
+//! Delta support for the [`NonZero*`] integer types.
+//!
+//! Like the primitive integers, these are replaced as a whole when they
+//! change.  Since a `NonZero*` delta only ever holds a `NonZero*` value,
+//! which also can't be deserialized from a `0`, applying a delta or
+//! converting it back can never produce a zero.
+//!
+//! [`NonZero*`]: https://doc.rust-lang.org/std/num/index.html
+
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
+use std::num::{
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+};
+
+
+macro_rules! impl_delta_trait_for_nonzero_types {
+    ( $($type:ty => $delta:ident);* $(;)? ) => {
+        $(
+            /// The replacement value, if it changed.
+            #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+            #[serde(transparent)]
+            pub struct $delta(#[doc(hidden)] pub Option<$type>);
+
+            impl Core for $type {
+                type Delta = $delta;
+            }
+
+            impl Apply for $type {
+                fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    Ok(delta.0.unwrap_or(*self))
+                }
+            }
+
+            impl Delta for $type {
+                fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+                    Ok($delta(if self == rhs { None } else { Some(*rhs) }))
+                }
+            }
+
+            impl FromDelta for $type {
+                fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+                    delta.0.ok_or_else(|| ExpectedValue!(stringify!($delta)))
+                }
+            }
+
+            impl IntoDelta for $type {
+                fn into_delta(self) -> DeltaResult<Self::Delta> {
+                    Ok($delta(Some(self)))
+                }
+            }
+
+            impl EmptyDelta for $type {
+                fn identity_delta() -> Self::Delta {
+                    $delta(None)
+                }
+            }
+
+            impl DeltaOps for $delta {
+                fn is_empty(&self) -> bool {
+                    self.0.is_none()
+                }
+            }
+
+            impl Compose for $delta {
+                fn compose(&self, next: &Self) -> DeltaResult<Self> {
+                    Ok($delta(next.0.or(self.0)))
+                }
+            }
+
+            impl Invert<$type> for $delta {
+                fn invert(&self, base: &$type) -> DeltaResult<Self> {
+                    Ok($delta(self.0.map(|_| *base)))
+                }
+            }
+
+            impl std::fmt::Debug for $delta {
+                fn fmt(&self, f: &mut std::fmt::Formatter)
+                       -> Result<(), std::fmt::Error>
+                {
+                    match self.0 {
+                        None =>
+                            write!(f, "{}(None)", stringify!($delta)),
+                        Some(value) =>
+                            write!(f, "{}({})", stringify!($delta), value),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_trait_for_nonzero_types! {
+    NonZeroI8    => NonZeroI8Delta;
+    NonZeroI16   => NonZeroI16Delta;
+    NonZeroI32   => NonZeroI32Delta;
+    NonZeroI64   => NonZeroI64Delta;
+    NonZeroI128  => NonZeroI128Delta;
+    NonZeroIsize => NonZeroIsizeDelta;
+
+    NonZeroU8    => NonZeroU8Delta;
+    NonZeroU16   => NonZeroU16Delta;
+    NonZeroU32   => NonZeroU32Delta;
+    NonZeroU64   => NonZeroU64Delta;
+    NonZeroU128  => NonZeroU128Delta;
+    NonZeroUsize => NonZeroUsizeDelta;
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DeltaError;
+
+    #[test]
+    fn NonZeroU32__delta__same_values() -> DeltaResult<()> {
+        let id0 = NonZeroU32::new(42).unwrap();
+        let id1 = NonZeroU32::new(42).unwrap();
+        let delta = id0.delta(&id1)?;
+        assert_eq!(delta, NonZeroU32Delta(None));
+        assert_eq!(id0.apply(delta)?, id1);
+        let n = NonZeroI64::new(-7).unwrap();
+        assert_eq!(n.delta(&n)?, NonZeroI64Delta(None));
+        Ok(())
+    }
+
+    #[test]
+    fn NonZeroU32__delta__round_trip() -> DeltaResult<()> {
+        let id0 = NonZeroU32::new(1).unwrap();
+        let id1 = NonZeroU32::new(u32::MAX).unwrap();
+        let delta = id0.delta(&id1)?;
+        let json = serde_json::to_string(&delta).expect("failed to serialize");
+        assert_eq!(json, u32::MAX.to_string());
+        let delta: NonZeroU32Delta = serde_json::from_str(&json)
+            .expect("failed to deserialize");
+        assert_eq!(id0.apply(delta)?, id1);
+        assert_eq!(NonZeroU32::from_delta(id1.into_delta()?)?, id1);
+        let n = NonZeroI8::new(i8::MIN).unwrap();
+        assert_eq!(NonZeroI8::from_delta(n.into_delta()?)?, n);
+        Ok(())
+    }
+
+    #[test]
+    fn NonZeroU32__from_delta__no_zero() {
+        match NonZeroU32::from_delta(NonZeroU32Delta(None)) {
+            Err(DeltaError::ExpectedValue { type_name, .. }) =>
+                assert_eq!(type_name, "NonZeroU32Delta"),
+            result => panic!("expected an ExpectedValue error: {:?}", result),
+        }
+        let result: Result<NonZeroU32Delta, _> = serde_json::from_str("0");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn DeltaOps__is_empty__NonZeroU32() -> DeltaResult<()> {
+        let (id0, id1) = (NonZeroU32::new(1).unwrap(), NonZeroU32::new(2).unwrap());
+        assert!(id0.delta(&id0)?.is_empty());
+        assert!(!id0.delta(&id1)?.is_empty());
+        Ok(())
+    }
+}
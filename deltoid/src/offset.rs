@@ -0,0 +1,323 @@
+//! Offset-based deltas for integers.
+//!
+//! The delta of a primitive integer replaces the whole value.  Wrapping an
+//! integer in an [`Offset`] instead makes its delta hold the signed offset
+//! `rhs - self`, which is much smaller to serialize when e.g. a large
+//! counter changes by one.  Applying a delta adds the offset back, and an
+//! unchanged value has an empty delta.
+//!
+//! For integers of up to 64 bits the offset is stored in a wider signed
+//! type, so that the offset between any 2 values fits.  Applying an offset
+//! whose result doesn't fit in the integer type returns an `Overflow` error.
+//! There's no wider type for the offsets of 128-bit integers, so those are
+//! stored in an `i128` that wraps around instead, i.e. they're exact modulo
+//! 2^128.
+//!
+//! An `Offset<T>` serializes exactly like a `T`.  Converting a value to and
+//! from a delta uses its offset from zero, i.e. the value itself (except for
+//! `u128` values above `i128::MAX`), so those conversions serialize like the
+//! replacement deltas of `T` too.
+
+use crate::{
+    Apply, Compose, Core, Delta, DeltaError, DeltaOps, DeltaResult,
+    EmptyDelta, FromDelta, IntoDelta, Invert,
+};
+
+
+/// An integer whose deltas are offsets rather than replacement values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+#[serde(transparent)]
+pub struct Offset<T>(pub T);
+
+/// Defines the arithmetic on the offsets of a `$delta` type, each of which
+/// returns `None` if its result doesn't fit in its type.  `checked` offsets
+/// are stored in a type wide enough to hold the offset between any 2
+/// values of `$type`, while `wrapping` offsets wrap around.
+macro_rules! impl_offset_arithmetic {
+    (checked $type:ty => $delta:ident ( $offset:ty )) => {
+        impl $delta {
+            fn offset_between(lhs: $type, rhs: $type) -> Option<$offset> {
+                use std::convert::TryFrom;
+                let lhs: $offset = <$offset>::try_from(lhs).ok()?;
+                let rhs: $offset = <$offset>::try_from(rhs).ok()?;
+                rhs.checked_sub(lhs)
+            }
+
+            fn add_offset(value: $type, offset: $offset) -> Option<$type> {
+                use std::convert::TryFrom;
+                <$offset>::try_from(value).ok()?
+                    .checked_add(offset)
+                    .and_then(|sum| <$type>::try_from(sum).ok())
+            }
+
+            fn sum_offsets(lhs: $offset, rhs: $offset) -> Option<$offset> {
+                lhs.checked_add(rhs)
+            }
+
+            fn negate_offset(offset: $offset) -> Option<$offset> {
+                offset.checked_neg()
+            }
+        }
+    };
+    (wrapping $type:ty => $delta:ident ( $offset:ty )) => {
+        impl $delta {
+            fn offset_between(lhs: $type, rhs: $type) -> Option<$offset> {
+                Some(rhs.wrapping_sub(lhs) as $offset)
+            }
+
+            fn add_offset(value: $type, offset: $offset) -> Option<$type> {
+                Some(value.wrapping_add(offset as $type))
+            }
+
+            fn sum_offsets(lhs: $offset, rhs: $offset) -> Option<$offset> {
+                Some(lhs.wrapping_add(rhs))
+            }
+
+            fn negate_offset(offset: $offset) -> Option<$offset> {
+                Some(offset.wrapping_neg())
+            }
+        }
+    };
+}
+
+macro_rules! impl_delta_trait_for_offset_types {
+    ( $($arithmetic:ident $type:ty => $delta:ident ( $offset:ty ));* $(;)? ) => {
+        $(
+            /// The offset from the old to the new value, if it changed.
+            #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+            pub struct $delta(#[doc(hidden)] pub Option<$offset>);
+
+            impl_offset_arithmetic!($arithmetic $type => $delta ($offset));
+
+            impl Core for Offset<$type> {
+                type Delta = $delta;
+            }
+
+            impl Apply for Offset<$type> {
+                /// Applying an empty delta leaves `self` unchanged.
+                fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    let offset: $offset = match delta.0 {
+                        Some(offset) => offset,
+                        None => return Ok(*self),
+                    };
+                    $delta::add_offset(self.0, offset)
+                        .map(Offset)
+                        .ok_or_else(|| DeltaError::Overflow(stringify!($type).to_string()))
+                }
+            }
+
+            impl Delta for Offset<$type> {
+                fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+                    if self == rhs { return Ok($delta(None)) }
+                    $delta::offset_between(self.0, rhs.0)
+                        .map(|offset| $delta(Some(offset)))
+                        .ok_or_else(|| DeltaError::Overflow(stringify!($type).to_string()))
+                }
+            }
+
+            impl FromDelta for Offset<$type> {
+                fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+                    if delta.0.is_none() {
+                        return Err(ExpectedValue!(stringify!($delta)));
+                    }
+                    Offset(0).apply(delta)
+                }
+            }
+
+            impl IntoDelta for Offset<$type> {
+                fn into_delta(self) -> DeltaResult<Self::Delta> {
+                    $delta::offset_between(0, self.0)
+                        .map(|offset| $delta(Some(offset)))
+                        .ok_or_else(|| DeltaError::Overflow(stringify!($type).to_string()))
+                }
+            }
+
+            impl EmptyDelta for Offset<$type> {
+                fn identity_delta() -> Self::Delta {
+                    $delta(None)
+                }
+            }
+
+            impl DeltaOps for $delta {
+                fn is_empty(&self) -> bool {
+                    matches!(self.0, None | Some(0))
+                }
+            }
+
+            impl Compose for $delta {
+                fn compose(&self, next: &Self) -> DeltaResult<Self> {
+                    match (self.0, next.0) {
+                        (Some(lhs), Some(rhs)) => $delta::sum_offsets(lhs, rhs)
+                            .map(|offset| $delta(Some(offset)))
+                            .ok_or_else(|| DeltaError::Overflow(stringify!($type).to_string())),
+                        (lhs, rhs) => Ok($delta(rhs.or(lhs))),
+                    }
+                }
+            }
+
+            impl Invert<Offset<$type>> for $delta {
+                /// The inverse of an offset doesn't depend on `base`.
+                fn invert(&self, _base: &Offset<$type>) -> DeltaResult<Self> {
+                    match self.0 {
+                        Some(offset) => $delta::negate_offset(offset)
+                            .map(|offset| $delta(Some(offset)))
+                            .ok_or_else(|| DeltaError::Overflow(stringify!($type).to_string())),
+                        None => Ok($delta(None)),
+                    }
+                }
+            }
+
+            impl std::fmt::Debug for $delta {
+                fn fmt(&self, f: &mut std::fmt::Formatter)
+                       -> Result<(), std::fmt::Error>
+                {
+                    match self.0 {
+                        None =>
+                            write!(f, "{}(None)", stringify!($delta)),
+                        Some(offset) =>
+                            write!(f, "{}({:+})", stringify!($delta), offset),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_trait_for_offset_types! {
+    checked  i8    => I8OffsetDelta(i16);
+    checked  i16   => I16OffsetDelta(i32);
+    checked  i32   => I32OffsetDelta(i64);
+    checked  i64   => I64OffsetDelta(i128);
+    wrapping i128  => I128OffsetDelta(i128);
+    checked  isize => IsizeOffsetDelta(i128);
+
+    checked  u8    => U8OffsetDelta(i16);
+    checked  u16   => U16OffsetDelta(i32);
+    checked  u32   => U32OffsetDelta(i64);
+    checked  u64   => U64OffsetDelta(i128);
+    wrapping u128  => U128OffsetDelta(i128);
+    checked  usize => UsizeOffsetDelta(i128);
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn Offset__delta() -> DeltaResult<()> {
+        let (counter0, counter1) = (Offset(4_000_000_000u32), Offset(4_000_000_001u32));
+        let delta = counter0.delta(&counter1)?;
+        assert_eq!(delta, U32OffsetDelta(Some(1)));
+        assert_eq!(counter0.apply(delta)?, counter1);
+        let delta = counter1.delta(&Offset(0))?;
+        assert_eq!(delta, U32OffsetDelta(Some(-4_000_000_001)));
+        assert_eq!(counter1.apply(delta)?, Offset(0));
+        // NOTE: An offset is much smaller to serialize than the new value:
+        let json = serde_json::to_string(&counter0.delta(&counter1)?)
+            .expect("failed to serialize");
+        assert_eq!(json, "1");
+        assert!(json.len() < serde_json::to_string(&4_000_000_001u32.into_delta()?)
+            .expect("failed to serialize")
+            .len());
+        Ok(())
+    }
+
+    #[test]
+    fn Offset__delta__same_values() -> DeltaResult<()> {
+        let delta = Offset(42u32).delta(&Offset(42))?;
+        assert_eq!(delta, U32OffsetDelta(None));
+        assert!(delta.is_empty());
+        assert!(!Offset(42u32).delta(&Offset(43))?.is_empty());
+        for value in [0, i128::MAX as u128, u128::MAX] {
+            assert_eq!(Offset(value).delta(&Offset(value))?, U128OffsetDelta(None));
+        }
+        assert_eq!(Offset(i128::MIN).delta(&Offset(i128::MIN))?, I128OffsetDelta(None));
+        Ok(())
+    }
+
+    #[test]
+    fn Offset__into_delta() -> DeltaResult<()> {
+        // NOTE: An `Offset<T>`, and its conversion to and from a delta,
+        //       serialize the same as a `T`, so that a field can switch
+        //       between the 2 types:
+        let json = serde_json::to_string(&Offset(42u32)).expect("failed to serialize");
+        assert_eq!(json, serde_json::to_string(&42u32).expect("failed to serialize"));
+        let json = serde_json::to_string(&Offset(42u32).into_delta()?)
+            .expect("failed to serialize");
+        assert_eq!(json, serde_json::to_string(&42u32.into_delta()?)
+            .expect("failed to serialize"));
+        let delta: U32OffsetDelta = serde_json::from_str(&json)
+            .expect("failed to deserialize");
+        assert_eq!(Offset::<u32>::from_delta(delta)?, Offset(42));
+        let delta: crate::U32Delta = serde_json::from_str(&json)
+            .expect("failed to deserialize");
+        assert_eq!(u32::from_delta(delta)?, 42);
+        assert!(Offset::<u32>::from_delta(U32OffsetDelta(None)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn Offset__delta__overflow() -> DeltaResult<()> {
+        assert_eq!(
+            Offset(u8::MAX).apply(U8OffsetDelta(Some(1))),
+            Err(DeltaError::Overflow("u8".to_string())),
+        );
+        assert_eq!(
+            Offset(0u8).apply(U8OffsetDelta(Some(-1))),
+            Err(DeltaError::Overflow("u8".to_string())),
+        );
+        assert_eq!(Offset(i8::MIN).apply(Offset(i8::MIN).delta(&Offset(i8::MAX))?)?, Offset(i8::MAX));
+        assert_eq!(Offset(u64::MAX).apply(Offset(u64::MAX).delta(&Offset(0))?)?, Offset(0));
+        Ok(())
+    }
+
+    #[test]
+    fn Offset__delta__128_bit_wrapping() -> DeltaResult<()> {
+        let (n0, n1) = (Offset(i128::MIN), Offset(i128::MAX));
+        assert_eq!(n0.apply(n0.delta(&n1)?)?, n1);
+        assert_eq!(n1.apply(n1.delta(&n0)?)?, n0);
+        let (n0, n1) = (Offset(0u128), Offset(u128::MAX));
+        let delta = n0.delta(&n1)?;
+        assert_eq!(delta, U128OffsetDelta(Some(-1)));
+        assert_eq!(n0.apply(delta)?, n1);
+        assert_eq!(n1.apply(n1.delta(&n0)?)?, n0);
+        assert_eq!(Offset::<u128>::from_delta(n1.into_delta()?)?, n1);
+        Ok(())
+    }
+
+    #[test]
+    fn Offset__compose() -> DeltaResult<()> {
+        let (n0, n1, n2) = (Offset(10u8), Offset(250u8), Offset(5u8));
+        let delta = n0.delta(&n1)?.compose(&n1.delta(&n2)?)?;
+        assert_eq!(delta, U8OffsetDelta(Some(-5)));
+        assert_eq!(n0.apply(delta)?, n2);
+        assert_eq!(
+            I64OffsetDelta(Some(i128::MAX)).compose(&I64OffsetDelta(Some(1))),
+            Err(DeltaError::Overflow("i64".to_string())),
+        );
+        let delta = I128OffsetDelta(Some(i128::MAX)).compose(&I128OffsetDelta(Some(1)))?;
+        assert_eq!(delta, I128OffsetDelta(Some(i128::MIN)));
+        Ok(())
+    }
+
+    #[test]
+    fn Offset__invert() -> DeltaResult<()> {
+        let (n0, n1) = (Offset(10u8), Offset(250u8));
+        let delta = n0.delta(&n1)?;
+        assert_eq!(delta.invert(&n0)?, U8OffsetDelta(Some(-240)));
+        assert_eq!(n1.apply(delta.invert(&n0)?)?, n0);
+        assert_eq!(
+            I64OffsetDelta(Some(i128::MIN)).invert(&Offset(0)),
+            Err(DeltaError::Overflow("i64".to_string())),
+        );
+        let (n0, n1) = (Offset(0i128), Offset(i128::MIN));
+        let delta = n0.delta(&n1)?;
+        assert_eq!(n1.apply(delta.invert(&n0)?)?, n0);
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
 use std::fmt::Debug;
 use serde::{Deserialize, Serialize};
 
@@ -71,6 +74,16 @@ where T: Clone + Debug + PartialEq + IntoDelta
     }
 }
 
+impl<T> EmptyDelta for Option<T>
+where T: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn identity_delta() -> Self::Delta {
+        OptionDelta::None
+    }
+}
+
 
 
 #[derive(Clone, PartialEq)]
@@ -80,6 +93,37 @@ pub enum OptionDelta<T: Core> {
     Some(<T as Core>::Delta),
 }
 
+impl<T: Core> DeltaOps for OptionDelta<T> {
+    /// `OptionDelta::None` leaves any `Option` unchanged, while
+    /// `OptionDelta::Some` turns a `None` into a `Some`.
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::None)
+    }
+}
+
+impl<T: Core + Clone> Compose for OptionDelta<T>
+where T::Delta: Compose
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(match (self, next) {
+            (Self::Some(lhs), Self::Some(rhs)) => Self::Some(lhs.compose(rhs)?),
+            (lhs, Self::None) => lhs.clone(),
+            (Self::None, rhs) => rhs.clone(),
+        })
+    }
+}
+
+impl<T: Core> Invert<Option<T>> for OptionDelta<T>
+where Option<T>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    /// As `OptionDelta::None` leaves a `Some` unchanged, the inverse of a
+    /// delta that turns a `None` into a `Some` can't turn it back.
+    fn invert(&self, base: &Option<T>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 impl<T: Core> std::fmt::Debug for OptionDelta<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         match &self {
@@ -90,6 +134,97 @@ impl<T: Core> std::fmt::Debug for OptionDelta<T> {
 }
 
 
+/// The delta of an `Option<Box<T>>` field in a derived type.  Compared to
+/// an `OptionDelta<Box<T>>` this elides the `BoxDelta` in between, so that
+/// e.g. `Some(5)` serializes as `{"Some":5}` rather than `{"Some":{"0":5}}`.
+/// The `T::Delta` remains boxed so that recursive types still have a size.
+#[derive(Clone, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub enum OptionBoxDelta<T: Core> {
+    None,
+    Some(Box<<T as Core>::Delta>),
+}
+
+impl<T> OptionBoxDelta<T>
+where T: Delta + Apply + FromDelta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    pub fn delta(lhs: &Option<Box<T>>, rhs: &Option<Box<T>>) -> DeltaResult<Self> {
+        Ok(match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) =>
+                Self::Some(Box::new(lhs.as_ref().delta(rhs)?)),
+            (None, Some(rhs)) =>
+                Self::Some(Box::new(rhs.as_ref().clone().into_delta()?)),
+            (_, None) => Self::None,
+        })
+    }
+
+    pub fn apply(base: &Option<Box<T>>, delta: Self) -> DeltaResult<Option<Box<T>>> {
+        Ok(match (base, delta) {
+            (_,       Self::None)    => None,
+            (None,    Self::Some(d)) => Some(Box::new(<T>::from_delta(*d)?)),
+            (Some(t), Self::Some(d)) => Some(Box::new(t.as_ref().apply(*d)?)),
+        })
+    }
+
+    pub fn from_delta(delta: Self) -> DeltaResult<Option<Box<T>>> {
+        Ok(match delta {
+            Self::None => None,
+            Self::Some(d) => Some(Box::new(<T>::from_delta(*d)?)),
+        })
+    }
+
+    pub fn into_delta(value: Option<Box<T>>) -> DeltaResult<Self> {
+        Ok(match value {
+            None => Self::None,
+            Some(t) => Self::Some(Box::new((*t).into_delta()?)),
+        })
+    }
+}
+
+impl<T: Core> DeltaOps for OptionBoxDelta<T> {
+    /// Unlike an `OptionDelta`, an `OptionBoxDelta` is never empty, as
+    /// `OptionBoxDelta::None` turns a `Some` into a `None`, while
+    /// `OptionBoxDelta::Some` turns a `None` into a `Some`.
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl<T: Core + Clone> Compose for OptionBoxDelta<T>
+where T::Delta: Compose
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(match (self, next) {
+            (Self::Some(lhs), Self::Some(rhs)) =>
+                Self::Some(Box::new(lhs.compose(rhs)?)),
+            (_, rhs) => rhs.clone(),
+        })
+    }
+}
+
+impl<T> Invert<Option<Box<T>>> for OptionBoxDelta<T>
+where T: Delta + Apply + FromDelta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn invert(&self, base: &Option<Box<T>>) -> DeltaResult<Self> {
+        let new: Option<Box<T>> = Self::apply(base, self.clone())?;
+        Self::delta(&new, base)
+    }
+}
+
+impl<T: Core> std::fmt::Debug for OptionBoxDelta<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match &self {
+            Self::Some(d) => write!(f, "OptionBoxDelta::Some({:#?})", d),
+            Self::None    => write!(f, "OptionBoxDelta::None"),
+        }
+    }
+}
+
+
 #[allow(non_snake_case)]
 #[cfg(test)]
 mod tests {
@@ -106,7 +241,7 @@ mod tests {
         let json_string = serde_json::to_string(&delta)
             .expect("Could not serialize to json");
         println!("json_string: \"{}\"", json_string);
-        assert_eq!(json_string, "{\"Some\":\"foo\"}");
+        assert_eq!(json_string, "{\"Some\":[]}");
         let delta1: <Option<String> as Core>::Delta = serde_json::from_str(
             &json_string
         ).expect("Could not deserialize from json");
@@ -155,4 +290,41 @@ mod tests {
         assert_eq!(option1, option2);
         Ok(())
     }
+
+    #[test]
+    fn DeltaOps__is_empty__Option() -> DeltaResult<()> {
+        let (option0, option1): (Option<u8>, Option<u8>) = (None, Some(1));
+        assert!(option0.delta(&option0)?.is_empty());
+        assert!(!option0.delta(&option1)?.is_empty());
+        assert!(!OptionBoxDelta::<u8>::None.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn OptionDelta__compose() -> DeltaResult<()> {
+        let (option0, option1, option2) = (None, Some(1u8), Some(2u8));
+        let delta = option0.delta(&option1)?.compose(&option1.delta(&option2)?)?;
+        assert_eq!(delta, option0.delta(&option2)?);
+        assert_eq!(option0.apply(delta)?, option2);
+        let delta = option1.delta(&option2)?.compose(&OptionDelta::None)?;
+        assert_eq!(delta, option1.delta(&option2)?);
+        let (boxed0, boxed1) = (Some(Box::new(1u8)), Some(Box::new(2u8)));
+        let delta = OptionBoxDelta::delta(&boxed0, &boxed1)?
+            .compose(&OptionBoxDelta::delta(&boxed1, &None)?)?;
+        assert_eq!(OptionBoxDelta::apply(&boxed0, delta)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn OptionDelta__identity_delta() -> DeltaResult<()> {
+        for option in [None, Some(1u8)] {
+            assert_eq!(option.apply(Option::<u8>::identity_delta())?, option);
+        }
+        assert!(Option::<u8>::identity_delta().is_empty());
+        let boxed: Box<[u8; 2]> = Box::new([1, 2]);
+        assert_eq!(boxed.apply(Box::<[u8; 2]>::identity_delta())?, boxed);
+        let result: Result<u8, String> = Err(String::from("foo"));
+        assert_eq!(result.apply(Result::<u8, String>::identity_delta())?, result);
+        Ok(())
+    }
 }
@@ -0,0 +1,133 @@
+//! Delta support for [`PathBuf`].
+//!
+//! Like `&str`, a borrowed `&Path` is unsupported: applying a delta must
+//! produce a new value, which a borrowed type can't own.  Use `PathBuf`
+//! (or e.g. `Cow<'_, Path>` converted to a `PathBuf`) instead.
+//!
+//! [`PathBuf`]: https://doc.rust-lang.org/std/path/struct.PathBuf.html
+
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
+use std::path::PathBuf;
+
+
+impl Core for PathBuf {
+    type Delta = PathBufDelta;
+}
+
+impl Apply for PathBuf {
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        Ok(delta.0.unwrap_or_else(|| self.clone()))
+    }
+}
+
+impl Delta for PathBuf {
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        Ok(PathBufDelta(if self == rhs { None } else { Some(rhs.clone()) }))
+    }
+}
+
+impl FromDelta for PathBuf {
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        delta.0.ok_or_else(|| ExpectedValue!("PathBufDelta"))
+    }
+}
+
+impl IntoDelta for PathBuf {
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        Ok(PathBufDelta(Some(self)))
+    }
+}
+
+impl EmptyDelta for PathBuf {
+    fn identity_delta() -> Self::Delta {
+        PathBufDelta(None)
+    }
+}
+
+
+/// The replacement path of a `PathBuf`, if it changed.  This serializes
+/// as a plain optional path, which like `PathBuf` itself requires the
+/// path to be valid UTF-8.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+#[serde(transparent)]
+pub struct PathBufDelta(#[doc(hidden)] pub Option<PathBuf>);
+
+impl DeltaOps for PathBufDelta {
+    fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl Compose for PathBufDelta {
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(PathBufDelta(next.0.as_ref().or(self.0.as_ref()).cloned()))
+    }
+}
+
+impl Invert<PathBuf> for PathBufDelta {
+    fn invert(&self, base: &PathBuf) -> DeltaResult<Self> {
+        Ok(PathBufDelta(self.0.as_ref().map(|_| base.clone())))
+    }
+}
+
+impl std::fmt::Debug for PathBufDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match &self.0 {
+            Some(path) => write!(f, "PathBufDelta({:#?})", path),
+            None       => write!(f, "PathBufDelta(None)"),
+        }
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn PathBuf__delta__same_values() -> DeltaResult<()> {
+        let path0 = PathBuf::from("/etc/hosts");
+        let path1 = PathBuf::from("/etc/hosts");
+        let delta = path0.delta(&path1)?;
+        assert_eq!(delta, PathBufDelta(None));
+        let json = serde_json::to_string(&delta).expect("failed to serialize");
+        assert_eq!(json, "null");
+        assert_eq!(path0.apply(delta)?, path1);
+        Ok(())
+    }
+
+    #[test]
+    fn PathBuf__delta__different_values() -> DeltaResult<()> {
+        let path0 = PathBuf::from("/etc/hosts");
+        let path1 = PathBuf::from("/etc/hostname");
+        let delta = path0.delta(&path1)?;
+        assert_eq!(delta, PathBufDelta(Some(path1.clone())));
+        let json = serde_json::to_string(&delta).expect("failed to serialize");
+        assert_eq!(json, r#""/etc/hostname""#);
+        let delta: PathBufDelta = serde_json::from_str(&json)
+            .expect("failed to deserialize");
+        assert_eq!(path0.apply(delta)?, path1);
+        assert_eq!(PathBuf::from_delta(path1.clone().into_delta()?)?, path1);
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn PathBuf__delta__non_utf8_values() -> DeltaResult<()> {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        let path0 = PathBuf::from("/tmp/a");
+        let path1 = PathBuf::from(OsStr::from_bytes(b"/tmp/\xff"));
+        let delta = path0.delta(&path1)?;
+        assert_eq!(path0.apply(delta.clone())?, path1);
+        // NOTE: Such a delta can be applied, but not serialized:
+        assert!(serde_json::to_string(&delta).is_err());
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de;
 use serde::ser::SerializeMap;
@@ -64,11 +67,39 @@ where T: Clone + Debug + PartialEq + IntoDelta
     }
 }
 
+impl<T> EmptyDelta for Range<T>
+where T: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn identity_delta() -> Self::Delta {
+        RangeDelta(None)
+    }
+}
+
 
 
 #[derive(Clone, PartialEq, Hash)]
 pub struct RangeDelta<T>(#[doc(hidden)] pub Option<Range<T>>);
 
+impl<T> DeltaOps for RangeDelta<T> {
+    fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl<T: Clone> Compose for RangeDelta<T> {
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(RangeDelta(next.0.as_ref().or(self.0.as_ref()).cloned()))
+    }
+}
+
+impl<T: Clone> Invert<Range<T>> for RangeDelta<T> {
+    fn invert(&self, base: &Range<T>) -> DeltaResult<Self> {
+        Ok(RangeDelta(self.0.as_ref().map(|_| base.clone())))
+    }
+}
+
 impl<T> std::fmt::Debug for RangeDelta<T>
 where T: Core + std::fmt::Debug {
     fn fmt(&self, f: &mut std::fmt::Formatter)
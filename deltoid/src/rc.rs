@@ -3,7 +3,10 @@
 //!
 //! [`Rc`]: https://doc.rust-lang.org/std/rc/struct.Rc.html
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de;
 use serde::ser::SerializeMap;
@@ -72,6 +75,16 @@ where T: Clone + Debug + PartialEq + IntoDelta
     }
 }
 
+impl<T> EmptyDelta for Rc<T>
+where T: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn identity_delta() -> Self::Delta {
+        RcDelta(None)
+    }
+}
+
 
 
 #[derive(Clone, PartialEq)]
@@ -79,6 +92,32 @@ pub struct RcDelta<T: Core>(
     #[doc(hidden)] pub Option<Box<<T as Core>::Delta>>
 );
 
+impl<T: Core> DeltaOps for RcDelta<T> {
+    fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl<T: Core> Compose for RcDelta<T>
+where T::Delta: Compose
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(RcDelta(match (&self.0, &next.0) {
+            (Some(lhs), Some(rhs)) => Some(Box::new(lhs.compose(rhs)?)),
+            (lhs, rhs) => rhs.as_ref().or(lhs.as_ref()).cloned(),
+        }))
+    }
+}
+
+impl<T: Core> Invert<Rc<T>> for RcDelta<T>
+where Rc<T>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &Rc<T>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 impl<T: Core> std::fmt::Debug for RcDelta<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         match &self.0 {
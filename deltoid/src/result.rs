@@ -1,6 +1,9 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -90,6 +93,15 @@ where T: Clone + Debug + PartialEq + IntoDelta
     }
 }
 
+impl<T, E> EmptyDelta for Result<T, E>
+where T: Clone + Debug + PartialEq + Core + for<'de> Deserialize<'de> + Serialize,
+      E: Clone + Debug + PartialEq + Core + for<'de> Deserialize<'de> + Serialize,
+{
+    fn identity_delta() -> Self::Delta {
+        ResultDelta::None
+    }
+}
+
 
 
 #[derive(Clone, PartialEq)]
@@ -100,6 +112,41 @@ pub enum ResultDelta<T: Core, E: Core> {
     None
 }
 
+impl<T: Core, E: Core> DeltaOps for ResultDelta<T, E> {
+    /// Only `ResultDelta::None` is empty, as the other variants replace
+    /// an `Err` with an `Ok` value or vice versa.
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::None)
+    }
+}
+
+impl<T: Core + Clone, E: Core + Clone> Compose for ResultDelta<T, E>
+where T::Delta: Compose,
+      E::Delta: Compose,
+{
+    /// A delta of the other variant than `self` replaces the value that
+    /// `self` results in, while one of the same variant changes it.
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(match (self, next) {
+            (Self::OkDelta(lhs), Self::OkDelta(rhs)) =>
+                Self::OkDelta(lhs.compose(rhs)?),
+            (Self::ErrDelta(lhs), Self::ErrDelta(rhs)) =>
+                Self::ErrDelta(lhs.compose(rhs)?),
+            (lhs, Self::None) => lhs.clone(),
+            (_, rhs) => rhs.clone(),
+        })
+    }
+}
+
+impl<T: Core, E: Core> Invert<Result<T, E>> for ResultDelta<T, E>
+where Result<T, E>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &Result<T, E>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 impl<T, E> std::fmt::Debug for ResultDelta<T, E>
 where T: Core, E: Core {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
@@ -237,4 +284,35 @@ mod tests {
         assert_eq!(box1, box2);
         Ok(())
     }
+
+    #[test]
+    fn DeltaOps__is_empty__Result() -> DeltaResult<()> {
+        let (result0, result1): (Result<u8, ()>, Result<u8, ()>) = (Ok(1), Err(()));
+        assert!(result0.delta(&result0)?.is_empty());
+        assert!(!result0.delta(&result1)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn ResultDelta__compose() -> DeltaResult<()> {
+        let result0: Result<u8, String> = Ok(1);
+        let (result1, result2) = (Err(String::from("foo")), Ok(2));
+        let delta = result0.delta(&result1)?.compose(&result1.delta(&result2)?)?;
+        assert_eq!(delta, result0.delta(&result2)?);
+        assert_eq!(result0.apply(delta)?, result2);
+        let delta = result0.delta(&result2)?.compose(&result2.delta(&result2)?)?;
+        assert_eq!(delta, result0.delta(&result2)?);
+        Ok(())
+    }
+
+    #[test]
+    fn ResultDelta__invert() -> DeltaResult<()> {
+        let result0: Result<u8, String> = Ok(1);
+        for result1 in [Ok(2), Err(String::from("foo"))] {
+            let delta = result0.delta(&result1)?;
+            let inverse = delta.invert(&result0)?;
+            assert_eq!(result0.apply(delta)?.apply(inverse)?, result0);
+        }
+        Ok(())
+    }
 }
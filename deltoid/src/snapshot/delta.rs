@@ -134,19 +134,36 @@ impl<T: Apply + Delta + Default> DeltaSnapshots<T> {
         self.snapshots.drain(..).collect()
     }
 
+    /// Apply the deltas of `self` in order to a single running state,
+    /// calling `f` with each snapshot and the state right after it, and
+    /// return the final state.  The deltas are applied in place, so that
+    /// types overriding `Apply::apply_mut` (e.g. maps) only do work
+    /// proportional to the size of each delta.
+    pub fn replay<F>(self, mut f: F) -> DeltaResult<T>
+    where F: FnMut(&DeltaSnapshot<T>, &T) -> DeltaResult<()> {
+        let mut state: T = FullSnapshot::<T>::default().state;
+        for snapshot in self.snapshots {
+            state.apply_mut(snapshot.delta.clone())?;
+            f(&snapshot, &state)?;
+        }
+        Ok(state)
+    }
+
+    /// Reconstruct the full state of each snapshot.  Since every returned
+    /// `FullSnapshot` owns a copy of its state, the cost of this is
+    /// dominated by the size of the output rather than of the deltas;
+    /// use `Self::replay()` to visit the states without copying them.
     pub fn to_full_snapshots(self) -> DeltaResult<FullSnapshots<T>> {
-        let initial = FullSnapshot::default();
         let mut uncompressed: Vec<FullSnapshot<T>> = vec![];
-        for snapshot in self.snapshots {
-            let old: &T = &uncompressed.last().unwrap_or(&initial).state;
-            let new: T = old.apply(snapshot.delta)?;
+        self.replay(|snapshot, state| {
             uncompressed.push(FullSnapshot {
                 timestamp: snapshot.timestamp,
-                origin:    snapshot.origin,
+                origin:    snapshot.origin.clone(),
                 msg:       snapshot.msg.clone(),
-                state:     new,
+                state:     state.clone(),
             });
-        }
+            Ok(())
+        })?;
         Ok(FullSnapshots(uncompressed))
     }
 
@@ -239,3 +256,88 @@ impl<T: Core + Ord> Ord for DeltaSnapshot<T> {
         Ordering::Equal
     }
 }
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn DeltaSnapshots__to_full_snapshots__hashmap() -> DeltaResult<()> {
+        let mut snapshots: DeltaSnapshots<HashMap<u16, u16>> = Default::default();
+        let mut expected: Vec<HashMap<u16, u16>> = vec![];
+        let mut state: HashMap<u16, u16> = HashMap::new();
+        for i in 0 .. 200u16 {
+            state.insert(i % 50, i);
+            snapshots.push_snapshot("test".into(), None, state.clone())?;
+            expected.push(state.clone());
+        }
+        assert!(snapshots.iter().all(|snapshot| snapshot.delta.len() == 1));
+        let full: FullSnapshots<HashMap<u16, u16>> = snapshots.to_full_snapshots()?;
+        assert_eq!(full.len(), 200);
+        for (snapshot, expected) in full.iter().zip(expected.iter()) {
+            assert_eq!(&snapshot.state, expected);
+        }
+        Ok(())
+    }
+
+    thread_local! {
+        static CLONES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    /// A value that counts how often it's cloned.
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+    struct Counted(u16);
+
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            CLONES.with(|clones| clones.set(clones.get() + 1));
+            Counted(self.0)
+        }
+    }
+
+    impl Core for Counted { type Delta = Counted; }
+
+    impl Apply for Counted {
+        fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> { Ok(delta) }
+    }
+
+    impl Delta for Counted {
+        fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> { Ok(rhs.clone()) }
+    }
+
+    impl crate::FromDelta for Counted {
+        fn from_delta(delta: Self::Delta) -> DeltaResult<Self> { Ok(delta) }
+    }
+
+    impl crate::IntoDelta for Counted {
+        fn into_delta(self) -> DeltaResult<Self::Delta> { Ok(self) }
+    }
+
+    #[test]
+    fn DeltaSnapshots__replay__bounded_work() -> DeltaResult<()> {
+        let mut snapshots: DeltaSnapshots<HashMap<u16, Counted>> = Default::default();
+        for i in 0 .. 200u16 {
+            let mut state: HashMap<u16, Counted> = snapshots.current().state.clone();
+            state.insert(i % 50, Counted(i));
+            snapshots.push_snapshot("test".into(), None, state)?;
+        }
+        let expected: HashMap<u16, Counted> = snapshots.current().state.clone();
+        CLONES.with(|clones| clones.set(0));
+        let mut visited: usize = 0;
+        let state = snapshots.replay(|_, state| {
+            visited += 1;
+            assert!(state.len() <= 50);
+            Ok(())
+        })?;
+        // NOTE: Each of the 200 single-entry deltas is cloned once to be
+        //       applied, and the 50-entry state is never cloned:
+        assert_eq!(CLONES.with(|clones| clones.get()), 200);
+        assert_eq!(visited, 200);
+        assert_eq!(state, expected);
+        Ok(())
+    }
+}
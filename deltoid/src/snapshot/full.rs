@@ -228,3 +228,36 @@ impl<T: Core + Ord> Ord for FullSnapshot<T> {
         Ordering::Equal
     }
 }
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EltDelta, IntoDelta, VecDelta};
+
+    #[test]
+    fn FullSnapshots__to_delta_snapshots__appending_vec() -> DeltaResult<()> {
+        let mut snapshots: FullSnapshots<Vec<u32>> = FullSnapshots(vec![]);
+        let mut state: Vec<u32> = vec![];
+        for i in 0 .. 50u32 {
+            state.push(i);
+            snapshots.push_snapshot("test".into(), None, state.clone())?;
+        }
+        let deltas: DeltaSnapshots<Vec<u32>> = snapshots.to_delta_snapshots()?;
+        let deltas: Vec<VecDelta<u32>> = deltas.into_iter()
+            .map(|snapshot| snapshot.delta)
+            .collect();
+        assert_eq!(deltas.len(), 50);
+        // NOTE: Appending to the initial, empty `Vec` sets all of its values:
+        let expected = VecDelta(vec![EltDelta::SetAll(vec![0u32.into_delta()?])]);
+        assert_eq!(deltas[0], expected);
+        for (i, delta) in deltas.iter().enumerate().skip(1) {
+            let expected = VecDelta(vec![EltDelta::Add((i as u32).into_delta()?)]);
+            assert_eq!(delta, &expected);
+            assert!(delta.is_append_only());
+        }
+        Ok(())
+    }
+}
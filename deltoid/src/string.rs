@@ -1,7 +1,13 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaError, DeltaOps, DeltaResult,
+    EmptyDelta, FromDelta, IntoDelta, Invert,
+};
+use crate::vec::myers::{self, DiffOp};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::borrow::Cow;
+#[cfg(feature = "unicode")] use unicode_segmentation::UnicodeSegmentation;
 
 impl Core for String {
     type Delta = StringDelta;
@@ -9,44 +15,491 @@ impl Core for String {
 
 impl Apply for String {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
-        Self::from_delta(delta)
+        match delta {
+            StringDelta::Replace(value) => Ok(value),
+            StringDelta::Edit(ops) =>
+                apply_string_ops(self, ops, |pos| self.is_char_boundary(pos)),
+            StringDelta::Lines { hunks } => apply_line_hunks(self, hunks),
+            #[cfg(feature = "unicode")]
+            StringDelta::Graphemes(ops) => {
+                let boundaries: Vec<usize> = self.grapheme_indices(true)
+                    .map(|(pos, _)| pos)
+                    .chain(std::iter::once(self.len()))
+                    .collect();
+                apply_string_ops(self, ops, |pos| boundaries.binary_search(&pos).is_ok())
+            },
+        }
     }
 }
 
 impl Delta for String {
     fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
-        rhs.clone().into_delta()
+        let ops: Vec<StringOp> = match diff_string_units(&chars(self), &chars(rhs)) {
+            Some(ops) => ops,
+            None => return rhs.clone().into_delta(),
+        };
+        if ops.is_empty() { return Ok(StringDelta::Edit(ops)) }
+        // NOTE: Only keep the ops if they're smaller than `rhs` itself,
+        //       where each op is estimated to take about a byte:
+        let size: usize = ops.iter()
+            .map(|op| match op { StringOp::Insert(s) => 1 + s.len(), _ => 1 })
+            .sum();
+        if size < rhs.len() {
+            Ok(StringDelta::Edit(ops))
+        } else {
+            rhs.clone().into_delta()
+        }
     }
 }
 
 impl FromDelta for String {
     fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
-        delta.0.ok_or_else(|| ExpectedValue!("StringDelta<T>"))
+        match delta {
+            StringDelta::Replace(value) => Ok(value),
+            _ => Err(ExpectedValue!("StringDelta")),
+        }
     }
 }
 
 impl IntoDelta for String {
     fn into_delta(self) -> DeltaResult<Self::Delta> {
-        Ok(StringDelta(Some(self)))
+        Ok(StringDelta::Replace(self))
     }
 }
 
+impl EmptyDelta for String {
+    fn identity_delta() -> Self::Delta {
+        StringDelta::Edit(vec![])
+    }
+}
+
+/// Split `s` into its `char`s, as string slices.
+fn chars(s: &str) -> Vec<&str> {
+    s.char_indices().map(|(pos, c)| &s[pos .. pos + c.len_utf8()]).collect()
+}
+
+/// The largest number of units that `diff_string_units()` inserts or
+/// deletes between the common prefix and suffix of 2 strings before it
+/// gives up, which bounds its running time to `O(N * MAX_EDIT_DISTANCE)`.
+const MAX_EDIT_DISTANCE: usize = 256;
+
+/// Calculate the ops that transform `lhs` into `rhs`, where both are
+/// split into units, e.g. `char`s, so that every op starts and ends on
+/// a unit boundary.  Returns `None` if the units in between the common
+/// prefix and suffix of `lhs` and `rhs` need more than
+/// `MAX_EDIT_DISTANCE` insertions and deletions, in which case the ops
+/// wouldn't be much smaller than `rhs` anyway.
+fn diff_string_units(lhs: &[&str], rhs: &[&str]) -> Option<Vec<StringOp>> {
+    let prefix: usize = lhs.iter().zip(rhs).take_while(|(l, r)| l == r).count();
+    let (lhs, rhs, head): (&[&str], &[&str], &[&str]) =
+        (&lhs[prefix ..], &rhs[prefix ..], &lhs[.. prefix]);
+    let suffix: usize = lhs.iter().rev().zip(rhs.iter().rev())
+        .take_while(|(l, r)| l == r)
+        .count();
+    let tail: usize = lhs[lhs.len() - suffix ..].iter().map(|unit| unit.len()).sum();
+    let (lhs, rhs) = (&lhs[.. lhs.len() - suffix], &rhs[.. rhs.len() - suffix]);
+    let diff: Vec<DiffOp> = if lhs.is_empty() || rhs.is_empty() {
+        (0 .. lhs.len()).map(|old| DiffOp::Delete { old })
+            .chain((0 .. rhs.len()).map(|new| DiffOp::Insert { new }))
+            .collect()
+    } else {
+        myers::diff_bounded(lhs.len(), rhs.len(), MAX_EDIT_DISTANCE, |i, j| lhs[i] == rhs[j])?
+    };
+    let mut ops: Vec<StringOp> = vec![];
+    push_string_op(&mut ops, StringOp::Retain(head.iter().map(|unit| unit.len()).sum()));
+    for op in diff {
+        push_string_op(&mut ops, match op {
+            DiffOp::Equal { old, .. } => StringOp::Retain(lhs[old].len()),
+            DiffOp::Delete { old } => StringOp::Delete(lhs[old].len()),
+            DiffOp::Insert { new } => StringOp::Insert(rhs[new].to_string()),
+        });
+    }
+    push_string_op(&mut ops, StringOp::Retain(tail));
+    Some(unless_unchanged(ops))
+}
+
+/// Return `ops`, or no ops at all if `ops` only retain the string.  Any
+/// other non-empty ops cover the whole string they're replayed against.
+fn unless_unchanged(mut ops: Vec<StringOp>) -> Vec<StringOp> {
+    if let [StringOp::Retain(_)] = ops[..] { ops.clear(); }
+    ops
+}
+
+/// Replay `ops` against `base`, which they must cover exactly, unless
+/// there are no `ops` at all, which leave any `base` unchanged.  Each
+/// `StringOp::Retain` and `StringOp::Delete` must end on a position of
+/// `base` for which `is_boundary` holds, which must imply that it's a
+/// `char` boundary.
+fn apply_string_ops<F>(base: &str, ops: Vec<StringOp>, is_boundary: F) -> DeltaResult<String>
+where F: Fn(usize) -> bool {
+    if ops.is_empty() { return Ok(base.to_string()) }
+    let mut new = String::with_capacity(base.len());
+    // NOTE: `pos` is the byte offset in `base`, and always a boundary:
+    let mut pos: usize = 0;
+    for op in ops {
+        match op {
+            StringOp::Retain(n) | StringOp::Delete(n) => {
+                let end: usize = pos.checked_add(n)
+                    .ok_or(DeltaError::IllegalDelta { index: pos })?;
+                ensure_le![end, base.len()]?;
+                if !is_boundary(end) {
+                    return Err(DeltaError::IllegalDelta { index: end });
+                }
+                if let StringOp::Retain(_) = op { new.push_str(&base[pos .. end]); }
+                pos = end;
+            },
+            StringOp::Insert(s) => new.push_str(&s),
+        }
+    }
+    // NOTE: A `base` that's longer than the ops cover isn't the one the
+    //       ops were calculated against, e.g. because they were applied
+    //       to it already:
+    if pos != base.len() {
+        return Err(DeltaError::IllegalDelta { index: pos });
+    }
+    Ok(new)
+}
+
+/// Append `op` to `ops`, merging it into the last op if it's of the
+/// same kind, and dropping it if it's empty.
+fn push_string_op(ops: &mut Vec<StringOp>, op: StringOp) {
+    match (ops.last_mut(), op) {
+        (_, StringOp::Retain(0)) | (_, StringOp::Delete(0)) => {},
+        (_, StringOp::Insert(s)) if s.is_empty() => {},
+        (Some(StringOp::Retain(n)), StringOp::Retain(m)) => *n += m,
+        (Some(StringOp::Delete(n)), StringOp::Delete(m)) => *n += m,
+        (Some(StringOp::Insert(s)), StringOp::Insert(t)) => s.push_str(&t),
+        (_, op) => ops.push(op),
+    }
+}
 
+/// Return what remains of `op` after its first `n` bytes.  `n` is at most
+/// the length of `op`, and `offset` is the position of `op` in the string
+/// that `op` produces or consumes, which is used for errors.
+fn rest_of_string_op(op: StringOp, n: usize, offset: usize) -> DeltaResult<Option<StringOp>> {
+    Ok(match op {
+        StringOp::Retain(len) if len > n => Some(StringOp::Retain(len - n)),
+        StringOp::Delete(len) if len > n => Some(StringOp::Delete(len - n)),
+        StringOp::Insert(s) if s.len() > n => match s.get(n ..) {
+            Some(rest) => Some(StringOp::Insert(rest.to_string())),
+            None => return Err(DeltaError::IllegalDelta { index: offset + n }),
+        },
+        _ => None,
+    })
+}
+
+/// Compose `first` and `second` into ops that have the same effect as
+/// replaying `first` against a string, and then `second` against the
+/// result.  The string produced by `first` is walked with both sequences
+/// of ops at once: `first` retains or inserts it, while `second` retains
+/// or deletes it.
+fn compose_string_ops(first: &[StringOp], second: &[StringOp]) -> DeltaResult<Vec<StringOp>> {
+    // NOTE: No ops at all leave any string unchanged:
+    if first.is_empty() { return Ok(second.to_vec()) }
+    if second.is_empty() { return Ok(first.to_vec()) }
+    let mut ops: Vec<StringOp> = vec![];
+    let (mut first, mut second) = (first.iter().cloned(), second.iter().cloned());
+    let (mut lhs, mut rhs) = (first.next(), second.next());
+    // NOTE: `mid` is the byte offset in the string produced by `first`:
+    let mut mid: usize = 0;
+    loop {
+        match (lhs.take(), rhs.take()) {
+            (None, None) => break,
+            (next_lhs, Some(StringOp::Insert(s))) => {
+                push_string_op(&mut ops, StringOp::Insert(s));
+                lhs = next_lhs;
+                rhs = second.next();
+            },
+            (Some(StringOp::Delete(n)), next_rhs) => {
+                push_string_op(&mut ops, StringOp::Delete(n));
+                lhs = first.next();
+                rhs = next_rhs;
+            },
+            // NOTE: `second` must cover exactly the string `first` produces:
+            (None, Some(_)) | (Some(_), None) =>
+                return Err(DeltaError::IllegalDelta { index: mid }),
+            (Some(l), Some(r)) => {
+                let n: usize = l.len().min(r.len());
+                match (&l, &r) {
+                    (StringOp::Retain(_), StringOp::Retain(_)) =>
+                        push_string_op(&mut ops, StringOp::Retain(n)),
+                    (StringOp::Retain(_), StringOp::Delete(_)) =>
+                        push_string_op(&mut ops, StringOp::Delete(n)),
+                    (StringOp::Insert(s), StringOp::Retain(_)) => {
+                        let head: &str = s.get(.. n)
+                            .ok_or(DeltaError::IllegalDelta { index: mid + n })?;
+                        push_string_op(&mut ops, StringOp::Insert(head.to_string()));
+                    },
+                    // NOTE: Inserting and then deleting the same bytes is a no-op:
+                    _ => {},
+                }
+                lhs = match rest_of_string_op(l, n, mid)? { None => first.next(), rest => rest };
+                rhs = match rest_of_string_op(r, n, mid)? { None => second.next(), rest => rest };
+                mid += n;
+            },
+        }
+    }
+    Ok(unless_unchanged(ops))
+}
+
+/// Calculate the hunks that transform the lines of `lhs` into those of
+/// `rhs`, where each maximal run of changed lines forms a hunk.
+fn diff_line_hunks(lhs: &str, rhs: &str) -> Vec<LineHunk> {
+    let lhs: Vec<&str> = lhs.split('\n').collect();
+    let rhs: Vec<&str> = rhs.split('\n').collect();
+    let mut hunks: Vec<LineHunk> = vec![];
+    // NOTE: `old` is the number of `lhs` lines before the current op, and
+    //       `extend` is whether the op continues the last hunk:
+    let (mut old, mut extend) = (0, false);
+    for op in myers::diff(lhs.len(), rhs.len(), |i, j| lhs[i] == rhs[j]) {
+        let hunk: &mut LineHunk = match op {
+            DiffOp::Equal { .. } => { old += 1; extend = false; continue },
+            _ if extend => hunks.last_mut().unwrap(/*`extend` implies a hunk*/),
+            _ => {
+                hunks.push(LineHunk { old_start: old, old_len: 0, lines: vec![] });
+                extend = true;
+                hunks.last_mut().unwrap(/*Just pushed*/)
+            },
+        };
+        match op {
+            DiffOp::Delete { .. } => { hunk.old_len += 1; old += 1; },
+            DiffOp::Insert { new } => hunk.lines.push(rhs[new].to_string()),
+            DiffOp::Equal { .. } => {/*NOP*/},
+        }
+    }
+    hunks
+}
+
+/// Apply `hunks`, which must be in ascending and non-overlapping order,
+/// to the lines of `base`.
+fn apply_line_hunks(base: &str, hunks: Vec<LineHunk>) -> DeltaResult<String> {
+    let lines: Vec<&str> = base.split('\n').collect();
+    let mut new: Vec<Cow<str>> = Vec::with_capacity(lines.len());
+    // NOTE: `next` is the first line of `base` that hasn't been copied yet:
+    let mut next: usize = 0;
+    for (index, hunk) in hunks.into_iter().enumerate() {
+        let end: usize = hunk.old_start.checked_add(hunk.old_len)
+            .ok_or(DeltaError::IllegalDelta { index })?;
+        if hunk.old_start < next || end > lines.len() {
+            return Err(DeltaError::IllegalDelta { index });
+        }
+        new.extend(lines[next .. hunk.old_start].iter().map(|l| Cow::Borrowed(*l)));
+        new.extend(hunk.lines.into_iter().map(Cow::Owned));
+        next = end;
+    }
+    new.extend(lines[next ..].iter().map(|l| Cow::Borrowed(*l)));
+    Ok(new.join("\n"))
+}
+
+
+/// The delta of a `String`.  Applying it to a `String` that it wasn't
+/// calculated from may fail if an op doesn't land on a `char` boundary.
+///
+/// In human-readable formats such as JSON, a `StringDelta::Replace` is
+/// serialized as just the new value, and a `StringDelta::Edit` as just
+/// its ops.  Other formats tag the variant.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
-pub struct StringDelta( // TODO: Improve delta space efficiency
-    #[doc(hidden)] pub Option<String>
-);
+pub enum StringDelta {
+    /// Replace the whole `String`.
+    Replace(String),
+    /// Edit the `String` by replaying ops against it from its start.
+    /// The ops must cover the whole `String`, except that no ops at all
+    /// leave any `String` unchanged.
+    Edit(Vec<StringOp>),
+    /// Edit the lines of the `String`, as separated by `'\n'`, by
+    /// replacing ranges of them.  See `StringDelta::line_diff()`.
+    Lines { hunks: Vec<LineHunk> },
+    /// Like `StringDelta::Edit`, but every op starts and ends on an
+    /// extended grapheme cluster boundary.  See
+    /// `StringDelta::grapheme_diff()`.
+    #[cfg(feature = "unicode")]
+    Graphemes(Vec<StringOp>),
+}
+
+impl StringDelta {
+    /// Calculate `lhs --[delta]--> rhs` line by line, like a unified diff,
+    /// rather than byte by byte as `Delta::delta()` does.  The delta stores
+    /// a `LineHunk` for each run of changed lines.  Since the lines are
+    /// separated by `'\n'`, a trailing `'\n'` is followed by an empty last
+    /// line, so a change in whether the string ends with `'\n'` is a
+    /// change to that last line, and applying the delta reproduces `rhs`
+    /// exactly.
+    pub fn line_diff(lhs: &str, rhs: &str) -> Self {
+        Self::Lines { hunks: diff_line_hunks(lhs, rhs) }
+    }
+
+    /// Calculate `lhs --[delta]--> rhs` over extended grapheme clusters
+    /// rather than `char`s, so that no op splits a cluster, such as an
+    /// emoji flag or a letter followed by combining accents.  Applying
+    /// the delta fails if an op doesn't end on a cluster boundary.  If
+    /// `lhs` and `rhs` differ in too many clusters, this falls back to a
+    /// `StringDelta::Replace`, like `Delta::delta()` does.
+    #[cfg(feature = "unicode")]
+    pub fn grapheme_diff(lhs: &str, rhs: &str) -> Self {
+        let lhs_units: Vec<&str> = lhs.graphemes(true).collect();
+        let rhs_units: Vec<&str> = rhs.graphemes(true).collect();
+        match diff_string_units(&lhs_units, &rhs_units) {
+            Some(ops) => Self::Graphemes(ops),
+            None => Self::Replace(rhs.to_string()),
+        }
+    }
+}
+
+impl DeltaOps for StringDelta {
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Replace(_) => false,
+            Self::Edit(ops) => ops.is_empty(),
+            Self::Lines { hunks } => hunks.is_empty(),
+            #[cfg(feature = "unicode")]
+            Self::Graphemes(ops) => ops.is_empty(),
+        }
+    }
+}
+
+impl Compose for StringDelta {
+    /// Composing 2 deltas that each edit the `String` results in a
+    /// `StringDelta::Edit` (or a `StringDelta::Graphemes` if both edit
+    /// grapheme clusters), and composing with a `StringDelta::Replace`
+    /// results in another `StringDelta::Replace`.  `StringDelta::Lines`
+    /// can't be composed with anything but a `StringDelta::Replace`.
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        match (self, next) {
+            (_, Self::Replace(value)) => Ok(Self::Replace(value.clone())),
+            (Self::Replace(value), next) => Ok(Self::Replace(value.apply(next.clone())?)),
+            (Self::Edit(first), Self::Edit(second)) =>
+                Ok(Self::Edit(compose_string_ops(first, second)?)),
+            #[cfg(feature = "unicode")]
+            (Self::Graphemes(first), Self::Graphemes(second)) =>
+                Ok(Self::Graphemes(compose_string_ops(first, second)?)),
+            #[cfg(feature = "unicode")]
+            (Self::Edit(first), Self::Graphemes(second))
+                | (Self::Graphemes(first), Self::Edit(second)) =>
+                Ok(Self::Edit(compose_string_ops(first, second)?)),
+            _ => Err(DeltaError::FailedToApplyDelta {
+                reason: "StringDelta::Lines can only be composed with \
+                         StringDelta::Replace".to_string(),
+            }),
+        }
+    }
+}
+
+impl Invert<String> for StringDelta {
+    fn invert(&self, base: &String) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
 
 impl std::fmt::Debug for StringDelta {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        match &self.0 {
-            Some(field) => write!(f, "StringDelta({:#?})", field),
-            None        => write!(f, "StringDelta(None)"),
+        match self {
+            Self::Replace(value) => write!(f, "StringDelta({:#?})", value),
+            Self::Edit(ops)      => write!(f, "StringDelta({:?})", ops),
+            Self::Lines { hunks } => write!(f, "StringDelta({:#?})", hunks),
+            #[cfg(feature = "unicode")]
+            Self::Graphemes(ops) => write!(f, "StringDelta(Graphemes {:?})", ops),
+        }
+    }
+}
+
+/// The serialized form of a `StringDelta` in human-readable formats.
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+#[serde(untagged)]
+enum UntaggedStringDelta<S, O, H> {
+    Replace(S),
+    Edit(O),
+    Lines { hunks: H },
+    #[cfg(feature = "unicode")]
+    Graphemes { graphemes: O },
+}
+
+/// The serialized form of a `StringDelta` in other formats.
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+#[serde(rename = "StringDelta")]
+enum TaggedStringDelta<S, O, H> {
+    Replace(S),
+    Edit(O),
+    Lines { hunks: H },
+    #[cfg(feature = "unicode")]
+    Graphemes(O),
+}
+
+impl Serialize for StringDelta {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        type Untagged<'d> = UntaggedStringDelta<&'d str, &'d [StringOp], &'d [LineHunk]>;
+        type Tagged<'d> = TaggedStringDelta<&'d str, &'d [StringOp], &'d [LineHunk]>;
+        match (self, serializer.is_human_readable()) {
+            (Self::Replace(value), true) => Untagged::Replace(value).serialize(serializer),
+            (Self::Edit(ops), true) => Untagged::Edit(ops).serialize(serializer),
+            (Self::Lines { hunks }, true) => Untagged::Lines { hunks }.serialize(serializer),
+            (Self::Replace(value), false) => Tagged::Replace(value).serialize(serializer),
+            (Self::Edit(ops), false) => Tagged::Edit(ops).serialize(serializer),
+            (Self::Lines { hunks }, false) => Tagged::Lines { hunks }.serialize(serializer),
+            #[cfg(feature = "unicode")]
+            (Self::Graphemes(graphemes), true) =>
+                Untagged::Graphemes { graphemes }.serialize(serializer),
+            #[cfg(feature = "unicode")]
+            (Self::Graphemes(ops), false) => Tagged::Graphemes(ops).serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StringDelta {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+        if deserializer.is_human_readable() {
+            Ok(match UntaggedStringDelta::deserialize(deserializer)? {
+                UntaggedStringDelta::Replace(value) => Self::Replace(value),
+                UntaggedStringDelta::Edit(ops) => Self::Edit(ops),
+                UntaggedStringDelta::Lines { hunks } => Self::Lines { hunks },
+                #[cfg(feature = "unicode")]
+                UntaggedStringDelta::Graphemes { graphemes } => Self::Graphemes(graphemes),
+            })
+        } else {
+            Ok(match TaggedStringDelta::deserialize(deserializer)? {
+                TaggedStringDelta::Replace(value) => Self::Replace(value),
+                TaggedStringDelta::Edit(ops) => Self::Edit(ops),
+                TaggedStringDelta::Lines { hunks } => Self::Lines { hunks },
+                #[cfg(feature = "unicode")]
+                TaggedStringDelta::Graphemes(ops) => Self::Graphemes(ops),
+            })
+        }
+    }
+}
+
+/// An operation of a `StringDelta::Edit`, over byte ranges of a `String`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub enum StringOp {
+    /// Keep the next `n` bytes.
+    Retain(usize),
+    /// Remove the next `n` bytes.
+    Delete(usize),
+    /// Insert a string before the next byte.
+    Insert(String),
+}
+
+impl StringOp {
+    /// The number of bytes that `self` retains, deletes or inserts.
+    fn len(&self) -> usize {
+        match self {
+            Self::Retain(n) | Self::Delete(n) => *n,
+            Self::Insert(s) => s.len(),
         }
     }
 }
 
+/// A hunk of a `StringDelta::Lines`, which replaces `old_len` lines
+/// starting at line `old_start` of the original `String` with `lines`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct LineHunk {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub lines: Vec<String>,
+}
+
 
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -150,12 +603,47 @@ mod tests {
         let json_string = serde_json::to_string(&delta)
             .expect("Could not serialize to json");
         println!("json_string: {}", json_string);
-        assert_eq!(json_string, "\"foo\"");
+        assert_eq!(json_string, "[]");
         let delta1: <String as Core>::Delta = serde_json::from_str(
             &json_string
         ).expect("Could not deserialize from json");
         assert_eq!(delta, delta1);
-        assert_eq!(delta, String::from("foo").into_delta()?);
+        assert_eq!(delta, StringDelta::Edit(vec![]));
+        Ok(())
+    }
+
+    #[test]
+    fn String__delta__large_disjoint_values() -> DeltaResult<()> {
+        // NOTE: Diffing these in full would take `O(N^2)` time:
+        let s0: String = "ab".repeat(20_000);
+        let s1: String = "cd".repeat(20_000);
+        let delta: <String as Core>::Delta = s0.delta(&s1)?;
+        assert_eq!(delta, StringDelta::Replace(s1.clone()));
+        assert_eq!(s0.apply(delta)?, s1);
+        Ok(())
+    }
+
+    #[test]
+    fn String__delta__large_values_small_edit() -> DeltaResult<()> {
+        let s0: String = "ab".repeat(20_000);
+        let s1: String = format!("{}xyz{}", &s0[.. 20_001], &s0[20_010 ..]);
+        let delta: <String as Core>::Delta = s0.delta(&s1)?;
+        assert_eq!(delta, StringDelta::Edit(vec![
+            StringOp::Retain(20_001),
+            StringOp::Delete(9),
+            StringOp::Insert("xyz".into()),
+            StringOp::Retain(19_990),
+        ]));
+        assert_eq!(s0.apply(delta)?, s1);
+        Ok(())
+    }
+
+    #[test]
+    fn String__delta__same_empty_values() -> DeltaResult<()> {
+        let delta: <String as Core>::Delta = String::new().delta(&String::new())?;
+        assert_eq!(delta, StringDelta::Edit(vec![]));
+        assert!(delta.is_empty());
+        assert_eq!(String::new().apply(delta)?, "");
         Ok(())
     }
 
@@ -196,6 +684,191 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn String__delta__insert_char() -> DeltaResult<()> {
+        let s0 = String::from("the quick brown fox jumps");
+        let s1 = String::from("the quick brown fox jumps!");
+        let delta: StringDelta = s0.delta(&s1)?;
+        assert_eq!(delta, StringDelta::Edit(vec![
+            StringOp::Retain(25),
+            StringOp::Insert("!".into()),
+        ]));
+        assert_eq!(s0.apply(delta)?, s1);
+
+        let s2 = String::from("the quick brown f-ox jumps");
+        let delta: StringDelta = s0.delta(&s2)?;
+        assert_eq!(delta, StringDelta::Edit(vec![
+            StringOp::Retain(17),
+            StringOp::Insert("-".into()),
+            StringOp::Retain(8),
+        ]));
+        assert_eq!(s0.apply(delta)?, s2);
+        Ok(())
+    }
+
+    #[test]
+    fn String__apply__edit_covers_base() -> DeltaResult<()> {
+        let s0 = String::from("the quick brown fox jumps");
+        let s1 = String::from("the quick brown fox jumps!");
+        let delta: StringDelta = s0.delta(&s1)?;
+        assert_eq!(s0.apply(delta.clone())?, s1);
+        // NOTE: The ops don't cover a base they were applied to already:
+        assert_eq!(s1.apply(delta.clone()), Err(DeltaError::IllegalDelta { index: 25 }));
+        assert!(String::from("the quick").apply(delta).is_err());
+        let delta: StringDelta = s0.delta(&s0)?;
+        assert_eq!(delta, StringDelta::Edit(vec![]));
+        assert_eq!(s1.apply(delta)?, s1);
+        Ok(())
+    }
+
+    #[test]
+    fn String__delta__delete_at_end() -> DeltaResult<()> {
+        let s0 = String::from("the quick brown fox jumps");
+        let s1 = String::from("the quick brown fox");
+        let delta: StringDelta = s0.delta(&s1)?;
+        assert_eq!(delta, StringDelta::Edit(vec![
+            StringOp::Retain(19),
+            StringOp::Delete(6),
+        ]));
+        assert_eq!(s0.apply(delta)?, s1);
+        Ok(())
+    }
+
+    #[test]
+    fn String__delta__multibyte() -> DeltaResult<()> {
+        let s0 = String::from("naïve café, über straße");
+        let s1 = String::from("naïve cafés, übel straße ✓");
+        let delta: StringDelta = s0.delta(&s1)?;
+        assert!(matches!(delta, StringDelta::Edit(_)), "{:?}", delta);
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        let delta1: StringDelta = serde_json::from_str(&json_string)
+            .expect("Could not deserialize from json");
+        assert_eq!(delta1, delta);
+        assert_eq!(s0.apply(delta)?, s1);
+
+        // NOTE: "ï" is 2 bytes long, so retaining 3 bytes splits it:
+        let delta = StringDelta::Edit(vec![StringOp::Retain(3), StringOp::Delete(1)]);
+        assert_eq!(s0.apply(delta), Err(DeltaError::IllegalDelta { index: 3 }));
+        let delta = StringDelta::Edit(vec![StringOp::Delete(100)]);
+        assert!(s0.apply(delta).is_err());
+        Ok(())
+    }
+
+
+    #[test]
+    fn StringDelta__line_diff() -> DeltaResult<()> {
+        let s0 = String::from("fn main() {\n    foo();\n    bar();\n}\n");
+        let s1 = String::from("fn main() {\n    foo();\n    baz();\n    qux();\n    bar();\n}\n");
+        let delta = StringDelta::line_diff(&s0, &s1);
+        assert_eq!(delta, StringDelta::Lines { hunks: vec![LineHunk {
+            old_start: 2,
+            old_len: 0,
+            lines: vec!["    baz();".into(), "    qux();".into()],
+        }]});
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        let delta1: StringDelta = serde_json::from_str(&json_string)
+            .expect("Could not deserialize from json");
+        assert_eq!(delta1, delta);
+        assert_eq!(s0.apply(delta)?, s1);
+
+        let s2 = String::from("fn main() {\n    bar();\n}\n");
+        let delta = StringDelta::line_diff(&s1, &s2);
+        assert_eq!(delta, StringDelta::Lines { hunks: vec![LineHunk {
+            old_start: 1,
+            old_len: 3,
+            lines: vec![],
+        }]});
+        assert_eq!(s1.apply(delta)?, s2);
+        Ok(())
+    }
+
+    #[test]
+    fn StringDelta__line_diff__trailing_newline() -> DeltaResult<()> {
+        let s0 = String::from("foo\nbar");
+        let s1 = String::from("foo\nbar\n");
+        let delta = StringDelta::line_diff(&s0, &s1);
+        assert_eq!(delta, StringDelta::Lines { hunks: vec![LineHunk {
+            old_start: 2,
+            old_len: 0,
+            lines: vec!["".into()],
+        }]});
+        assert_eq!(s0.apply(delta)?, s1);
+        let delta = StringDelta::line_diff(&s1, &s0);
+        assert_eq!(s1.apply(delta)?, s0);
+
+        let delta = StringDelta::Lines { hunks: vec![
+            LineHunk { old_start: 1, old_len: 1, lines: vec![] },
+            LineHunk { old_start: 0, old_len: 1, lines: vec![] },
+        ]};
+        assert_eq!(s0.apply(delta), Err(DeltaError::IllegalDelta { index: 1 }));
+        Ok(())
+    }
+
+
+    /// Returns `true` if every op in `delta` that applies to `base` ends on
+    /// an extended grapheme cluster boundary of `base`.
+    #[cfg(feature = "unicode")]
+    fn keeps_clusters(base: &str, delta: &StringDelta) -> bool {
+        let ops: &[StringOp] = match delta {
+            StringDelta::Edit(ops) | StringDelta::Graphemes(ops) => ops,
+            _ => return true,
+        };
+        let boundaries: Vec<usize> = base.grapheme_indices(true)
+            .map(|(pos, _)| pos)
+            .chain(std::iter::once(base.len()))
+            .collect();
+        let mut pos: usize = 0;
+        ops.iter().all(|op| match op {
+            StringOp::Retain(n) | StringOp::Delete(n) => {
+                pos += n;
+                boundaries.contains(&pos)
+            },
+            StringOp::Insert(_) => true,
+        })
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn StringDelta__grapheme_diff__flags() -> DeltaResult<()> {
+        // NOTE: Each flag is a pair of 4-byte regional indicators, and
+        //       🇳🇱 and 🇳🇴 only differ in the 2nd one:
+        let s0 = String::from("flags: 🇳🇱🇧🇪!");
+        let s1 = String::from("flags: 🇳🇴🇧🇪!");
+        assert!(!keeps_clusters(&s0, &s0.delta(&s1)?));
+        let delta = StringDelta::grapheme_diff(&s0, &s1);
+        assert_eq!(delta, StringDelta::Graphemes(vec![
+            StringOp::Retain(7),
+            StringOp::Delete(8),
+            StringOp::Insert("🇳🇴".into()),
+            StringOp::Retain(9),
+        ]));
+        assert!(keeps_clusters(&s0, &delta));
+        assert_eq!(s0.apply(delta)?, s1);
+
+        let delta = StringDelta::Graphemes(vec![StringOp::Retain(11), StringOp::Delete(4)]);
+        assert_eq!(s0.apply(delta), Err(DeltaError::IllegalDelta { index: 11 }));
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn StringDelta__grapheme_diff__combining_accents() -> DeltaResult<()> {
+        // NOTE: Each accented letter is a base letter and a combining accent:
+        let s0 = String::from("cafe\u{301} na\u{308}ive");
+        let s1 = String::from("cafe\u{300} na\u{308}ive, cafe\u{301}");
+        let delta = StringDelta::grapheme_diff(&s0, &s1);
+        assert!(keeps_clusters(&s0, &delta), "{:?}", delta);
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        let delta1: StringDelta = serde_json::from_str(&json_string)
+            .expect("Could not deserialize from json");
+        assert_eq!(delta1, delta);
+        assert_eq!(s0.apply(delta)?, s1);
+        Ok(())
+    }
+
 
     #[test]
     fn Str__delta__same_values() -> DeltaResult<()> {
@@ -250,4 +923,82 @@ mod tests {
         assert_eq!(s1, s2);
         Ok(())
     }
+
+    #[test]
+    fn StringDelta__is_empty() -> DeltaResult<()> {
+        let s0: String = String::from("foo\nbar");
+        assert!(s0.delta(&s0)?.is_empty());
+        assert!(StringDelta::line_diff(&s0, &s0).is_empty());
+        assert!(!s0.delta(&String::from("foo\nbaz"))?.is_empty());
+        assert!(!StringDelta::Replace(s0).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn StringDelta__identity_delta() -> DeltaResult<()> {
+        for s0 in ["", "foo\nbar", "ünïcödé"] {
+            let s0: String = s0.into();
+            assert_eq!(s0.apply(String::identity_delta())?, s0);
+        }
+        assert!(String::identity_delta().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn StringDelta__compose() -> DeltaResult<()> {
+        let states: Vec<String> = vec![
+            "the quick brown fox".into(),
+            "the quick red fox jumps".into(),
+            "a quick rëd föx jumps over".into(),
+            "a quick föx".into(),
+            "".into(),
+            "ünïcödé".into(),
+        ];
+        for s0 in &states { for s1 in &states { for s2 in &states {
+            let first = StringDelta::Edit(diff_string_units(&chars(s0), &chars(s1)).expect("Too many edits"));
+            let second = StringDelta::Edit(diff_string_units(&chars(s1), &chars(s2)).expect("Too many edits"));
+            let delta = first.compose(&second)?;
+            assert!(matches!(delta, StringDelta::Edit(_)), "{:?}", delta);
+            assert_eq!(&s0.apply(delta)?, s2, "{:?} -> {:?} -> {:?}", s0, s1, s2);
+            let delta = s0.delta(s1)?.compose(&s1.delta(s2)?)?;
+            assert_eq!(&s0.apply(delta)?, s2);
+        }}}
+        Ok(())
+    }
+
+    #[test]
+    fn StringDelta__invert() -> DeltaResult<()> {
+        let states: Vec<String> = vec![
+            "the quick brown fox".into(),
+            "a quick rëd föx jumps\nover".into(),
+            "".into(),
+        ];
+        for s0 in &states { for s1 in &states {
+            let delta = s0.delta(s1)?;
+            assert_eq!(&s0.apply(delta.clone())?.apply(delta.invert(s0)?)?, s0);
+            let delta = StringDelta::line_diff(s0, s1);
+            assert_eq!(&s0.apply(delta.clone())?.apply(delta.invert(s0)?)?, s0);
+        }}
+        Ok(())
+    }
+
+    #[test]
+    fn StringDelta__compose__incompatible() -> DeltaResult<()> {
+        let (s0, s1, s2) = ("foo\nbar", "foo\nbaz", "foo\nbaz\nqux");
+        let lines = StringDelta::line_diff(s0, s1);
+        let edit = StringDelta::Edit(diff_string_units(&chars(s1), &chars(s2)).expect("Too many edits"));
+        assert!(matches!(
+            lines.compose(&edit),
+            Err(DeltaError::FailedToApplyDelta { .. }),
+        ));
+        // NOTE: A `StringDelta::Replace` holds the whole `String`:
+        let delta = lines.compose(&StringDelta::Replace(s2.into()))?;
+        assert_eq!(delta, StringDelta::Replace(s2.into()));
+        let delta = StringDelta::Replace(s1.into()).compose(&edit)?;
+        assert_eq!(delta, StringDelta::Replace(s2.into()));
+        // NOTE: Edits must cover the whole `String` the other produces:
+        let first = StringDelta::Edit(vec![StringOp::Retain(3), StringOp::Delete(4)]);
+        assert_eq!(first.compose(&edit), Err(DeltaError::IllegalDelta { index: 3 }));
+        Ok(())
+    }
 }
@@ -3,7 +3,10 @@
 //!
 //! [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de;
 use serde::ser::SerializeMap;
@@ -76,6 +79,16 @@ where T: Clone + Debug + PartialEq + IntoDelta
     }
 }
 
+impl<T> EmptyDelta for Arc<T>
+where T: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn identity_delta() -> Self::Delta {
+        ArcDelta(None)
+    }
+}
+
 
 
 
@@ -84,6 +97,32 @@ pub struct ArcDelta<T: Core>(
     #[doc(hidden)] pub Option<Box<<T as Core>::Delta>>
 );
 
+impl<T: Core> DeltaOps for ArcDelta<T> {
+    fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl<T: Core> Compose for ArcDelta<T>
+where T::Delta: Compose
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(ArcDelta(match (&self.0, &next.0) {
+            (Some(lhs), Some(rhs)) => Some(Box::new(lhs.compose(rhs)?)),
+            (lhs, rhs) => rhs.as_ref().or(lhs.as_ref()).cloned(),
+        }))
+    }
+}
+
+impl<T: Core> Invert<Arc<T>> for ArcDelta<T>
+where Arc<T>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &Arc<T>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 impl<T: Core> std::fmt::Debug for ArcDelta<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         match &self.0 {
@@ -3,7 +3,10 @@
 //!
 //! [`RwLock`]: https://doc.rust-lang.org/std/sync/struct.RwLock.html
 
-use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaError, DeltaOps, DeltaResult,
+    EmptyDelta, FromDelta, IntoDelta, Invert,
+};
 use serde::{Serialize, Serializer, Deserialize, Deserializer};
 use serde::de::Visitor;
 use std::cmp::Ordering;
@@ -163,6 +166,16 @@ where T: Clone + Debug + PartialEq + IntoDelta
     }
 }
 
+impl<T> EmptyDelta for RwLock<T>
+where T: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn identity_delta() -> Self::Delta {
+        RwLockDelta(None)
+    }
+}
+
 
 
 
@@ -172,6 +185,32 @@ pub struct RwLockDelta<T: Core>(
     #[doc(hidden)] pub Option<<T as Core>::Delta>
 );
 
+impl<T: Core> DeltaOps for RwLockDelta<T> {
+    fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl<T: Core> Compose for RwLockDelta<T>
+where T::Delta: Compose
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(RwLockDelta(match (&self.0, &next.0) {
+            (Some(lhs), Some(rhs)) => Some(lhs.compose(rhs)?),
+            (lhs, rhs) => rhs.as_ref().or(lhs.as_ref()).cloned(),
+        }))
+    }
+}
+
+impl<T: Core> Invert<RwLock<T>> for RwLockDelta<T>
+where RwLock<T>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &RwLock<T>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 impl<T: Core> std::fmt::Debug for RwLockDelta<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         match &self.0 {
@@ -198,12 +237,12 @@ mod tests {
         let json_string = serde_json::to_string(&delta)
             .expect("Could not serialize to json");
         println!("json_string: {}", json_string);
-        assert_eq!(json_string, "\"foo\"");
+        assert_eq!(json_string, "[]");
         let delta1: <RwLock<String> as Core>::Delta = serde_json::from_str(
             &json_string
         ).expect("Could not deserialize from json");
         assert_eq!(delta, delta1);
-        assert_eq!(delta, RwLock::new(String::from("foo")).into_delta()?);
+        assert_eq!(delta, RwLockDelta(Some(crate::StringDelta::Edit(vec![]))));
         Ok(())
     }
 
@@ -0,0 +1,171 @@
+//! Delta support for [`Duration`].
+//!
+//! [`Duration`]: https://doc.rust-lang.org/std/time/struct.Duration.html
+
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
+use std::time::Duration;
+
+
+/// The number of nanoseconds in a second, i.e. the
+/// exclusive upper bound of `Duration::subsec_nanos()`.
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+impl Core for Duration {
+    type Delta = DurationDelta;
+}
+
+impl Apply for Duration {
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        let secs: u64 = delta.secs.unwrap_or(self.as_secs());
+        let nanos: u32 = delta.nanos.unwrap_or(self.subsec_nanos());
+        ensure_lt![nanos, NANOS_PER_SEC]?;
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+impl Delta for Duration {
+    /// Calculate `self --[delta]--> rhs`.  Rather than the (signed)
+    /// difference of `self` and `rhs`, the delta holds the new whole
+    /// seconds and nanoseconds of `rhs`, each only if it changed.  This
+    /// keeps e.g. a sub-second change small, and can't under- or overflow.
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        Ok(DurationDelta {
+            secs: Some(rhs.as_secs()).filter(|&secs| secs != self.as_secs()),
+            nanos: Some(rhs.subsec_nanos())
+                .filter(|&nanos| nanos != self.subsec_nanos()),
+        })
+    }
+}
+
+impl FromDelta for Duration {
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        let secs: u64 = delta.secs
+            .ok_or_else(|| ExpectedValue!("DurationDelta"))?;
+        let nanos: u32 = delta.nanos
+            .ok_or_else(|| ExpectedValue!("DurationDelta"))?;
+        ensure_lt![nanos, NANOS_PER_SEC]?;
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+impl IntoDelta for Duration {
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        Ok(DurationDelta {
+            secs: Some(self.as_secs()),
+            nanos: Some(self.subsec_nanos()),
+        })
+    }
+}
+
+impl EmptyDelta for Duration {
+    fn identity_delta() -> Self::Delta {
+        DurationDelta::default()
+    }
+}
+
+
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct DurationDelta {
+    #[doc(hidden)] pub secs: Option<u64>,
+    #[doc(hidden)] pub nanos: Option<u32>,
+}
+
+impl DeltaOps for DurationDelta {
+    fn is_empty(&self) -> bool {
+        self.secs.is_none() && self.nanos.is_none()
+    }
+}
+
+impl Compose for DurationDelta {
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(DurationDelta {
+            secs: next.secs.or(self.secs),
+            nanos: next.nanos.or(self.nanos),
+        })
+    }
+}
+
+impl Invert<Duration> for DurationDelta {
+    fn invert(&self, base: &Duration) -> DeltaResult<Self> {
+        Ok(DurationDelta {
+            secs: self.secs.map(|_| base.as_secs()),
+            nanos: self.nanos.map(|_| base.subsec_nanos()),
+        })
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DeltaError;
+
+    #[test]
+    fn Duration__delta__zero() -> DeltaResult<()> {
+        let duration0 = Duration::from_secs(0);
+        let delta = duration0.delta(&Duration::default())?;
+        assert!(delta.is_empty());
+        assert_eq!(duration0.apply(delta)?, duration0);
+        let json = serde_json::to_string(&delta).expect("failed to serialize");
+        assert_eq!(json, r#"{"secs":null,"nanos":null}"#);
+
+        let duration1 = Duration::from_millis(1500);
+        let delta = duration1.delta(&duration0)?;
+        assert_eq!(delta, DurationDelta { secs: Some(0), nanos: Some(0) });
+        assert_eq!(duration1.apply(delta)?, duration0);
+        Ok(())
+    }
+
+    #[test]
+    fn Duration__delta__sub_second() -> DeltaResult<()> {
+        let duration0 = Duration::new(3, 900_000_000);
+        let duration1 = Duration::new(3, 100_000_000);
+        // NOTE: The nanos decrease, which mustn't underflow:
+        let delta = duration0.delta(&duration1)?;
+        assert_eq!(delta, DurationDelta { secs: None, nanos: Some(100_000_000) });
+        assert_eq!(duration0.apply(delta)?, duration1);
+        let delta = duration1.delta(&duration0)?;
+        assert_eq!(duration1.apply(delta)?, duration0);
+
+        let delta = DurationDelta { secs: None, nanos: Some(NANOS_PER_SEC) };
+        assert!(matches!(
+            duration0.apply(delta),
+            Err(DeltaError::FailedToEnsure { .. }),
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn Duration__delta__multiple_hours() -> DeltaResult<()> {
+        let duration0 = Duration::new(5 * 3600 + 59, 999_999_999);
+        let duration1 = Duration::new(7 * 3600, 0);
+        let delta = duration0.delta(&duration1)?;
+        assert_eq!(delta, DurationDelta { secs: Some(7 * 3600), nanos: Some(0) });
+        assert_eq!(duration0.apply(delta)?, duration1);
+        let delta = duration1.delta(&duration0)?;
+        assert_eq!(duration1.apply(delta)?, duration0);
+
+        let duration2 = Duration::new(u64::MAX, 1);
+        assert_eq!(duration0.apply(duration0.delta(&duration2)?)?, duration2);
+        assert_eq!(Duration::from_delta(duration2.into_delta()?)?, duration2);
+        assert!(Duration::from_delta(DurationDelta::default()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn DurationDelta__invert() -> DeltaResult<()> {
+        let (duration0, duration1) = (Duration::new(1, 500), Duration::new(1, 700));
+        let delta = duration0.delta(&duration1)?;
+        let inverse = delta.invert(&duration0)?;
+        assert_eq!(inverse, DurationDelta { secs: None, nanos: Some(500) });
+        assert_eq!(duration0.apply(delta)?.apply(inverse)?, duration0);
+        Ok(())
+    }
+}
@@ -1,8 +1,22 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
 
 
+/// Combine the deltas of a single field of a tuple, where `None` leaves
+/// the field unchanged.
+fn compose_field<D>(lhs: &Option<D>, rhs: &Option<D>) -> DeltaResult<Option<D>>
+where D: Compose + Clone
+{
+    Ok(match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Some(lhs.compose(rhs)?),
+        (lhs, rhs) => rhs.as_ref().or(lhs.as_ref()).cloned(),
+    })
+}
+
 impl<T0> Core for (T0,)
 where T0: Core
 {
@@ -55,6 +69,42 @@ where T0: IntoDelta,
     }
 }
 
+impl<T0> EmptyDelta for (T0,)
+where T0: Core,
+{
+    fn identity_delta() -> Self::Delta {
+        (
+            None,
+        )
+    }
+}
+
+impl<D0> DeltaOps for (Option<D0>,) {
+    fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl<D0> Compose for (Option<D0>,)
+where D0: Compose + Clone,
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok((
+            compose_field(&self.0, &next.0)?,
+        ))
+    }
+}
+
+impl<T0> Invert<(T0,)> for (
+    Option<<T0 as Core>::Delta>,
+)
+where T0: Apply + Delta,
+{
+    fn invert(&self, base: &(T0,)) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 
 
 
@@ -125,6 +175,48 @@ where T0: IntoDelta,
     }
 }
 
+impl<T0, T1> EmptyDelta for (T0, T1)
+where T0: Core,
+      T1: Core,
+{
+    fn identity_delta() -> Self::Delta {
+        (
+            None,
+            None,
+        )
+    }
+}
+
+impl<D0, D1> DeltaOps for (Option<D0>, Option<D1>) {
+    fn is_empty(&self) -> bool {
+        self.0.is_none() && self.1.is_none()
+    }
+}
+
+impl<D0, D1> Compose for (Option<D0>, Option<D1>)
+where D0: Compose + Clone,
+      D1: Compose + Clone,
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok((
+            compose_field(&self.0, &next.0)?,
+            compose_field(&self.1, &next.1)?,
+        ))
+    }
+}
+
+impl<T0, T1> Invert<(T0, T1)> for (
+    Option<<T0 as Core>::Delta>,
+    Option<<T1 as Core>::Delta>,
+)
+where T0: Apply + Delta,
+      T1: Apply + Delta,
+{
+    fn invert(&self, base: &(T0, T1)) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 
 
 
@@ -210,6 +302,54 @@ where T0: IntoDelta,
     }
 }
 
+impl<T0, T1, T2> EmptyDelta for (T0, T1, T2)
+where T0: Core,
+      T1: Core,
+      T2: Core,
+{
+    fn identity_delta() -> Self::Delta {
+        (
+            None,
+            None,
+            None,
+        )
+    }
+}
+
+impl<D0, D1, D2> DeltaOps for (Option<D0>, Option<D1>, Option<D2>) {
+    fn is_empty(&self) -> bool {
+        self.0.is_none() && self.1.is_none() && self.2.is_none()
+    }
+}
+
+impl<D0, D1, D2> Compose for (Option<D0>, Option<D1>, Option<D2>)
+where D0: Compose + Clone,
+      D1: Compose + Clone,
+      D2: Compose + Clone,
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok((
+            compose_field(&self.0, &next.0)?,
+            compose_field(&self.1, &next.1)?,
+            compose_field(&self.2, &next.2)?,
+        ))
+    }
+}
+
+impl<T0, T1, T2> Invert<(T0, T1, T2)> for (
+    Option<<T0 as Core>::Delta>,
+    Option<<T1 as Core>::Delta>,
+    Option<<T2 as Core>::Delta>,
+)
+where T0: Apply + Delta,
+      T1: Apply + Delta,
+      T2: Apply + Delta,
+{
+    fn invert(&self, base: &(T0, T1, T2)) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 
 
 
@@ -310,6 +450,60 @@ where T0: IntoDelta,
     }
 }
 
+impl<T0, T1, T2, T3> EmptyDelta for (T0, T1, T2, T3)
+where T0: Core,
+      T1: Core,
+      T2: Core,
+      T3: Core,
+{
+    fn identity_delta() -> Self::Delta {
+        (
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+}
+
+impl<D0, D1, D2, D3> DeltaOps for (Option<D0>, Option<D1>, Option<D2>, Option<D3>) {
+    fn is_empty(&self) -> bool {
+        self.0.is_none() && self.1.is_none() && self.2.is_none() && self.3.is_none()
+    }
+}
+
+impl<D0, D1, D2, D3> Compose for (Option<D0>, Option<D1>, Option<D2>, Option<D3>)
+where D0: Compose + Clone,
+      D1: Compose + Clone,
+      D2: Compose + Clone,
+      D3: Compose + Clone,
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok((
+            compose_field(&self.0, &next.0)?,
+            compose_field(&self.1, &next.1)?,
+            compose_field(&self.2, &next.2)?,
+            compose_field(&self.3, &next.3)?,
+        ))
+    }
+}
+
+impl<T0, T1, T2, T3> Invert<(T0, T1, T2, T3)> for (
+    Option<<T0 as Core>::Delta>,
+    Option<<T1 as Core>::Delta>,
+    Option<<T2 as Core>::Delta>,
+    Option<<T3 as Core>::Delta>,
+)
+where T0: Apply + Delta,
+      T1: Apply + Delta,
+      T2: Apply + Delta,
+      T3: Apply + Delta,
+{
+    fn invert(&self, base: &(T0, T1, T2, T3)) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 
 
 
@@ -426,6 +620,66 @@ where T0: IntoDelta,
     }
 }
 
+impl<T0, T1, T2, T3, T4> EmptyDelta for (T0, T1, T2, T3, T4)
+where T0: Core,
+      T1: Core,
+      T2: Core,
+      T3: Core,
+      T4: Core,
+{
+    fn identity_delta() -> Self::Delta {
+        (
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+}
+
+impl<D0, D1, D2, D3, D4> DeltaOps for (Option<D0>, Option<D1>, Option<D2>, Option<D3>, Option<D4>) {
+    fn is_empty(&self) -> bool {
+        self.0.is_none() && self.1.is_none() && self.2.is_none() && self.3.is_none() && self.4.is_none()
+    }
+}
+
+impl<D0, D1, D2, D3, D4> Compose for (Option<D0>, Option<D1>, Option<D2>, Option<D3>, Option<D4>)
+where D0: Compose + Clone,
+      D1: Compose + Clone,
+      D2: Compose + Clone,
+      D3: Compose + Clone,
+      D4: Compose + Clone,
+{
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok((
+            compose_field(&self.0, &next.0)?,
+            compose_field(&self.1, &next.1)?,
+            compose_field(&self.2, &next.2)?,
+            compose_field(&self.3, &next.3)?,
+            compose_field(&self.4, &next.4)?,
+        ))
+    }
+}
+
+impl<T0, T1, T2, T3, T4> Invert<(T0, T1, T2, T3, T4)> for (
+    Option<<T0 as Core>::Delta>,
+    Option<<T1 as Core>::Delta>,
+    Option<<T2 as Core>::Delta>,
+    Option<<T3 as Core>::Delta>,
+    Option<<T4 as Core>::Delta>,
+)
+where T0: Apply + Delta,
+      T1: Apply + Delta,
+      T2: Apply + Delta,
+      T3: Apply + Delta,
+      T4: Apply + Delta,
+{
+    fn invert(&self, base: &(T0, T1, T2, T3, T4)) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
 
 
 #[allow(non_snake_case)]
@@ -951,5 +1205,43 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn DeltaOps__is_empty__tuples() -> DeltaResult<()> {
+        let (tuple0, tuple1) = ((1u8, 'a'), (1u8, 'b'));
+        assert!(tuple0.delta(&tuple0)?.is_empty());
+        assert!(!tuple0.delta(&tuple1)?.is_empty());
+        let tuple2 = (1u8, 2u16, 3u32, 4u64, 'e');
+        assert!(tuple2.delta(&tuple2)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn tuple__compose() -> DeltaResult<()> {
+        let tuple0 = (1u8, String::from("foo"), 3u16);
+        let tuple1 = (2u8, String::from("foo"), 4u16);
+        let tuple2 = (2u8, String::from("bar"), 5u16);
+        let delta = tuple0.delta(&tuple1)?.compose(&tuple1.delta(&tuple2)?)?;
+        assert_eq!(tuple0.apply(delta)?, tuple2);
+        Ok(())
+    }
 
+    #[test]
+    fn tuple__invert() -> DeltaResult<()> {
+        let tuple0 = (1u8, String::from("foo"), vec![1u16, 2]);
+        let tuple1 = (2u8, String::from("foo"), vec![2u16]);
+        let delta = tuple0.delta(&tuple1)?;
+        let inverse = delta.invert(&tuple0)?;
+        assert_eq!(inverse, tuple1.delta(&tuple0)?);
+        assert_eq!(tuple0.apply(delta)?.apply(inverse)?, tuple0);
+        Ok(())
+    }
+
+    #[test]
+    fn tuple__identity_delta() -> DeltaResult<()> {
+        let tuple = (1u8, String::from("foo"), vec![1u16, 2]);
+        let delta = <(u8, String, Vec<u16>)>::identity_delta();
+        assert!(delta.is_empty());
+        assert_eq!(tuple.apply(delta)?, tuple);
+        Ok(())
+    }
 }
@@ -0,0 +1,195 @@
+//! Automatic selection of the way a `VecDelta` is calculated, based on
+//! the shape of the change between 2 `Vec`s.
+
+use crate::{Delta, DeltaResult, IntoDelta};
+use crate::config::DeltaConfig;
+use crate::vec::{delta_myers, delta_positional, delta_with_strategy, EltDelta};
+use crate::vec::{VecDelta, VecStrategy};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+
+/// The way in which `VecStrategy::Auto` calculates a `VecDelta`, as
+/// returned by `select_strategy()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SelectedStrategy {
+    /// Values were only appended, so `EltDelta::Add` them.
+    Append,
+    /// Values were only prepended, so `EltDelta::Prepend` them.
+    Prepend,
+    /// Values were only removed from the end, so `EltDelta::Remove` them.
+    Truncate,
+    /// The changed values don't shift any unchanged values that follow
+    /// them, so compare the elements index by index.
+    Positional,
+    /// The changed values shift the unchanged values that follow them,
+    /// so calculate a minimal edit script as `VecStrategy::Myers` does.
+    Myers,
+}
+
+/// Select how to calculate `lhs --[delta]--> rhs`, by probing the lengths
+/// of the common prefix and suffix of `lhs` and `rhs`.  This takes time
+/// linear in the length of the common prefix and suffix only.
+pub fn select_strategy<T: PartialEq>(lhs: &[T], rhs: &[T]) -> SelectedStrategy {
+    let prefix: usize = lhs.iter().zip(rhs).take_while(|(l, r)| l == r).count();
+    // NOTE: The suffix may not overlap with the prefix:
+    let suffix: usize = lhs[prefix ..].iter().rev()
+        .zip(rhs[prefix ..].iter().rev())
+        .take_while(|(l, r)| l == r)
+        .count();
+    if prefix == lhs.len() {
+        SelectedStrategy::Append
+    } else if suffix == lhs.len() {
+        SelectedStrategy::Prepend
+    } else if prefix == rhs.len() {
+        SelectedStrategy::Truncate
+    } else if lhs.len() == rhs.len() || suffix == 0 {
+        SelectedStrategy::Positional
+    } else {
+        SelectedStrategy::Myers
+    }
+}
+
+/// Calculate `lhs --[delta]--> rhs` as `VecStrategy::Auto` does, and also
+/// return the strategy that was selected for it, e.g. for logging.  The
+/// strategy is selected only once, and is the one the delta was actually
+/// calculated with.  Note
+/// that, as with any strategy, a transition from or to an empty `Vec` is
+/// a single `EltDelta::SetAll` or `EltDelta::Clear`.
+pub fn delta_auto<T>(
+    lhs: &[T],
+    rhs: &[T],
+) -> DeltaResult<(VecDelta<T>, SelectedStrategy)>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    let config = DeltaConfig {
+        vec_strategy: VecStrategy::Auto,
+        ..Default::default()
+    };
+    delta_with_strategy(lhs, rhs, &config)
+}
+
+/// Calculate the changes that transform `lhs` into `rhs` in the way
+/// `selected`, or `None` if they would consist of more than `max_ops`
+/// operations.
+pub(super) fn delta_selected<T>(
+    lhs: &[T],
+    rhs: &[T],
+    selected: SelectedStrategy,
+    max_ops: usize,
+) -> DeltaResult<Option<Vec<EltDelta<T>>>>
+where T: Clone + PartialEq + Delta + IntoDelta {
+    let changes: Vec<EltDelta<T>> = match selected {
+        SelectedStrategy::Append => rhs[lhs.len() ..].iter()
+            .map(|r| Ok(EltDelta::Add(r.clone().into_delta()?)))
+            .collect::<DeltaResult<_>>()?,
+        SelectedStrategy::Prepend => {
+            let items = rhs[.. rhs.len() - lhs.len()].iter()
+                .map(|r| r.clone().into_delta())
+                .collect::<DeltaResult<_>>()?;
+            vec![EltDelta::Prepend(items)]
+        },
+        SelectedStrategy::Truncate =>
+            vec![EltDelta::Remove { count: lhs.len() - rhs.len() }],
+        SelectedStrategy::Positional => delta_positional(lhs, rhs)?,
+        SelectedStrategy::Myers => return delta_myers(lhs, rhs, max_ops),
+    };
+    Ok(Some(changes).filter(|changes| changes.len() <= max_ops))
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Apply;
+    use crate::vec::delta_with_config;
+
+    #[test]
+    fn select_strategy__shapes() -> DeltaResult<()> {
+        let base: Vec<u32> = (0 .. 100).collect();
+        let shapes: Vec<(Vec<u32>, SelectedStrategy)> = vec![
+            (base.clone(), SelectedStrategy::Append),
+            ((0 .. 120).collect(), SelectedStrategy::Append),
+            ((1000 .. 1005).chain(0 .. 100).collect(), SelectedStrategy::Prepend),
+            ((0 .. 80).collect(), SelectedStrategy::Truncate),
+            (base.iter().map(|n| if n % 10 == 0 { n + 1 } else { *n }).collect(),
+                SelectedStrategy::Positional),
+            ((0 .. 90).chain(1000 .. 1020).collect(), SelectedStrategy::Positional),
+            ((0 .. 50).chain(1000 .. 1003).chain(50 .. 100).collect(),
+                SelectedStrategy::Myers),
+            ((0 .. 30).chain(60 .. 100).collect(), SelectedStrategy::Myers),
+            (vec![], SelectedStrategy::Truncate),
+        ];
+        let config = DeltaConfig {
+            vec_strategy: VecStrategy::Auto,
+            ..Default::default()
+        };
+        for (rhs, expected) in shapes {
+            let (delta, selected) = delta_auto(&base, &rhs)?;
+            assert_eq!(selected, expected, "{:?}", rhs);
+            assert_eq!(delta, delta_with_config(&base, &rhs, &config)?);
+            assert_eq!(base.apply(delta)?, rhs);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn select_strategy__delta_size() -> DeltaResult<()> {
+        let base: Vec<u32> = (0 .. 1000).collect();
+        let mut rhs: Vec<u32> = base.clone();
+        rhs.insert(10, 5000);
+        let (delta, selected) = delta_auto(&base, &rhs)?;
+        assert_eq!(selected, SelectedStrategy::Myers);
+        assert_eq!(delta.len(), 1);
+        let prepended: Vec<u32> = (5000 .. 5003).chain(0 .. 1000).collect();
+        let (delta, selected) = delta_auto(&base, &prepended)?;
+        assert_eq!(selected, SelectedStrategy::Prepend);
+        assert_eq!(delta.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn VecStrategy__Auto__empty_and_max_ops() -> DeltaResult<()> {
+        let config = DeltaConfig {
+            vec_strategy: VecStrategy::Auto,
+            max_ops: Some(2),
+        };
+        let vec0: Vec<u32> = vec![];
+        let vec1: Vec<u32> = vec![1, 2, 3, 4];
+        let delta = delta_with_config(&vec0, &vec1, &config)?;
+        assert!(matches!(delta.iter().next(), Some(EltDelta::SetAll(_))));
+        assert_eq!(vec0.apply(delta)?, vec1);
+        let vec2: Vec<u32> = vec![1, 2, 3, 4, 5, 6, 7];
+        let delta = delta_with_config(&vec1, &vec2, &config)?;
+        assert!(matches!(delta.iter().next(), Some(EltDelta::SetAll(_))));
+        assert_eq!(vec1.apply(delta)?, vec2);
+        assert_eq!(delta_auto(&vec1, &vec0)?.1, SelectedStrategy::Truncate);
+        Ok(())
+    }
+
+    #[test]
+    fn VecStrategy__Auto__max_ops_range() -> DeltaResult<()> {
+        let config = DeltaConfig {
+            vec_strategy: VecStrategy::Auto,
+            max_ops: Some(2),
+        };
+        // NOTE: Each run of deleted elements is a single operation:
+        let vec0: Vec<u32> = (0 .. 100).collect();
+        let vec1: Vec<u32> = (0 .. 10).chain(30 .. 60).chain(90 .. 100).collect();
+        assert_eq!(select_strategy(&vec0, &vec1), SelectedStrategy::Myers);
+        let delta = delta_with_config(&vec0, &vec1, &config)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::DeleteRange { index: 10, count: 20 },
+            EltDelta::DeleteRange { index: 40, count: 30 },
+        ]));
+        let vec2: Vec<u32> = (0 .. 10).chain(30 .. 60).chain(90 .. 95).collect();
+        let delta = delta_with_config(&vec0, &vec2, &config)?;
+        assert!(matches!(&delta.0[..], [EltDelta::SetAll(_)]));
+        assert_eq!(vec0.apply(delta)?, vec2);
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+//! Incremental calculation of `VecDelta`s as elements are produced.
+
+use crate::{Delta, DeltaResult, IntoDelta};
+use crate::vec::{compact_swap, push_positional, EltDelta, VecDelta};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+
+/// Accumulates a positional delta from a base `Vec` to a new one whose
+/// elements are pushed one at a time, e.g. by a streaming producer, so
+/// that no full diff is needed once the new `Vec` is complete.
+/// See `VecDelta::builder()`.
+#[derive(Clone, Debug)]
+pub struct VecDeltaBuilder<'b, T: Delta> {
+    base: &'b [T],
+    /// The number of elements pushed so far.
+    len: usize,
+    changes: Vec<EltDelta<T>>,
+    /// The first few edited elements, as pushed, which
+    /// are needed to recognize an exchange of 2 elements.
+    edited: Vec<(usize, T)>,
+}
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Start an incremental calculation of `base --[delta]--> new`, where
+    /// the elements of `new` are passed to `VecDeltaBuilder::push()` in
+    /// order.  Each element is compared against `base` as it is pushed.
+    ///
+    /// The final delta is the same as that of `base.delta(&new)`, except
+    /// that values prepended to `base` are never recognized as such, as
+    /// that would require keeping all pushed elements around.  They are
+    /// edits and additions instead.
+    pub fn builder(base: &[T]) -> VecDeltaBuilder<'_, T> {
+        VecDeltaBuilder { base, len: 0, changes: vec![], edited: vec![] }
+    }
+}
+
+impl<'b, T> VecDeltaBuilder<'b, T>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// The number of elements pushed so far.
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.len }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+
+    /// Append `item` to the new `Vec`.
+    pub fn push(&mut self, item: &T) -> DeltaResult<()> {
+        let (index, base): (usize, &[T]) = (self.len, self.base);
+        let pos: usize = self.changes.len();
+        push_positional(
+            &mut self.changes,
+            index .. index + 1,
+            (base.len(), |index| Ok(Cow::Borrowed(&base[index]))),
+            (index + 1, |_| Ok(Cow::Borrowed(item))),
+        )?;
+        let edited: bool = matches!(self.changes.get(pos), Some(EltDelta::Edit { .. }));
+        if edited && self.edited.len() <= 2 {
+            self.edited.push((index, item.clone()));
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Append each of `items` to the new `Vec`.
+    pub fn extend<'i, I>(&mut self, items: I) -> DeltaResult<()>
+    where I: IntoIterator<Item = &'i T>, T: 'i {
+        for item in items { self.push(item)?; }
+        Ok(())
+    }
+
+    /// Return the delta from the base `Vec` to the pushed elements.
+    pub fn finish(mut self) -> DeltaResult<VecDelta<T>> {
+        // NOTE: Transitions from and to an empty `Vec` are a single operation:
+        if self.base.is_empty() && self.len > 0 {
+            let items = self.changes.into_iter()
+                .map(|change| match change {
+                    EltDelta::Add(item) => Ok(item),
+                    _ => bug_detected!(),
+                })
+                .collect::<DeltaResult<_>>()?;
+            return Ok(VecDelta(vec![EltDelta::SetAll(items)]));
+        } else if !self.base.is_empty() && self.len == 0 {
+            return Ok(VecDelta(vec![EltDelta::Clear]));
+        }
+        let base: &[T] = self.base;
+        push_positional(
+            &mut self.changes,
+            self.len .. base.len(),
+            (base.len(), |index| Ok(Cow::Borrowed(&base[index]))),
+            (self.len, |_| bug_detected!()),
+        )?;
+        // NOTE: Only the edited elements are kept, which is all that
+        //       `compact_swap()` reads of the new `Vec`:
+        let edited: &[(usize, T)] = &self.edited;
+        compact_swap(
+            &mut self.changes,
+            |index| Ok(Cow::Borrowed(&base[index])),
+            |index| match edited.iter().find(|(i, _)| *i == index) {
+                Some((_, item)) => Ok(Cow::Borrowed(item)),
+                None => bug_detected!(),
+            },
+        )?;
+        Ok(VecDelta(self.changes))
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Apply;
+
+    #[test]
+    fn VecDeltaBuilder__push() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 100).collect();
+        let cases: Vec<Vec<u32>> = vec![
+            (0 .. 100).map(|n| if n % 7 == 0 { n + 1 } else { n }).collect(),
+            (0 .. 150).collect(),
+            (0 .. 90).map(|n| n * 2).collect(),
+            vec0.clone(),
+            (0 .. 100).map(|n| match n { 3 => 60, 60 => 3, n => n }).collect(),
+            vec![],
+        ];
+        for vec1 in cases {
+            let mut builder = VecDelta::builder(&vec0);
+            for item in &vec1 { builder.push(item)?; }
+            assert_eq!(builder.len(), vec1.len());
+            let delta = builder.finish()?;
+            assert_eq!(delta, vec0.delta(&vec1)?);
+            assert_eq!(vec0.apply(delta)?, vec1);
+        }
+        let mut builder = VecDelta::builder(&[]);
+        builder.extend(&vec0)?;
+        assert_eq!(builder.finish()?, Vec::<u32>::new().delta(&vec0)?);
+        Ok(())
+    }
+
+    #[test]
+    fn VecDeltaBuilder__push__prepended() -> DeltaResult<()> {
+        let vec0: Vec<u32> = vec![1, 2, 3];
+        let vec1: Vec<u32> = vec![0, 1, 2, 3];
+        let mut builder = VecDelta::builder(&vec0);
+        builder.extend(&vec1)?;
+        let delta = builder.finish()?;
+        assert_eq!(delta.len(), 4);
+        assert_eq!(vec0.apply(delta)?, vec1);
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+//! `VecDelta`s that detect conflicting concurrent edits on apply.
+
+use crate::{Apply, Core, DeltaError, DeltaResult, FromDelta};
+use crate::fnv::FnvHasher;
+use crate::vec::{apply_elt_delta, EltDelta, VecDelta};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+
+
+/// A `VecDelta` together with a checksum of the values that each of its
+/// operations expects to find at its target, as calculated against the
+/// `Vec` the delta was made for.  See `VecDelta::apply_checked()`.
+/// The checksums are 64-bit FNV-1a hashes, fed integers in little-endian
+/// order, so a `CheckedVecDelta` can be serialized and checked by another
+/// build or platform, as long as the `Hash` impl of `T` is stable too.
+#[derive(Clone, Debug, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct CheckedVecDelta<T: Core> {
+    pub delta: VecDelta<T>,
+    /// For each operation in `delta`, the index and checksum of its target,
+    /// or `None` if the operation doesn't target existing values.
+    pub checksums: Vec<Option<(usize, u64)>>,
+}
+
+/// Hash `items` one by one, rather than with `<[T]>::hash()`, which
+/// hashes slices of integers as a single native-endian byte string.
+fn hash_items<T: Hash>(items: &[T], hasher: &mut FnvHasher) {
+    hasher.write_usize(items.len());
+    items.iter().for_each(|item| item.hash(hasher));
+}
+
+/// Calculate the index and checksum of the values in `vec` that `change`
+/// targets, if any.  `EltDelta::Add`, `EltDelta::Insert` and
+/// `EltDelta::Prepend` don't target any existing values, while
+/// `EltDelta::SetAll` and `EltDelta::Clear` target all of them.
+fn target_checksum<T>(vec: &[T], change: &EltDelta<T>) -> Option<(usize, u64)>
+where T: Hash + Core {
+    let mut hasher = FnvHasher::default();
+    let index: usize = match change {
+        EltDelta::Edit { index, .. } | EltDelta::Delete { index } => {
+            vec.get(*index)?.hash(&mut hasher);
+            *index
+        },
+        EltDelta::Swap { i, j } => {
+            (vec.get(*i)?, vec.get(*j)?).hash(&mut hasher);
+            usize::min(*i, *j)
+        },
+        EltDelta::Remove { count } => {
+            let index: usize = vec.len().checked_sub(*count)?;
+            hash_items(&vec[index ..], &mut hasher);
+            index
+        },
+        EltDelta::DeleteRange { index, count } => {
+            hash_items(vec.get(*index .. index.checked_add(*count)?)?, &mut hasher);
+            *index
+        },
+        EltDelta::SetAll(_) | EltDelta::Clear => {
+            hash_items(vec, &mut hasher);
+            0
+        },
+        EltDelta::Add(_) | EltDelta::Insert { .. } | EltDelta::Prepend(_) =>
+            return None,
+    };
+    Some((index, hasher.finish()))
+}
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Hash + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Attach to `self` a checksum of the target of each of its operations,
+    /// as seen when applying `self` to `base`.
+    pub fn with_checksums(self, base: &[T]) -> DeltaResult<CheckedVecDelta<T>> {
+        let mut vec: Vec<T> = base.to_vec();
+        let mut checksums = vec![];
+        for change in self.iter() {
+            checksums.push(target_checksum(&vec, change));
+            apply_elt_delta(&mut vec, change.clone())?;
+        }
+        Ok(CheckedVecDelta { delta: self, checksums })
+    }
+
+    /// Apply `delta` to `base`, verifying before each operation that its
+    /// target still has the content it had when `delta` was made.
+    /// If it doesn't, e.g. because that part of `base` was concurrently
+    /// edited, a `DeltaError::Conflict` for the target index is returned.
+    pub fn apply_checked(
+        base: &[T],
+        delta: CheckedVecDelta<T>,
+    ) -> DeltaResult<Vec<T>> {
+        let CheckedVecDelta { delta, checksums } = delta;
+        ensure_eq!(delta.len(), checksums.len())?;
+        let mut vec: Vec<T> = base.to_vec();
+        for (change, expected) in delta.into_iter().zip(checksums) {
+            if let Some((index, _)) = expected {
+                if target_checksum(&vec, &change) != expected {
+                    return Err(DeltaError::Conflict {
+                        index: Some(index),
+                        key: None,
+                    });
+                }
+            }
+            apply_elt_delta(&mut vec, change)?;
+        }
+        Ok(vec)
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Delta;
+
+    #[test]
+    fn VecDelta__apply_checked() -> DeltaResult<()> {
+        let vec0: Vec<u32> = vec![1, 2, 3, 4, 5];
+        let vec1: Vec<u32> = vec![1, 20, 3, 40];
+        let delta = vec0.delta(&vec1)?.with_checksums(&vec0)?;
+        assert_eq!(VecDelta::apply_checked(&vec0, delta.clone())?, vec1);
+
+        // NOTE: The element at index 1 was changed concurrently:
+        let concurrent: Vec<u32> = vec![1, 7, 3, 4, 5];
+        let result = VecDelta::apply_checked(&concurrent, delta.clone());
+        assert_eq!(result, Err(DeltaError::Conflict {
+            index: Some(1),
+            key: None,
+        }));
+
+        // NOTE: The element at index 4 was changed concurrently:
+        let concurrent: Vec<u32> = vec![1, 2, 3, 4, 6];
+        let result = VecDelta::apply_checked(&concurrent, delta);
+        assert_eq!(result, Err(DeltaError::Conflict {
+            index: Some(4),
+            key: None,
+        }));
+        Ok(())
+    }
+
+    #[test]
+    fn VecDelta__with_checksums__stable() -> DeltaResult<()> {
+        let vec0: Vec<u32> = vec![1, 2];
+        let vec1: Vec<u32> = vec![1];
+        let delta = vec0.delta(&vec1)?.with_checksums(&vec0)?;
+        // NOTE: The checksum must not depend on the Rust release or the
+        //       platform, so pin it to the FNV-1a hash of the length `1u64`
+        //       followed by `2u32`, both in little-endian order.
+        assert_eq!(delta.delta.0, vec![EltDelta::Remove { count: 1 }]);
+        assert_eq!(delta.checksums, vec![Some((1, 0x9f19_854a_6ead_a506))]);
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+//! Semantic cleanup of `VecDelta`s, which trades minimality for fewer,
+//! larger changes, like the semantic cleanup of diff-match-patch.
+
+use crate::{Apply, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::vec::{EltDelta, VecDelta};
+use crate::vec::myers::{self, DiffOp};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+
+/// A maximal run of `DiffOp`s of a diff.
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    /// Unchanged elements, as `(old, new)` index pairs.
+    Equal(Vec<(usize, usize)>),
+    /// Deleted old indices, and inserted new indices, in ascending order.
+    Change { deleted: Vec<usize>, inserted: Vec<usize> },
+}
+
+/// Group `ops` into alternating `Segment`s.
+fn segments(ops: Vec<DiffOp>) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = vec![];
+    for op in ops {
+        match (segments.last_mut(), op) {
+            (Some(Segment::Equal(pairs)), DiffOp::Equal { old, new }) =>
+                pairs.push((old, new)),
+            (_, DiffOp::Equal { old, new }) =>
+                segments.push(Segment::Equal(vec![(old, new)])),
+            (Some(Segment::Change { deleted, .. }), DiffOp::Delete { old }) =>
+                deleted.push(old),
+            (Some(Segment::Change { inserted, .. }), DiffOp::Insert { new }) =>
+                inserted.push(new),
+            (_, DiffOp::Delete { old }) =>
+                segments.push(Segment::Change { deleted: vec![old], inserted: vec![] }),
+            (_, DiffOp::Insert { new }) =>
+                segments.push(Segment::Change { deleted: vec![], inserted: vec![new] }),
+        }
+    }
+    segments
+}
+
+/// Absorb each run of unchanged elements that is no longer than the
+/// changes on either side of it into those changes, until none is left.
+fn absorb_short_equalities(segments: &mut Vec<Segment>) {
+    let size = |segment: &Segment| match segment {
+        Segment::Change { deleted, inserted } => usize::max(deleted.len(), inserted.len()),
+        Segment::Equal(_) => 0,
+    };
+    let mut k = 1;
+    while k + 1 < segments.len() {
+        let absorb = match &segments[k] {
+            Segment::Equal(pairs) =>
+                pairs.len() <= size(&segments[k - 1])
+                    && pairs.len() <= size(&segments[k + 1]),
+            Segment::Change { .. } => false,
+        };
+        if !absorb { k += 1; continue }
+        let merged: Vec<Segment> = segments.drain(k - 1 ..= k + 1).collect();
+        let (mut deleted, mut inserted) = (vec![], vec![]);
+        for segment in merged { match segment {
+            Segment::Equal(pairs) => for (old, new) in pairs {
+                deleted.push(old);
+                inserted.push(new);
+            },
+            Segment::Change { deleted: d, inserted: i } => {
+                deleted.extend(d);
+                inserted.extend(i);
+            },
+        }}
+        deleted.sort_unstable();
+        inserted.sort_unstable();
+        segments.insert(k - 1, Segment::Change { deleted, inserted });
+        // NOTE: The merged change may now absorb a preceding equality:
+        k = usize::max(k - 1, 1);
+    }
+}
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Apply + Delta + FromDelta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Rewrite `self`, which applies to `base`, into a delta that changes
+    /// fewer, larger regions of `base`, at the cost of minimality: a short
+    /// run of unchanged elements in between 2 larger changes is treated as
+    /// changed as well, so that the changes merge.  Within each region,
+    /// changed elements are expressed as `EltDelta::Edit`s where possible,
+    /// followed by a single `EltDelta::DeleteRange` or the `EltDelta::Insert`s
+    /// for the difference in length.
+    ///
+    /// Unlike the delta itself, this needs `base`, since the elements
+    /// that are treated as changed must be included in the delta.
+    pub fn cleanup_semantic(&mut self, base: &[T]) -> DeltaResult<()> {
+        let new: Vec<T> = base.to_vec().apply(self.clone())?;
+        let ops: Vec<DiffOp> = myers::diff(base.len(), new.len(), |i, j| {
+            base[i] == new[j]
+        });
+        let mut segments: Vec<Segment> = segments(ops);
+        absorb_short_equalities(&mut segments);
+        let mut changes: Vec<EltDelta<T>> = vec![];
+        // NOTE: `index` tracks the position in the `Vec` as it looks
+        //       after all previous changes have been applied to it:
+        let mut index = 0;
+        for segment in segments { match segment {
+            Segment::Equal(pairs) => index += pairs.len(),
+            Segment::Change { deleted, inserted } => {
+                let edited: usize = usize::min(deleted.len(), inserted.len());
+                for (k, (old, new_pos)) in deleted.iter().zip(&inserted).enumerate() {
+                    let item = base[*old].delta(&new[*new_pos])?;
+                    changes.push(EltDelta::Edit { index: index + k, item });
+                }
+                match deleted.len() - edited {
+                    0 => {},
+                    1 => changes.push(EltDelta::Delete { index: index + edited }),
+                    count => changes.push(EltDelta::DeleteRange {
+                        index: index + edited,
+                        count,
+                    }),
+                }
+                for (k, new_pos) in inserted[edited ..].iter().enumerate() {
+                    let item = new[*new_pos].clone().into_delta()?;
+                    changes.push(EltDelta::Insert { index: index + edited + k, item });
+                }
+                index += inserted.len();
+            },
+        }}
+        self.0 = changes;
+        Ok(())
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DeltaConfig, VecStrategy};
+
+    #[test]
+    fn VecDelta__cleanup_semantic() -> DeltaResult<()> {
+        let vec0: Vec<char> = "the quick brown fox".chars().collect();
+        let vec1: Vec<char> = "thy quack briwn fix!".chars().collect();
+        let config = DeltaConfig {
+            vec_strategy: VecStrategy::Myers,
+            ..Default::default()
+        };
+        let mut delta: VecDelta<char> =
+            crate::vec::delta_with_config(&vec0, &vec1, &config)?;
+        let noisy_len: usize = delta.len();
+        delta.cleanup_semantic(&vec0)?;
+        assert!(delta.len() < noisy_len, "{} >= {}", delta.len(), noisy_len);
+        assert_eq!(vec0.apply(delta)?, vec1);
+        Ok(())
+    }
+
+    #[test]
+    fn VecDelta__cleanup_semantic__keeps_long_equalities() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 20).collect();
+        let mut vec1: Vec<u32> = vec0.clone();
+        vec1.insert(15, 100);
+        vec1.remove(2);
+        let mut delta: VecDelta<u32> = vec0.delta(&vec1)?;
+        delta.cleanup_semantic(&vec0)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::Delete { index: 2 },
+            EltDelta::Insert { index: 14, item: 100u32.into_delta()? },
+        ]));
+        assert_eq!(vec0.apply(delta)?, vec1);
+        Ok(())
+    }
+}
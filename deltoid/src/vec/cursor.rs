@@ -0,0 +1,294 @@
+//! In-place application of `VecDelta`s, from the tail of the `Vec` toward
+//! its head.
+
+use crate::{Apply, DeltaResult, FromDelta};
+use crate::vec::{apply_elt_delta, EltDelta, VecDelta};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::ops::Range;
+
+
+/// Returns `true` if `changes` only insert, delete or edit elements, at
+/// indices that never move backwards, as e.g. in a `VecDelta` calculated
+/// with `VecStrategy::Myers`.  The indices of such changes can be mapped
+/// back to indices into the original `Vec`, and so they can be applied in
+/// any order.
+fn is_cursor_ordered<T: crate::Core>(changes: &[EltDelta<T>]) -> bool {
+    // NOTE: `next` is the lowest index that the next change may have:
+    let mut next: usize = 0;
+    for change in changes {
+        next = match change {
+            EltDelta::Insert { index, .. } | EltDelta::Edit { index, .. }
+                if *index >= next => index + 1,
+            EltDelta::Delete { index } | EltDelta::DeleteRange { index, .. }
+                if *index >= next => *index,
+            _ => return false,
+        };
+    }
+    true
+}
+
+/// A cursor-ordered delta, with its indices mapped back to indices into
+/// the original `Vec`.
+struct Hunks<T: crate::Core> {
+    /// Each hunk replaces a range of the original elements with new ones.
+    /// The ranges are ascending and don't overlap.
+    hunks: Vec<(Range<usize>, Vec<T>)>,
+    /// The edits of original elements, at ascending indices.
+    edits: Vec<(usize, T::Delta)>,
+}
+
+impl<T> Hunks<T>
+where T: Apply + FromDelta {
+    /// Map `changes`, which must be cursor-ordered, to indices into a `Vec`
+    /// of length `len`.  This validates all indices, and converts all new
+    /// elements, without touching that `Vec`.
+    fn new(len: usize, changes: Vec<EltDelta<T>>) -> DeltaResult<Self> {
+        let mut hunks: Vec<(Range<usize>, Vec<T>)> = vec![];
+        let mut edits: Vec<(usize, T::Delta)> = vec![];
+        // NOTE: `out` is the index in the new `Vec` that corresponds to
+        //       the index `orig` in the original one:
+        let (mut out, mut orig): (usize, usize) = (0, 0);
+        for change in changes {
+            let index: usize = match change {
+                EltDelta::Insert { index, .. } | EltDelta::Edit { index, .. }
+                    | EltDelta::Delete { index } | EltDelta::DeleteRange { index, .. }
+                    => index,
+                _ => return bug_detected!(),
+            };
+            orig += index - out;
+            out = index;
+            ensure_le![orig, len]?;
+            let (start, end, item): (usize, usize, Option<T>) = match change {
+                EltDelta::Insert { item, .. } => {
+                    out += 1;
+                    (orig, orig, Some(<T>::from_delta(item)?))
+                },
+                EltDelta::Edit { item, .. } => {
+                    ensure_lt![orig, len]?;
+                    edits.push((orig, item));
+                    orig += 1;
+                    out += 1;
+                    continue;
+                },
+                EltDelta::Delete { .. } => {
+                    ensure_lt![orig, len]?;
+                    orig += 1;
+                    (orig - 1, orig, None)
+                },
+                EltDelta::DeleteRange { count, .. } => {
+                    ensure_le![count, len - orig]?;
+                    orig += count;
+                    (orig - count, orig, None)
+                },
+                _ => return bug_detected!(),
+            };
+            match hunks.last_mut() {
+                Some((range, items)) if range.end == start => {
+                    range.end = end;
+                    items.extend(item);
+                },
+                _ => hunks.push((start .. end, item.into_iter().collect())),
+            }
+        }
+        Ok(Self { hunks, edits })
+    }
+
+    /// Apply `self` to `vec`, from its tail toward its head, so that the
+    /// indices of the hunks that are yet to be applied remain valid.
+    fn apply_to(self, vec: &mut Vec<T>) -> DeltaResult<()> {
+        for (index, item) in self.edits.into_iter().rev() {
+            vec[index].apply_mut(item)?;
+        }
+        let growth: usize = self.hunks.iter()
+            .map(|(range, items)| items.len().saturating_sub(range.len()))
+            .sum();
+        vec.reserve(growth);
+        for (range, items) in self.hunks.into_iter().rev() {
+            vec.splice(range, items);
+        }
+        Ok(())
+    }
+}
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Apply `delta` to `vec` in place, without cloning any of its
+    /// elements.  If `delta` only inserts, deletes or edits elements at
+    /// ascending indices, as the deltas of `VecStrategy::Myers` do, its
+    /// changes are applied from the tail of `vec` toward its head, so that
+    /// each of them only shifts the elements that follow it in `vec`, and
+    /// never the elements inserted by a previous change.  Any other delta
+    /// is applied one operation at a time.
+    ///
+    /// If an error is returned for a delta of the first kind, the length
+    /// and order of the elements of `vec` are unchanged, but the edits of
+    /// elements after the failing edit may have been applied.  For a delta
+    /// of the second kind, the operations before the failing one have been
+    /// applied.
+    pub fn apply_delta_mut(vec: &mut Vec<T>, delta: VecDelta<T>) -> DeltaResult<()> {
+        if is_cursor_ordered(&delta.0) {
+            return Hunks::new(vec.len(), delta.0)?.apply_to(vec);
+        }
+        for change in delta.0 {
+            apply_elt_delta(vec, change)?;
+        }
+        Ok(())
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Core, Delta, DeltaConfig, IntoDelta, U32Delta, VecStrategy};
+    use crate::vec::delta_with_config;
+    use std::cell::Cell;
+
+    thread_local! {
+        static CLONES: Cell<usize> = const { Cell::new(0) };
+        static DROPS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// An element type that counts how often it's cloned and dropped.
+    #[derive(Debug, PartialEq)]
+    #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+    struct Counted(u32);
+
+    impl Counted {
+        fn reset() {
+            CLONES.with(|c| c.set(0));
+            DROPS.with(|c| c.set(0));
+        }
+
+        fn clones() -> usize { CLONES.with(|c| c.get()) }
+
+        fn drops() -> usize { DROPS.with(|c| c.get()) }
+    }
+
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            CLONES.with(|c| c.set(c.get() + 1));
+            Counted(self.0)
+        }
+    }
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPS.with(|c| c.set(c.get() + 1));
+        }
+    }
+
+    impl Core for Counted {
+        type Delta = U32Delta;
+    }
+
+    impl Apply for Counted {
+        fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+            Ok(Counted(self.0.apply(delta)?))
+        }
+
+        fn apply_mut(&mut self, delta: Self::Delta) -> DeltaResult<()> {
+            self.0.apply_mut(delta)
+        }
+    }
+
+    impl Delta for Counted {
+        fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+            self.0.delta(&rhs.0)
+        }
+    }
+
+    impl FromDelta for Counted {
+        fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+            Ok(Counted(u32::from_delta(delta)?))
+        }
+    }
+
+    impl IntoDelta for Counted {
+        fn into_delta(self) -> DeltaResult<Self::Delta> {
+            self.0.into_delta()
+        }
+    }
+
+    #[test]
+    fn VecDelta__apply_delta_mut() -> DeltaResult<()> {
+        let config = DeltaConfig {
+            vec_strategy: VecStrategy::Myers,
+            ..Default::default()
+        };
+        let vec0: Vec<Counted> = (0 .. 1000).map(Counted).collect();
+        let vec1: Vec<Counted> = (0 .. 1000)
+            .filter(|n| n % 100 != 50)
+            .flat_map(|n| if n % 100 == 10 { vec![n, 5000 + n] } else { vec![n] })
+            .map(|n| if n % 100 == 70 { n + 1 } else { n })
+            .map(Counted)
+            .collect();
+        let delta = delta_with_config(&vec0, &vec1, &config)?;
+        assert!(is_cursor_ordered(&delta.0));
+
+        Counted::reset();
+        let vec2: Vec<Counted> = vec0.apply(delta.clone())?;
+        let (naive_clones, naive_drops) = (Counted::clones(), Counted::drops());
+        assert_eq!(vec2, vec1);
+
+        let mut vec3: Vec<Counted> = vec0.clone();
+        Counted::reset();
+        VecDelta::apply_delta_mut(&mut vec3, delta)?;
+        assert_eq!(vec3, vec1);
+        // NOTE: Only the 10 deleted and the 10 replaced elements are
+        //       dropped, while a naive apply clones all elements first:
+        assert_eq!((Counted::clones(), Counted::drops()), (0, 20));
+        assert_eq!((naive_clones, naive_drops), (vec0.len(), 20));
+        Ok(())
+    }
+
+    #[test]
+    fn VecDelta__apply_delta_mut__error() -> DeltaResult<()> {
+        let vec0: Vec<u32> = vec![1, 2, 3, 4];
+        let delta: VecDelta<u32> = VecDelta(vec![
+            EltDelta::Delete { index: 1 },
+            EltDelta::Insert { index: 2, item: 0u32.into_delta()? },
+            EltDelta::Delete { index: 4 },
+        ]);
+        assert!(is_cursor_ordered(&delta.0));
+        let mut vec1: Vec<u32> = vec0.clone();
+        assert!(VecDelta::apply_delta_mut(&mut vec1, delta).is_err());
+        assert_eq!(vec1, vec0);
+        Ok(())
+    }
+
+    #[test]
+    fn VecDelta__apply_delta_mut__unordered() -> DeltaResult<()> {
+        let vec0: Vec<u32> = vec![1, 2, 3, 4];
+        let delta: VecDelta<u32> = VecDelta(vec![
+            EltDelta::Delete { index: 2 },
+            EltDelta::Insert { index: 0, item: 0u32.into_delta()? },
+            EltDelta::Swap { i: 0, j: 1 },
+        ]);
+        assert!(!is_cursor_ordered(&delta.0));
+        let mut vec1: Vec<u32> = vec0.clone();
+        VecDelta::apply_delta_mut(&mut vec1, delta.clone())?;
+        assert_eq!(vec1, vec0.apply(delta)?);
+        Ok(())
+    }
+
+    #[test]
+    fn VecDelta__apply_delta_mut__empty_range() -> DeltaResult<()> {
+        let vec0: Vec<u32> = vec![1, 2, 3, 4];
+        let delta: VecDelta<u32> = VecDelta(vec![
+            EltDelta::DeleteRange { index: 1, count: 0 },
+        ]);
+        assert!(is_cursor_ordered(&delta.0));
+        let mut vec1: Vec<u32> = vec0.clone();
+        vec1.apply_mut(delta.clone())?;
+        assert_eq!(vec1, vec0.apply(delta)?);
+        assert_eq!(vec1, vec0);
+        Ok(())
+    }
+}
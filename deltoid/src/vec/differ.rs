@@ -0,0 +1,119 @@
+//! Incremental diffing of a sequence of `Vec`s against a fixed baseline.
+
+use crate::{Core, DeltaResult, IntoDelta};
+use crate::vec::{edit_script, VecDelta};
+use crate::vec::myers::DiffOp;
+
+
+/// Diffs `Vec`s against a fixed baseline, memoizing the longest common
+/// subsequence (LCS) table between calls.  When the `Vec` passed to
+/// `Self::diff_against()` shares a prefix with the one passed to the
+/// previous call, e.g. because it only grew in the meantime, the rows
+/// of the table belonging to that prefix are reused rather than
+/// recalculated.
+#[derive(Clone, Debug)]
+pub struct VecDiffer<T> {
+    baseline: Vec<T>,
+    /// The `Vec` that `baseline` was most recently diffed against.
+    prev: Vec<T>,
+    /// `rows[j][i]` is the length of the LCS of `baseline[.. i]`
+    /// and `prev[.. j]`.  Thus `rows[0]` consists of zeroes.
+    rows: Vec<Vec<usize>>,
+}
+
+impl<T> VecDiffer<T>
+where T: Clone + PartialEq + Core + IntoDelta {
+    pub fn new(baseline: Vec<T>) -> Self {
+        let rows = vec![vec![0; baseline.len() + 1]];
+        Self { baseline, prev: vec![], rows }
+    }
+
+    #[inline(always)]
+    pub fn baseline(&self) -> &[T] { &self.baseline }
+
+    /// Calculate `baseline --[delta]--> new`, as a minimal sequence of
+    /// `EltDelta::Insert`s and `EltDelta::Delete`s.
+    pub fn diff_against(&mut self, new: &[T]) -> DeltaResult<VecDelta<T>> {
+        let reusable: usize = self.prev.iter()
+            .zip(new.iter())
+            .take_while(|(p, n)| p == n)
+            .count();
+        self.rows.truncate(reusable + 1);
+        self.prev.truncate(reusable);
+        for item in &new[reusable ..] {
+            let above: &[usize] = self.rows.last().unwrap(/*rows[0] exists*/);
+            let mut row: Vec<usize> = vec![0; self.baseline.len() + 1];
+            for (i, base) in self.baseline.iter().enumerate() {
+                row[i + 1] = if base == item {
+                    above[i] + 1
+                } else {
+                    usize::max(above[i + 1], row[i])
+                };
+            }
+            self.rows.push(row);
+            self.prev.push(item.clone());
+        }
+        edit_script(new, self.backtrack()).map(VecDelta)
+    }
+
+    /// Read an edit script transforming `self.baseline` into `self.prev`
+    /// from the LCS table.  Where there is a choice between a deletion
+    /// and an insertion, the deletion is placed first.
+    fn backtrack(&self) -> Vec<DiffOp> {
+        let mut ops: Vec<DiffOp> = vec![];
+        let (mut i, mut j) = (self.baseline.len(), self.prev.len());
+        while i > 0 || j > 0 {
+            if i > 0 && j > 0 && self.baseline[i - 1] == self.prev[j - 1] {
+                ops.push(DiffOp::Equal { old: i - 1, new: j - 1 });
+                i -= 1;
+                j -= 1;
+            } else if j > 0 && (i == 0 || self.rows[j - 1][i] >= self.rows[j][i - 1]) {
+                ops.push(DiffOp::Insert { new: j - 1 });
+                j -= 1;
+            } else {
+                ops.push(DiffOp::Delete { old: i - 1 });
+                i -= 1;
+            }
+        }
+        ops.reverse();
+        ops
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Apply, DeltaConfig, VecStrategy};
+    use crate::vec::delta_with_config;
+
+    #[test]
+    fn VecDiffer__diff_against__growing_vec() -> DeltaResult<()> {
+        let baseline: Vec<char> = "abcabba".chars().collect();
+        let mut differ = VecDiffer::new(baseline.clone());
+        let config = DeltaConfig {
+            vec_strategy: VecStrategy::Myers,
+            ..Default::default()
+        };
+        let mut new: Vec<char> = vec![];
+        for c in "cbabacxyzab".chars() {
+            new.push(c);
+            let delta = differ.diff_against(&new)?;
+            let fresh = VecDiffer::new(baseline.clone()).diff_against(&new)?;
+            assert_eq!(delta, fresh);
+            let myers = delta_with_config(&baseline, &new, &config)?;
+            assert_eq!(delta.edit_distance(), myers.edit_distance());
+            assert_eq!(baseline.apply(delta)?, new);
+        }
+        // NOTE: Diffing against a `Vec` with a different prefix
+        //       invalidates the memoized rows past that prefix:
+        let new: Vec<char> = "cbxbac".chars().collect();
+        let delta = differ.diff_against(&new)?;
+        let fresh = VecDiffer::new(baseline.clone()).diff_against(&new)?;
+        assert_eq!(delta, fresh);
+        assert_eq!(baseline.apply(delta)?, new);
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+//! Calculation of `VecDelta`s guided by caller-known changed ranges.
+
+use crate::{Delta, DeltaResult, IntoDelta};
+use crate::vec::{EltDelta, VecDelta};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::ops::Range;
+
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Calculate `old --[delta]--> new` positionally, like
+    /// `VecStrategy::Positional`, but only compare the elements at the
+    /// indices in `dirty_ranges`, e.g. as reported by a dirty-tracking
+    /// system.  All other elements that `old` and `new` have in common
+    /// are assumed to be unchanged, while any elements past the end of
+    /// the shorter of the two are always added or removed.
+    ///
+    /// In debug builds, this panics if that assumption doesn't hold,
+    /// i.e. if an element outside of `dirty_ranges` did change.
+    pub fn diff_with_hint(
+        old: &[T],
+        new: &[T],
+        dirty_ranges: &[Range<usize>],
+    ) -> DeltaResult<Self> {
+        let common_len: usize = usize::min(old.len(), new.len());
+        let mut dirty: Vec<Range<usize>> = dirty_ranges.iter()
+            .map(|range| range.start .. usize::min(range.end, common_len))
+            .filter(|range| range.start < range.end)
+            .collect();
+        dirty.sort_by_key(|range| range.start);
+        let mut changes: Vec<EltDelta<T>> = vec![];
+        // NOTE: `next` is the first index that hasn't been visited yet,
+        //       which keeps overlapping ranges from producing duplicates:
+        let mut next: usize = 0;
+        for range in dirty {
+            let start: usize = usize::max(range.start, next);
+            debug_assert!(
+                old[next .. start] == new[next .. start],
+                "an element outside of the dirty ranges changed",
+            );
+            for index in start .. range.end {
+                if old[index] != new[index] {
+                    let item = old[index].delta(&new[index])?;
+                    changes.push(EltDelta::Edit { index, item });
+                }
+            }
+            next = usize::max(next, range.end);
+        }
+        debug_assert!(
+            old[next .. common_len] == new[next .. common_len],
+            "an element outside of the dirty ranges changed",
+        );
+        for item in &new[common_len ..] {
+            changes.push(EltDelta::Add(item.clone().into_delta()?));
+        }
+        if old.len() > common_len {
+            changes.push(EltDelta::Remove { count: old.len() - common_len });
+        }
+        Ok(VecDelta(changes))
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Apply;
+
+    #[test]
+    fn VecDelta__diff_with_hint() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 1000).collect();
+        let mut vec1: Vec<u32> = vec0.clone();
+        vec1[10] = 0;
+        vec1[500] = 1;
+        vec1[501] = 2;
+        vec1.push(1000);
+        let delta = VecDelta::diff_with_hint(&vec0, &vec1, &[500 .. 510, 8 .. 12])?;
+        assert_eq!(delta, vec0.delta(&vec1)?);
+        assert_eq!(vec0.apply(delta)?, vec1);
+
+        let vec2: Vec<u32> = vec0[.. 990].to_vec();
+        let delta = VecDelta::diff_with_hint(&vec0, &vec2, &[])?;
+        assert_eq!(delta, VecDelta(vec![EltDelta::Remove { count: 10 }]));
+        Ok(())
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "an element outside of the dirty ranges changed")]
+    fn VecDelta__diff_with_hint__incorrect_hint() {
+        let vec0: Vec<u32> = (0 .. 1000).collect();
+        let mut vec1: Vec<u32> = vec0.clone();
+        vec1[10] = 0;
+        let _ = VecDelta::diff_with_hint(&vec0, &vec1, &[0 .. 5, 500 .. 510]);
+    }
+}
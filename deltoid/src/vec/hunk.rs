@@ -0,0 +1,129 @@
+//! Rendering of `VecDelta`s as unified diff hunks, for interop with
+//! `patch`-like tools when each value in a `Vec` represents a line e.g.:
+//!
+//! ```text
+//! @@ -2,3 +2,3 @@
+//!  two
+//! -three
+//! +THREE
+//!  four
+//! ```
+
+use crate::{Apply, DeltaResult, FromDelta};
+use crate::vec::VecDelta;
+use crate::vec::myers::{self, DiffOp};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Display};
+
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Display + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Render the changes that `self` makes to `base` as unified diff
+    /// hunks, each surrounded by up to `context` unchanged lines.
+    pub fn to_hunks(&self, base: &[T], context: usize) -> DeltaResult<String> {
+        let new: Vec<T> = base.to_vec().apply(self.clone())?;
+        let ops: Vec<DiffOp> = myers::diff(base.len(), new.len(), |i, j| {
+            base[i] == new[j]
+        });
+        // NOTE: `positions[k]` is the (old, new) position before `ops[k]`:
+        let mut positions: Vec<(usize, usize)> = vec![];
+        let (mut old, mut new_pos) = (0, 0);
+        for op in &ops {
+            positions.push((old, new_pos));
+            match op {
+                DiffOp::Equal { .. } => { old += 1; new_pos += 1; },
+                DiffOp::Delete { .. } => old += 1,
+                DiffOp::Insert { .. } => new_pos += 1,
+            }
+        }
+        // NOTE: Group the changes into hunks as ranges of `ops`, merging
+        //       hunks whose context would otherwise overlap or touch:
+        let mut hunks: Vec<(usize, usize)> = vec![];
+        for (k, op) in ops.iter().enumerate() {
+            if let DiffOp::Equal { .. } = op { continue }
+            let start = k.saturating_sub(context);
+            let end = usize::min(ops.len(), k + 1 + context);
+            match hunks.last_mut() {
+                Some((_, last_end)) if start <= *last_end => *last_end = end,
+                _ => hunks.push((start, end)),
+            }
+        }
+        let mut text = String::new();
+        for (start, end) in hunks {
+            let (old_start, new_start) = positions[start];
+            let mut lines: Vec<String> = vec![];
+            let (mut old_count, mut new_count) = (0, 0);
+            for op in &ops[start .. end] { match op {
+                DiffOp::Equal { old, .. } => {
+                    lines.push(format!(" {}", base[*old]));
+                    old_count += 1;
+                    new_count += 1;
+                },
+                DiffOp::Delete { old } => {
+                    lines.push(format!("-{}", base[*old]));
+                    old_count += 1;
+                },
+                DiffOp::Insert { new: n } => {
+                    lines.push(format!("+{}", new[*n]));
+                    new_count += 1;
+                },
+            }}
+            // NOTE: Line numbers are 1-based, except that an empty range
+            //       is identified by the line right before it:
+            let line_no = |start: usize, count: usize| {
+                if count == 0 { start } else { start + 1 }
+            };
+            text.push_str(&format!(
+                "@@ -{},{} +{},{} @@\n",
+                line_no(old_start, old_count), old_count,
+                line_no(new_start, new_count), new_count,
+            ));
+            for line in lines {
+                text.push_str(&line);
+                text.push('\n');
+            }
+        }
+        Ok(text)
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Delta;
+
+    #[test]
+    fn VecDelta__to_hunks() -> DeltaResult<()> {
+        let lines = |text: &str| -> Vec<String> {
+            text.split_whitespace().map(String::from).collect()
+        };
+        let base: Vec<String> = lines("one two three four five six seven eight nine ten");
+        let new: Vec<String> = lines("one two THREE four five six seven eight nine ten eleven");
+        let delta = base.delta(&new)?;
+        assert_eq!(delta.to_hunks(&base, 1)?, concat!(
+            "@@ -2,3 +2,3 @@\n",
+            " two\n",
+            "-three\n",
+            "+THREE\n",
+            " four\n",
+            "@@ -10,1 +10,2 @@\n",
+            " ten\n",
+            "+eleven\n",
+        ));
+        assert_eq!(delta.to_hunks(&base, 0)?, concat!(
+            "@@ -3,1 +3,1 @@\n",
+            "-three\n",
+            "+THREE\n",
+            "@@ -10,0 +11,1 @@\n",
+            "+eleven\n",
+        ));
+        assert_eq!(base.delta(&base)?.to_hunks(&base, 3)?, "");
+        Ok(())
+    }
+}
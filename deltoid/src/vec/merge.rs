@@ -0,0 +1,137 @@
+//! Three-way merging of `Vec`s that were edited independently,
+//! starting from a common ancestor.
+
+use crate::DeltaResult;
+use crate::vec::myers::{self, DiffOp};
+use std::ops::Range;
+
+
+/// The outcome of `merge3()`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MergeResult<T> {
+    /// Both sets of edits were merged cleanly into the given `Vec`.
+    Merged(Vec<T>),
+    /// Some edits overlap, and can't be merged automatically.
+    Conflicts(Vec<MergeConflict<T>>),
+}
+
+/// A region of the ancestor that was edited differently on both sides.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MergeConflict<T> {
+    /// The conflicting region, as a range of indices into the ancestor.
+    /// This range is empty if both sides inserted values at one position.
+    pub range: Range<usize>,
+    /// The values that replaced the region in `mine`.
+    pub mine: Vec<T>,
+    /// The values that replaced the region in `theirs`.
+    pub theirs: Vec<T>,
+}
+
+/// Merge the edits that turned `ancestor` into `mine` with those that
+/// turned it into `theirs`.  Regions of `ancestor` that were edited on
+/// only one side take that side's edits, as do regions that were edited
+/// identically on both sides.  Any region that was edited differently on
+/// both sides is reported as a `MergeConflict`.
+pub fn merge3<T>(
+    ancestor: &[T],
+    mine: &[T],
+    theirs: &[T],
+) -> DeltaResult<MergeResult<T>>
+where T: Clone + PartialEq {
+    let mine_matches: Vec<Option<usize>> = matches(ancestor, mine);
+    let theirs_matches: Vec<Option<usize>> = matches(ancestor, theirs);
+    // NOTE: An ancestor index is stable iff. its value is kept on both sides:
+    let stable = |i: usize| match (mine_matches[i], theirs_matches[i]) {
+        (Some(j), Some(k)) => Some((j, k)),
+        _ => None,
+    };
+    let mut merged: Vec<T> = vec![];
+    let mut conflicts: Vec<MergeConflict<T>> = vec![];
+    let (mut i, mut j, mut k) = (0, 0, 0);
+    loop {
+        // NOTE: Copy the stable values, then find the next stable index
+        //       or the end of all 3 `Vec`s, bounding an unstable chunk:
+        while i < ancestor.len() && stable(i) == Some((j, k)) {
+            merged.push(ancestor[i].clone());
+            i += 1;
+            j += 1;
+            k += 1;
+        }
+        let (next_i, next_j, next_k) = (i .. ancestor.len())
+            .find_map(|i| stable(i).map(|(j, k)| (i, j, k)))
+            .unwrap_or((ancestor.len(), mine.len(), theirs.len()));
+        let base_chunk: &[T] = &ancestor[i .. next_i];
+        let mine_chunk: &[T] = &mine[j .. next_j];
+        let theirs_chunk: &[T] = &theirs[k .. next_k];
+        if mine_chunk == base_chunk || mine_chunk == theirs_chunk {
+            merged.extend_from_slice(theirs_chunk);
+        } else if theirs_chunk == base_chunk {
+            merged.extend_from_slice(mine_chunk);
+        } else {
+            conflicts.push(MergeConflict {
+                range: i .. next_i,
+                mine: mine_chunk.to_vec(),
+                theirs: theirs_chunk.to_vec(),
+            });
+        }
+        if next_i == ancestor.len() { break }
+        i = next_i;
+        j = next_j;
+        k = next_k;
+    }
+    Ok(if conflicts.is_empty() {
+        MergeResult::Merged(merged)
+    } else {
+        MergeResult::Conflicts(conflicts)
+    })
+}
+
+/// For each index into `lhs`, return the index into `rhs` of the value
+/// it's matched with by a shortest edit script, if any.
+fn matches<T: PartialEq>(lhs: &[T], rhs: &[T]) -> Vec<Option<usize>> {
+    let mut matches: Vec<Option<usize>> = vec![None; lhs.len()];
+    for op in myers::diff(lhs.len(), rhs.len(), |i, j| lhs[i] == rhs[j]) {
+        if let DiffOp::Equal { old, new } = op { matches[old] = Some(new); }
+    }
+    matches
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge3__disjoint_edits() -> DeltaResult<()> {
+        let ancestor: Vec<u32> = vec![1, 2, 3, 4, 5, 6];
+        let mine: Vec<u32>     = vec![1, 20, 3, 4, 5, 6, 7];
+        let theirs: Vec<u32>   = vec![0, 1, 2, 3, 5, 6];
+        let result = merge3(&ancestor, &mine, &theirs)?;
+        assert_eq!(result, MergeResult::Merged(vec![0, 1, 20, 3, 5, 6, 7]));
+        // NOTE: Identical edits on both sides merge cleanly, too:
+        let result = merge3(&ancestor, &mine, &mine)?;
+        assert_eq!(result, MergeResult::Merged(mine.clone()));
+        let result = merge3(&ancestor, &ancestor, &ancestor)?;
+        assert_eq!(result, MergeResult::Merged(ancestor.clone()));
+        Ok(())
+    }
+
+    #[test]
+    fn merge3__overlapping_edits() -> DeltaResult<()> {
+        let ancestor: Vec<u32> = vec![1, 2, 3, 4, 5, 6];
+        let mine: Vec<u32>     = vec![1, 2, 30, 40, 5, 6];
+        let theirs: Vec<u32>   = vec![1, 2, 3, 41, 5, 60];
+        let result = merge3(&ancestor, &mine, &theirs)?;
+        assert_eq!(result, MergeResult::Conflicts(vec![
+            MergeConflict { range: 2 .. 4, mine: vec![30, 40], theirs: vec![3, 41] },
+        ]));
+        // NOTE: Different insertions at the same position conflict:
+        let result = merge3(&ancestor, &[1, 2, 7, 3, 4, 5, 6], &[1, 2, 8, 3, 4, 5, 6])?;
+        assert_eq!(result, MergeResult::Conflicts(vec![
+            MergeConflict { range: 2 .. 2, mine: vec![7], theirs: vec![8] },
+        ]));
+        Ok(())
+    }
+}
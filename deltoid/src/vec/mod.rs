@@ -0,0 +1,1290 @@
+//!
+
+mod adaptive;
+mod builder;
+mod checked;
+mod cleanup;
+mod cursor;
+mod differ;
+mod hint;
+mod merge;
+pub(crate) mod myers;
+mod normalize;
+mod paired;
+mod resumable;
+mod reverse;
+mod rows;
+mod seed;
+mod source;
+mod unordered;
+#[cfg(feature = "text-format")] mod hunk;
+#[cfg(feature = "text-format")] mod text;
+
+use crate::{
+    Apply, Compose, Core, Delta, DeltaError, DeltaOps, DeltaResult,
+    EmptyDelta, FromDelta, IntoDelta, Invert,
+};
+use crate::config::DeltaConfig;
+use crate::vec::myers::DiffOp;
+pub use crate::vec::adaptive::{delta_auto, select_strategy, SelectedStrategy};
+pub use crate::vec::builder::VecDeltaBuilder;
+pub use crate::vec::checked::CheckedVecDelta;
+pub use crate::vec::differ::VecDiffer;
+pub use crate::vec::merge::{merge3, MergeConflict, MergeResult};
+pub use crate::vec::paired::{delta_paired, PairedVecDelta};
+pub use crate::vec::resumable::ResumableDiff;
+pub use crate::vec::reverse::ReversibleVecDelta;
+pub use crate::vec::rows::ElementChange;
+pub use crate::vec::seed::VecDeltaSeed;
+pub use crate::vec::source::IndexedSource;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::ops::Range;
+
+
+// TODO While these impls should work fine in terms of soundness, it
+//      is actually more suited to a `Stack`-like type in terms of
+//      efficiency.  So, change the scheme to be more efficient,
+//      possibly using dynamic programming techniques to do so.
+
+impl<T> Core for Vec<T>
+where T: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    type Delta = VecDelta<T>;
+}
+
+impl<T> Apply for Vec<T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        let mut new: Self = self.clone();
+        for change in delta.into_iter() {
+            apply_elt_delta(&mut new, change)?;
+        }
+        Ok(new)
+    }
+
+    fn apply_mut(&mut self, delta: Self::Delta) -> DeltaResult<()> {
+        VecDelta::apply_delta_mut(self, delta)
+    }
+}
+
+fn apply_elt_delta<T>(vec: &mut Vec<T>, change: EltDelta<T>) -> DeltaResult<()>
+where T: Apply + FromDelta {
+    match change {
+        EltDelta::Edit { index, item } => {
+            // NOTE: If vec.len() == 0, the Edit should have been an Add:
+            ensure_gt![vec.len(), 0]?;
+            // NOTE: Ensure index is not out of bounds:
+            ensure_lt![index, vec.len()]?;
+            vec[index].apply_mut(item)?;
+        },
+        EltDelta::Add(delta) =>  vec.push(<T>::from_delta(delta)?),
+        EltDelta::Remove { count } =>  for _ in 0 .. count {
+            vec.pop().ok_or_else(|| ExpectedValue!("VecDelta<T>"))?;
+        },
+        EltDelta::Swap { i, j } => {
+            ensure_lt![i, vec.len()]?;
+            ensure_lt![j, vec.len()]?;
+            vec.swap(i, j);
+        },
+        EltDelta::Insert { index, item } => {
+            ensure_le![index, vec.len()]?;
+            vec.insert(index, <T>::from_delta(item)?);
+        },
+        EltDelta::Delete { index } => {
+            ensure_lt![index, vec.len()]?;
+            vec.remove(index);
+        },
+        EltDelta::DeleteRange { index, count } => {
+            ensure_le![index, vec.len()]?;
+            ensure_le![count, vec.len() - index]?;
+            vec.drain(index .. index + count);
+        },
+        EltDelta::Prepend(items) => {
+            let items: Vec<T> = items.into_iter()
+                .map(<T>::from_delta)
+                .collect::<DeltaResult<_>>()?;
+            vec.splice(0 .. 0, items);
+        },
+        EltDelta::SetAll(items) => {
+            *vec = items.into_iter()
+                .map(<T>::from_delta)
+                .collect::<DeltaResult<_>>()?;
+        },
+        EltDelta::Clear => vec.clear(),
+    }
+    Ok(())
+}
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Apply only the first `n` operations of `self` to `base`, and return
+    /// the intermediate result.  This is useful for e.g. bisecting which
+    /// operation of a delta produces an unexpected state.  If `n` exceeds
+    /// `self.len()`, all of `self` is applied.
+    pub fn apply_prefix(&self, base: &[T], n: usize) -> DeltaResult<Vec<T>> {
+        let mut vec: Vec<T> = base.to_vec();
+        for change in self.iter().take(n) {
+            apply_elt_delta(&mut vec, change.clone())?;
+        }
+        Ok(vec)
+    }
+}
+
+impl<T> Delta for Vec<T>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        delta_with_config(self, rhs, &DeltaConfig::default())
+    }
+}
+
+/// The algorithm used to calculate a `VecDelta`.
+///
+/// Both strategies are deterministic, including for `Vec`s that contain
+/// duplicate values: equal values are matched greedily from left to right,
+/// so e.g. `[a, a, a] -> [a, a]` removes the last `a`.  When the Myers
+/// strategy can choose between a deletion and an insertion of equal cost,
+/// it deletes first, so `[a, b, a] -> [a, a, b]` deletes `b` at index 1
+/// and then inserts `b` at index 2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VecStrategy {
+    /// Compare the elements of both `Vec`s index by index.  This is cheap
+    /// to calculate, but an insertion or removal near the front of a `Vec`
+    /// produces an `EltDelta::Edit` for every element that follows it.
+    Positional,
+    /// Calculate a minimal edit script of `EltDelta::Insert`s and
+    /// `EltDelta::Delete`s using Myers' `O(ND)` difference algorithm.
+    Myers,
+    /// Probe the common prefix and suffix of both `Vec`s to select one
+    /// of the above, or a single append, prepend or truncation, for each
+    /// delta.  See `select_strategy()`.
+    Auto,
+}
+
+/// Calculate `lhs --[delta]--> rhs` using the strategy in `config`.
+pub fn delta_with_config<T>(
+    lhs: &[T],
+    rhs: &[T],
+    config: &DeltaConfig,
+) -> DeltaResult<VecDelta<T>>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    delta_with_strategy(lhs, rhs, config).map(|(delta, _)| delta)
+}
+
+/// Calculate `lhs --[delta]--> rhs` using the strategy in `config`, and
+/// return the strategy that was used for it.  For `VecStrategy::Auto`,
+/// this is the result of the single call to `select_strategy()`.
+pub(crate) fn delta_with_strategy<T>(
+    lhs: &[T],
+    rhs: &[T],
+    config: &DeltaConfig,
+) -> DeltaResult<(VecDelta<T>, SelectedStrategy)>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    let selected: SelectedStrategy = match config.vec_strategy {
+        VecStrategy::Positional => SelectedStrategy::Positional,
+        VecStrategy::Myers => SelectedStrategy::Myers,
+        VecStrategy::Auto => select_strategy(lhs, rhs),
+    };
+    // NOTE: Transitions from and to an empty `Vec` are a single operation,
+    //       regardless of the strategy:
+    if lhs.is_empty() && !rhs.is_empty() {
+        let items = rhs.iter()
+            .map(|r| r.clone().into_delta())
+            .collect::<DeltaResult<_>>()?;
+        return Ok((VecDelta(vec![EltDelta::SetAll(items)]), selected));
+    } else if !lhs.is_empty() && rhs.is_empty() {
+        return Ok((VecDelta(vec![EltDelta::Clear]), selected));
+    }
+    let max_ops: usize = config.max_ops.unwrap_or(usize::MAX);
+    let changes: Option<Vec<EltDelta<T>>> =
+        adaptive::delta_selected(lhs, rhs, selected, max_ops)?;
+    let delta = VecDelta(match changes {
+        Some(changes) => changes,
+        None => {
+            // NOTE: The diff exceeded `max_ops`, so replace `lhs` entirely:
+            let items = rhs.iter()
+                .map(|r| r.clone().into_delta())
+                .collect::<DeltaResult<_>>()?;
+            vec![EltDelta::SetAll(items)]
+        },
+    });
+    Ok((delta, selected))
+}
+
+/// Calculate `lhs --[delta]--> rhs`, treating both `lhs` and `rhs` as
+/// unordered multisets rather than as sequences.  The resulting delta
+/// deletes every value in `lhs` that doesn't occur (often enough) in `rhs`
+/// and appends every value in `rhs` that doesn't occur (often enough) in
+/// `lhs`.  Thus applying it to `lhs` produces a `Vec` with the same values
+/// as `rhs`, though not necessarily in the same order.  In particular,
+/// the delta between a `Vec` and a reordering of it is empty.
+pub fn delta_as_set<T>(lhs: &[T], rhs: &[T]) -> DeltaResult<VecDelta<T>>
+where T: Clone + Debug + Ord + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    // NOTE: `unmatched` counts the occurrences of each value in `rhs`
+    //       that have not been matched to an occurrence in `lhs` yet:
+    let mut unmatched: BTreeMap<&T, usize> = BTreeMap::new();
+    for r in rhs { *unmatched.entry(r).or_insert(0) += 1; }
+    let mut changes: Vec<EltDelta<T>> = vec![];
+    let mut deleted = 0;
+    for (index, l) in lhs.iter().enumerate() { match unmatched.get_mut(l) {
+        Some(count) if *count > 0 => *count -= 1,
+        _ => {
+            changes.push(EltDelta::Delete { index: index - deleted });
+            deleted += 1;
+        },
+    }}
+    for r in rhs { match unmatched.get_mut(r) {
+        Some(count) if *count > 0 => {
+            *count -= 1;
+            changes.push(EltDelta::Add(r.clone().into_delta()?));
+        },
+        _ => {/*NOP*/},
+    }}
+    Ok(VecDelta(changes))
+}
+
+/// Calculate an edit script transforming `lhs` into `rhs` like
+/// `VecStrategy::Myers` does, but with elements compared by `eq` rather
+/// than by `PartialEq`, e.g. to ignore whitespace in lines of text like
+/// `diff -w`.  Elements that `eq` considers equal produce no operations,
+/// so applying the delta to `lhs` keeps their values from `lhs`.
+pub fn delta_with_elem_eq<T, F>(lhs: &[T], rhs: &[T], eq: F) -> DeltaResult<VecDelta<T>>
+where T: Clone + Debug + PartialEq + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      F: Fn(&T, &T) -> bool,
+{
+    let ops: Vec<DiffOp> = myers::diff(lhs.len(), rhs.len(), |i, j| eq(&lhs[i], &rhs[j]));
+    Ok(VecDelta(edit_script(rhs, ops)?))
+}
+
+fn delta_positional<T>(lhs: &[T], rhs: &[T]) -> DeltaResult<Vec<EltDelta<T>>>
+where T: Clone + PartialEq + Delta + IntoDelta {
+    // NOTE: Fast path for when values were only prepended to `lhs`:
+    if !lhs.is_empty() && rhs.len() > lhs.len() && rhs.ends_with(lhs) {
+        let prepended = &rhs[.. rhs.len() - lhs.len()];
+        let items = prepended.iter()
+            .map(|r| r.clone().into_delta())
+            .collect::<DeltaResult<_>>()?;
+        return Ok(vec![EltDelta::Prepend(items)]);
+    }
+    let mut changes: Vec<EltDelta<T>> = vec![];
+    push_positional(
+        &mut changes,
+        0 .. usize::max(lhs.len(), rhs.len()),
+        (lhs.len(), |index| Ok(Cow::Borrowed(&lhs[index]))),
+        (rhs.len(), |index| Ok(Cow::Borrowed(&rhs[index]))),
+    )?;
+    compact_swap(
+        &mut changes,
+        |index| Ok(Cow::Borrowed(&lhs[index])),
+        |index| Ok(Cow::Borrowed(&rhs[index])),
+    )?;
+    Ok(changes)
+}
+
+/// Push the positional changes at `indices` onto `changes`: an `Edit` for
+/// each index at which the values of `lhs` and `rhs` differ, an `Add` for
+/// each index past the end of `lhs`, and a `Remove` that counts the indices
+/// past the end of `rhs`.  Each side is given as its length and a closure
+/// reading the value at an index, which is only called where needed, so
+/// that slices, lazy sources and values pushed one at a time all produce
+/// the same changes.
+fn push_positional<'t, T, L, R>(
+    changes: &mut Vec<EltDelta<T>>,
+    indices: Range<usize>,
+    (lhs_len, mut lhs_at): (usize, L),
+    (rhs_len, mut rhs_at): (usize, R),
+) -> DeltaResult<()>
+where T: Clone + PartialEq + Delta + IntoDelta + 't,
+      L: FnMut(usize) -> DeltaResult<Cow<'t, T>>,
+      R: FnMut(usize) -> DeltaResult<Cow<'t, T>>,
+{
+    for index in indices { match (index < lhs_len, index < rhs_len) {
+        (false, false) => return bug_detected!(),
+        (true, true) => {
+            let (l, r): (Cow<T>, Cow<T>) = (lhs_at(index)?, rhs_at(index)?);
+            if l != r {
+                changes.push(EltDelta::Edit { index, item: l.delta(&r)? });
+            }
+        },
+        (false, true) =>
+            changes.push(EltDelta::Add(rhs_at(index)?.into_owned().into_delta()?)),
+        (true, false) => match changes.last_mut() {
+            Some(EltDelta::Remove { ref mut count }) => *count += 1,
+            _ => changes.push(EltDelta::Remove { count: 1 }),
+        },
+    }}
+    Ok(())
+}
+
+/// Calculate a minimal edit script transforming `lhs` into `rhs`,
+/// or `None` if it would consist of more than `max_ops` operations.
+fn delta_myers<T>(
+    lhs: &[T],
+    rhs: &[T],
+    max_ops: usize,
+) -> DeltaResult<Option<Vec<EltDelta<T>>>>
+where T: Clone + PartialEq + Core + IntoDelta {
+    // NOTE: Every inserted element is an operation of its own, while
+    //       deleted elements may be coalesced into a single one.  Since an
+    //       edit distance `d` inserts `(d + rhs.len() - lhs.len()) / 2`
+    //       elements, larger distances than this can't fit in `max_ops`:
+    let max_distance: usize = max_ops.saturating_mul(2)
+        .saturating_add(lhs.len())
+        .saturating_sub(rhs.len());
+    let eq = |i: usize, j: usize| lhs[i] == rhs[j];
+    match myers::diff_bounded(lhs.len(), rhs.len(), max_distance, eq) {
+        Some(ops) => Ok(Some(edit_script(rhs, ops)?)
+            .filter(|changes| changes.len() <= max_ops)),
+        None => Ok(None),
+    }
+}
+
+/// Convert a sequence of `DiffOp`s that transforms some `lhs` into `rhs`
+/// to a sequence of `EltDelta::Insert`s, `EltDelta::Delete`s and
+/// `EltDelta::DeleteRange`s.
+fn edit_script<T, I>(rhs: &[T], ops: I) -> DeltaResult<Vec<EltDelta<T>>>
+where T: Clone + Core + IntoDelta,
+      I: IntoIterator<Item = DiffOp> {
+    let mut changes: Vec<EltDelta<T>> = vec![];
+    // NOTE: `index` tracks the position in the `Vec` as it looks
+    //       after all previous changes have been applied to it:
+    let mut index = 0;
+    for op in ops {
+        match op {
+            DiffOp::Equal { .. } => index += 1,
+            // NOTE: A run of adjacent deletions all delete at the same
+            //       index, and is coalesced into a single operation:
+            DiffOp::Delete { .. } => match changes.last_mut() {
+                Some(last) if matches!(*last, EltDelta::Delete { index: i } if i == index) =>
+                    *last = EltDelta::DeleteRange { index, count: 2 },
+                Some(EltDelta::DeleteRange { index: i, count }) if *i == index =>
+                    *count += 1,
+                _ => changes.push(EltDelta::Delete { index }),
+            },
+            DiffOp::Insert { new } => {
+                let item = rhs[new].clone().into_delta()?;
+                changes.push(EltDelta::Insert { index, item });
+                index += 1;
+            },
+        }
+    }
+    Ok(changes)
+}
+
+/// If exactly 2 elements at indices `i` and `j` were edited, and they
+/// exchanged positions, replace both edits with a single `EltDelta::Swap`.
+/// As for `push_positional()`, each side is given as a closure reading the
+/// value at an index, which is only called for the 2 edited indices.
+fn compact_swap<'t, T, L, R>(
+    changes: &mut Vec<EltDelta<T>>,
+    mut lhs_at: L,
+    mut rhs_at: R,
+) -> DeltaResult<()>
+where T: Clone + PartialEq + Core + 't,
+      L: FnMut(usize) -> DeltaResult<Cow<'t, T>>,
+      R: FnMut(usize) -> DeltaResult<Cow<'t, T>>,
+{
+    let edited: Vec<(usize, usize)> = changes.iter()
+        .enumerate()
+        .filter_map(|(pos, change)| match change {
+            EltDelta::Edit { index, .. } => Some((pos, *index)),
+            _ => None,
+        })
+        .collect();
+    if let [(pos_i, i), (pos_j, j)] = edited[..] {
+        if lhs_at(i)? == rhs_at(j)? && lhs_at(j)? == rhs_at(i)? {
+            changes.remove(pos_j);
+            changes[pos_i] = EltDelta::Swap { i, j };
+        }
+    }
+    Ok(())
+}
+
+impl<T> FromDelta for Vec<T>
+where T: Clone + Debug + PartialEq + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn from_delta(delta: <Self as Core>::Delta) -> DeltaResult<Self> {
+        let mut vec: Vec<T> = vec![];
+        for (index, element) in delta.0.into_iter().enumerate() {
+            match element {
+                EltDelta::Add(elt) => vec.push(<T>::from_delta(elt)?),
+                EltDelta::SetAll(items) => {
+                    vec = items.into_iter()
+                        .map(<T>::from_delta)
+                        .collect::<DeltaResult<_>>()?;
+                },
+                EltDelta::Clear => vec.clear(),
+                _ => return Err(DeltaError::IllegalDelta { index })?,
+            }
+        }
+        Ok(vec)
+    }
+}
+
+impl<T> IntoDelta for Vec<T>
+where T: Clone + Debug + PartialEq + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn into_delta(self) -> DeltaResult<<Self as Core>::Delta> {
+        let mut changes: Vec<EltDelta<T>> = vec![];
+        for elt in self {
+            changes.push(EltDelta::Add(elt.into_delta()?));
+        }
+        Ok(VecDelta(changes))
+    }
+}
+
+impl<T> EmptyDelta for Vec<T>
+where T: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn identity_delta() -> Self::Delta {
+        VecDelta(vec![])
+    }
+}
+
+
+
+/// A single operation in a `VecDelta`.  The operations in a `VecDelta`
+/// are applied in order, and every index refers to a position in the
+/// `Vec` as it looks *after* all preceding operations have been applied.
+/// So e.g. an `Edit { index: 2, .. }` that follows an `Insert { index: 0, .. }`
+/// edits the value that was at index 1 before the insertion.
+#[derive(Clone, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub enum EltDelta<T: Core> {
+    /// Edit a value at a given `index`.
+    Edit {
+        /// The location of the edit
+        index: usize,
+        /// The new value of the item
+        item: <T as Core>::Delta,
+    },
+    /// Remove `count` elements from the end of the Vec.
+    Remove { count: usize },
+    /// Add a value.
+    Add(<T as Core>::Delta),
+    /// Swap the values at indices `i` and `j`.
+    Swap { i: usize, j: usize },
+    /// Insert a value at a given `index`, shifting all
+    /// values after it to the right.
+    Insert {
+        /// The location of the insertion
+        index: usize,
+        /// The inserted item
+        item: <T as Core>::Delta,
+    },
+    /// Remove the value at a given `index`, shifting all
+    /// values after it to the left.
+    Delete { index: usize },
+    /// Remove `count` values starting at a given `index`,
+    /// shifting all values after them to the left.
+    DeleteRange { index: usize, count: usize },
+    /// Prepend values to the front of the Vec, in order.
+    Prepend(Vec<<T as Core>::Delta>),
+    /// Replace all values in the Vec with the given values, in order.
+    SetAll(Vec<<T as Core>::Delta>),
+    /// Remove all values from the Vec.
+    Clear,
+}
+
+impl<T: Core> std::fmt::Debug for EltDelta<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match &self {
+            Self::Edit { index, item } => f.debug_struct("Edit")
+                .field("index", index)
+                .field("item", item)
+                .finish(),
+            Self::Remove { count } => f.debug_struct("Remove")
+                .field("count", count)
+                .finish(),
+            Self::Add(delta) => write!(f, "Add({:#?})", delta),
+            Self::Swap { i, j } => f.debug_struct("Swap")
+                .field("i", i)
+                .field("j", j)
+                .finish(),
+            Self::Insert { index, item } => f.debug_struct("Insert")
+                .field("index", index)
+                .field("item", item)
+                .finish(),
+            Self::Delete { index } => f.debug_struct("Delete")
+                .field("index", index)
+                .finish(),
+            Self::DeleteRange { index, count } => f.debug_struct("DeleteRange")
+                .field("index", index)
+                .field("count", count)
+                .finish(),
+            Self::Prepend(items) => write!(f, "Prepend({:#?})", items),
+            Self::SetAll(items) => write!(f, "SetAll({:#?})", items),
+            Self::Clear => write!(f, "Clear"),
+        }
+    }
+}
+
+
+#[derive(Clone, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct VecDelta<T: Core>(#[doc(hidden)] pub Vec<EltDelta<T>>);
+
+impl<T: Core> VecDelta<T> {
+    #[inline(always)]
+    pub fn iter<'d>(&'d self) -> impl Iterator<Item = &EltDelta<T>> + 'd {
+        self.0.iter()
+    }
+
+    #[inline(always)]
+    pub fn into_iter<'d>(self) -> impl Iterator<Item = EltDelta<T>> + 'd
+    where Self: 'd {
+        self.0.into_iter()
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.0.len() }
+
+    /// Return the number of element-level edits encoded in `self`.
+    /// Unlike `self.len()`, this counts every element removed by a
+    /// single `EltDelta::Remove { count }` as a separate edit.
+    pub fn edit_distance(&self) -> usize {
+        self.iter().map(|change| match change {
+            EltDelta::Edit { .. } => 1,
+            EltDelta::Add(_) => 1,
+            EltDelta::Remove { count } => *count,
+            EltDelta::Swap { .. } => 1,
+            EltDelta::Insert { .. } => 1,
+            EltDelta::Delete { .. } => 1,
+            EltDelta::DeleteRange { count, .. } => *count,
+            EltDelta::Prepend(items) => items.len(),
+            EltDelta::SetAll(items) => items.len(),
+            EltDelta::Clear => 1,
+        }).sum()
+    }
+
+    /// Return an estimate of the number of bytes that `self` takes up
+    /// when serialized with a compact binary format, e.g. to decide
+    /// whether storing `self` is cheaper than storing the whole `Vec`.
+    /// Each operation is counted as a tag plus its indices, counts and
+    /// items, where each item is estimated by its in-memory size.  Heap
+    /// data owned by the items themselves is not taken into account.
+    pub fn estimated_serialized_size(&self) -> usize {
+        use std::mem::size_of;
+        const TAG: usize = size_of::<u32>();
+        const INDEX: usize = size_of::<u64>();
+        let item: usize = size_of::<<T as Core>::Delta>();
+        self.iter().map(|change| TAG + match change {
+            EltDelta::Edit { .. } => INDEX + item,
+            EltDelta::Remove { .. } => INDEX,
+            EltDelta::Add(_) => item,
+            EltDelta::Swap { .. } => 2 * INDEX,
+            EltDelta::Insert { .. } => INDEX + item,
+            EltDelta::Delete { .. } => INDEX,
+            EltDelta::DeleteRange { .. } => 2 * INDEX,
+            EltDelta::Prepend(items) => INDEX + items.len() * item,
+            EltDelta::SetAll(items) => INDEX + items.len() * item,
+            EltDelta::Clear => 0,
+        }).sum::<usize>() + INDEX
+    }
+
+    /// Return true iff. every operation in `self` appends a value to the
+    /// tail of the Vec, i.e. applying `self` leaves all existing values
+    /// in place.  This is trivially true for an empty delta.
+    ///
+    /// Note that the delta from an empty `Vec` to a non-empty one is a
+    /// single `EltDelta::SetAll`, which replaces all values of whichever
+    /// `Vec` it's applied to.  Since that needn't be an empty `Vec`, such
+    /// a delta is *not* append-only.
+    pub fn is_append_only(&self) -> bool {
+        self.iter().all(|change| matches!(change, EltDelta::Add(_)))
+    }
+
+    /// Return a human-readable description of each operation in `self`,
+    /// in order.  Values are described by their (possibly shortened)
+    /// `Debug` representation.
+    pub fn explain(&self) -> Vec<String> {
+        fn describe<D: Debug>(item: &D) -> String {
+            const MAX_LEN: usize = 40;
+            let desc: String = format!("{:?}", item);
+            if desc.chars().count() <= MAX_LEN { return desc }
+            let mut desc: String = desc.chars().take(MAX_LEN - 3).collect();
+            desc.push_str("...");
+            desc
+        }
+        self.iter().map(|change| match change {
+            EltDelta::Edit { index, item } =>
+                format!("edit [{}] to {}", index, describe(item)),
+            EltDelta::Remove { count } =>
+                format!("remove {} from the end", count),
+            EltDelta::Add(item) =>
+                format!("push {}", describe(item)),
+            EltDelta::Swap { i, j } =>
+                format!("swap [{}] and [{}]", i, j),
+            EltDelta::Insert { index, item } =>
+                format!("insert {} at [{}]", describe(item), index),
+            EltDelta::Delete { index } =>
+                format!("delete [{}]", index),
+            EltDelta::DeleteRange { index, count } =>
+                format!("delete [{}..{}]", index, index + count),
+            EltDelta::Prepend(items) =>
+                format!("prepend {} value(s): {}", items.len(), describe(items)),
+            EltDelta::SetAll(items) =>
+                format!("set all {} value(s): {}", items.len(), describe(items)),
+            EltDelta::Clear =>
+                "clear".to_string(),
+        }).collect()
+    }
+}
+
+impl<T: Core> DeltaOps for VecDelta<T> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T: Core + Clone> Compose for VecDelta<T> {
+    /// Since the changes of a `VecDelta` are applied one after another,
+    /// the changes of `next` can simply follow those of `self`.
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(Self(self.0.iter().chain(next.0.iter()).cloned().collect()))
+    }
+}
+
+impl<T> Invert<Vec<T>> for VecDelta<T>
+where T: Core,
+      Vec<T>: Apply + Delta + Core<Delta = Self>,
+      Self: Clone,
+{
+    fn invert(&self, base: &Vec<T>) -> DeltaResult<Self> {
+        base.apply(self.clone())?.delta(base)
+    }
+}
+
+impl<T: Core> std::fmt::Debug for VecDelta<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "VecDelta ")?;
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta__same_values() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![1, 3, 10, 30];
+        let vec1: Vec<i32> = vec![1, 3, 10, 30];
+        assert_eq!(vec0, vec1);
+
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta, VecDelta(vec![]));
+        let vec2 = vec0.apply(delta)?;
+        assert_eq!(vec1, vec2);
+
+        let delta = vec1.delta(&vec0)?;
+        assert_eq!(delta, VecDelta(vec![]));
+        let vec3 = vec1.apply(delta)?;
+        assert_eq!(vec0, vec3);
+
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta__different_values__same_length() -> DeltaResult<()> {
+        let vec0 = vec![1, 3, 10, 49, 30, 500];
+        let vec1 = vec![1, 3, 10, 30, 500, 49];
+
+        let delta0 = vec0.delta(&vec1)?;
+        assert_eq!(delta0, VecDelta(vec![
+            EltDelta::Edit { index: 3, item:  30i32.into_delta()?, },
+            EltDelta::Edit { index: 4, item: 500i32.into_delta()?, },
+            EltDelta::Edit { index: 5, item:  49i32.into_delta()?, },
+        ]));
+        let vec2 = vec0.apply(delta0)?;
+        assert_eq!(vec1, vec2);
+
+        let delta = vec1.delta(&vec0)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::Edit { index: 3, item:  49i32.into_delta()?, },
+            EltDelta::Edit { index: 4, item:  30i32.into_delta()?, },
+            EltDelta::Edit { index: 5, item: 500i32.into_delta()?, },
+        ]));
+        let vec3 = vec1.apply(delta)?;
+        assert_eq!(vec0, vec3);
+
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta__different_values__different_length() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![1, 3, 10, 30];
+        let vec1: Vec<i32> = vec![1, 3, 10, 49, 30, 500];
+
+        let delta0 = vec0.delta(&vec1)?;
+        assert_eq!(delta0, VecDelta(vec![
+            EltDelta::Edit { index: 3, item:  49.into_delta()?, },
+            EltDelta::Add(30.into_delta()?),
+            EltDelta::Add(500.into_delta()?),
+        ]));
+        let vec2 = vec0.apply(delta0)?;
+        assert_eq!(vec1, vec2);
+
+        let delta = vec1.delta(&vec0)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::Edit { index: 3, item: 30.into_delta()?, },
+            EltDelta::Remove  { count: 2, },
+        ]));
+        let vec3 = vec1.apply(delta)?;
+        assert_eq!(vec0, vec3);
+
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__apply__different_values__same_length() -> DeltaResult<()> {
+        let vec0 = vec![1, 3, 10, 30, 30];
+        let vec1 = vec![1, 3, 10, 30, 40];
+        let delta = vec0.delta(&vec1)?;
+        let vec2 = vec0.apply(delta)?;
+        assert_eq!(vec1, vec2);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__apply__different_values__different_length() -> DeltaResult<()> {
+        let vec0 = vec![1, 3, 10, 30, 30];
+        let vec1 = vec![1, 3, 10, 30, 30, 40];
+        let delta = vec0.delta(&vec1)?;
+        let vec2 = vec0.apply(delta)?;
+        assert_eq!(vec1, vec2);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDelta__edit_distance() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![1, 3, 10, 30, 40, 50];
+        let vec1: Vec<i32> = vec![1, 4, 10, 30];
+        // NOTE: 1 edit at index 1, and 2 elements removed from the end:
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta.len(), 2);
+        assert_eq!(delta.edit_distance(), 3);
+
+        let delta = vec1.delta(&vec0)?;
+        assert_eq!(delta.edit_distance(), 3);
+
+        let delta = vec0.delta(&vec0)?;
+        assert_eq!(delta.edit_distance(), 0);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDelta__estimated_serialized_size() -> DeltaResult<()> {
+        let vec0: Vec<u64> = (0 .. 10_000).collect();
+        let mut vec1: Vec<u64> = vec0.clone();
+        vec1[5000] = 0;
+        let small = vec0.delta(&vec1)?;
+        assert_eq!(small.len(), 1);
+        let full: VecDelta<u64> = Vec::<u64>::new().delta(&vec1)?;
+        assert!(matches!(full.0.as_slice(), [EltDelta::SetAll(_)]));
+        assert!(
+            small.estimated_serialized_size() * 1000 < full.estimated_serialized_size(),
+            "{} vs {}",
+            small.estimated_serialized_size(),
+            full.estimated_serialized_size(),
+        );
+        assert!(full.estimated_serialized_size() >= 10_000 * 8);
+        assert!(VecDelta::<u64>(vec![]).estimated_serialized_size() < 16);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__apply__indices_after_prior_ops() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![10, 20, 30];
+        let delta: VecDelta<i32> = VecDelta(vec![
+            EltDelta::Insert { index: 0, item: 5i32.into_delta()? },
+            // NOTE: Index 2 refers to the value 20, which was
+            //       shifted to the right by the insertion:
+            EltDelta::Edit { index: 2, item: 25i32.into_delta()? },
+            EltDelta::Delete { index: 1 },
+            EltDelta::Edit { index: 2, item: 35i32.into_delta()? },
+        ]);
+        let vec1 = vec0.apply(delta)?;
+        assert_eq!(vec1, vec![5, 25, 35]);
+
+        let delta: VecDelta<i32> = VecDelta(vec![
+            EltDelta::Remove { count: 1 },
+            EltDelta::Edit { index: 2, item: 35i32.into_delta()? },
+        ]);
+        assert!(vec0.apply(delta).is_err());
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDelta__apply_prefix() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![10, 20, 30];
+        let changes: Vec<EltDelta<i32>> = vec![
+            EltDelta::Add(40i32.into_delta()?),
+            EltDelta::Edit { index: 0, item: 15i32.into_delta()? },
+            EltDelta::Swap { i: 1, j: 3 },
+            EltDelta::Delete { index: 2 },
+            EltDelta::Insert { index: 0, item: 5i32.into_delta()? },
+        ];
+        let delta: VecDelta<i32> = VecDelta(changes.clone());
+        let prefix: VecDelta<i32> = VecDelta(changes[.. 3].to_vec());
+        assert_eq!(delta.apply_prefix(&vec0, 3)?, vec0.apply(prefix)?);
+        assert_eq!(delta.apply_prefix(&vec0, 3)?, vec![15, 40, 30, 20]);
+        assert_eq!(delta.apply_prefix(&vec0, 0)?, vec0);
+        assert_eq!(delta.apply_prefix(&vec0, 9)?, vec0.apply(delta.clone())?);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta_as_set() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![1, 3, 10, 3, 30];
+        let vec1: Vec<i32> = vec![30, 3, 1, 10, 3];
+        let delta = delta_as_set(&vec0, &vec1)?;
+        assert_eq!(delta, VecDelta(vec![]));
+        assert_eq!(vec0.apply(delta)?, vec0);
+
+        let vec2: Vec<i32> = vec![3, 40, 1, 40, 10];
+        let delta = delta_as_set(&vec0, &vec2)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::Delete { index: 3 },
+            EltDelta::Delete { index: 3 },
+            EltDelta::Add(40i32.into_delta()?),
+            EltDelta::Add(40i32.into_delta()?),
+        ]));
+        let mut vec3 = vec0.apply(delta)?;
+        vec3.sort();
+        let mut expected = vec2.clone();
+        expected.sort();
+        assert_eq!(vec3, expected);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDelta__explain() -> DeltaResult<()> {
+        let delta: VecDelta<String> = VecDelta(vec![
+            EltDelta::Insert { index: 0, item: "foo".to_string().into_delta()? },
+            EltDelta::Edit { index: 2, item: "bar".to_string().into_delta()? },
+            EltDelta::Delete { index: 1 },
+            EltDelta::Swap { i: 0, j: 1 },
+            EltDelta::Add("x".repeat(50).into_delta()?),
+            EltDelta::Remove { count: 2 },
+        ]);
+        assert_eq!(delta.explain(), vec![
+            r#"insert StringDelta("foo") at [0]"#.to_string(),
+            r#"edit [2] to StringDelta("bar")"#.to_string(),
+            "delete [1]".to_string(),
+            "swap [0] and [1]".to_string(),
+            r#"push StringDelta("xxxxxxxxxxxxxxxxxxxxxxxx..."#.to_string(),
+            "remove 2 from the end".to_string(),
+        ]);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDelta__is_append_only() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![1, 3, 10];
+        let vec1: Vec<i32> = vec![1, 3, 10, 30, 40];
+        let delta = vec0.delta(&vec1)?;
+        assert!(delta.is_append_only());
+
+        let vec2: Vec<i32> = vec![1, 4, 10, 30, 40];
+        let delta = vec0.delta(&vec2)?;
+        assert!(!delta.is_append_only());
+
+        // NOTE: Applying this `SetAll` to `vec0` would replace its values:
+        let delta = Vec::<i32>::new().delta(&vec![1])?;
+        assert_eq!(delta, VecDelta(vec![EltDelta::SetAll(vec![1.into_delta()?])]));
+        assert!(!delta.is_append_only());
+        assert_eq!(vec0.apply(delta)?, vec![1]);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta_with_elem_eq() -> DeltaResult<()> {
+        let lines = |text: &str| -> Vec<String> { text.split('\n').map(String::from).collect() };
+        let trimmed_eq = |l: &String, r: &String| l.trim() == r.trim();
+        let vec0: Vec<String> = lines("fn main() {\n    foo();\n}");
+        let vec1: Vec<String> = lines("fn main() {  \n    foo();\t\n}");
+        let delta = delta_with_elem_eq(&vec0, &vec1, trimmed_eq)?;
+        assert_eq!(delta, VecDelta(vec![]));
+        assert_eq!(vec0.apply(delta)?, vec0);
+
+        let vec2: Vec<String> = lines("fn main() {  \n    bar();\n}");
+        let delta = delta_with_elem_eq(&vec0, &vec2, trimmed_eq)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::Delete { index: 1 },
+            EltDelta::Insert { index: 1, item: "    bar();".to_string().into_delta()? },
+        ]));
+        assert_eq!(vec0.apply(delta)?, lines("fn main() {\n    bar();\n}"));
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta_with_config__myers__shifted_elements() -> DeltaResult<()> {
+        let config = DeltaConfig {
+            vec_strategy: VecStrategy::Myers,
+            ..Default::default()
+        };
+        let vec0: Vec<String> = (0 .. 5000).map(|n| format!("line {}", n)).collect();
+        // NOTE: Inserting or removing a single element anywhere shifts
+        //       all elements after it, but costs a single operation:
+        for index in [0, 2500, 4999] {
+            let mut vec1: Vec<String> = vec0.clone();
+            vec1.insert(index, "new line".into());
+            let delta = delta_with_config(&vec0, &vec1, &config)?;
+            assert_eq!(delta.len(), 1);
+            assert_eq!(vec0.apply(delta)?, vec1);
+            let mut vec2: Vec<String> = vec0.clone();
+            vec2.remove(index);
+            let delta = delta_with_config(&vec0, &vec2, &config)?;
+            assert_eq!(delta.len(), 1);
+            assert_eq!(vec0.apply(delta)?, vec2);
+        }
+        // NOTE: Whereas the positional diff edits each shifted element:
+        let mut vec1: Vec<String> = vec0.clone();
+        vec1.insert(2500, "new line".into());
+        assert_eq!(vec0.delta(&vec1)?.len(), 2501);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta_with_config__myers() -> DeltaResult<()> {
+        let config = DeltaConfig {
+            vec_strategy: VecStrategy::Myers,
+            ..Default::default()
+        };
+        let vec0: Vec<char> = "abcabba".chars().collect();
+        let vec1: Vec<char> = "cbabac".chars().collect();
+        // NOTE: The shortest edit script consists of 5 edits:
+        let delta = delta_with_config(&vec0, &vec1, &config)?;
+        assert_eq!(delta.edit_distance(), 5);
+        let vec2 = vec0.apply(delta)?;
+        assert_eq!(vec1, vec2);
+
+        // NOTE: The positional diff needs more edits than that:
+        let delta = delta_with_config(&vec0, &vec1, &DeltaConfig::default())?;
+        assert_eq!(delta.edit_distance(), 6);
+
+        let vec0: Vec<i32> = vec![1, 3, 10, 30];
+        let vec1: Vec<i32> = vec![0, 1, 3, 30, 500];
+        let delta = delta_with_config(&vec0, &vec1, &config)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::Insert { index: 0, item: 0.into_delta()? },
+            EltDelta::Delete { index: 3 },
+            EltDelta::Insert { index: 4, item: 500.into_delta()? },
+        ]));
+        let vec2 = vec0.apply(delta)?;
+        assert_eq!(vec1, vec2);
+
+        let empty: Vec<i32> = vec![];
+        let delta = delta_with_config(&empty, &vec1, &config)?;
+        assert_eq!(delta.edit_distance(), vec1.len());
+        assert_eq!(empty.apply(delta)?, vec1);
+        let delta = delta_with_config(&vec1, &empty, &config)?;
+        assert_eq!(delta, VecDelta(vec![EltDelta::Clear]));
+        assert_eq!(vec1.apply(delta)?, empty);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta_with_config__myers__deleted_block() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 100).collect();
+        let vec1: Vec<u32> = (0 .. 40).chain(60 .. 100).collect();
+        let config = DeltaConfig {
+            vec_strategy: VecStrategy::Myers,
+            ..Default::default()
+        };
+        let delta = delta_with_config(&vec0, &vec1, &config)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::DeleteRange { index: 40, count: 20 },
+        ]));
+        assert_eq!(delta.edit_distance(), 20);
+        assert_eq!(delta.explain(), vec!["delete [40..60]".to_string()]);
+        assert_eq!(vec0.apply(delta)?, vec1);
+
+        let delta: VecDelta<u32> = VecDelta(vec![
+            EltDelta::DeleteRange { index: 90, count: 11 },
+        ]);
+        assert!(vec0.apply(delta).is_err());
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta__from_empty() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![];
+        let vec1: Vec<i32> = vec![1, 3, 10, 30];
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta, VecDelta(vec![EltDelta::SetAll(vec![
+            1.into_delta()?,
+            3.into_delta()?,
+            10.into_delta()?,
+            30.into_delta()?,
+        ])]));
+        assert_eq!(vec0.apply(delta.clone())?, vec1);
+        assert_eq!(Vec::<i32>::from_delta(delta)?, vec1);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta__to_empty() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![1, 3, 10, 30];
+        let vec1: Vec<i32> = vec![];
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta, VecDelta(vec![EltDelta::Clear]));
+        assert_eq!(vec0.apply(delta)?, vec1);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta__max_ops() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 100).collect();
+        let vec1: Vec<u32> = (0 .. 100).map(|n| n * 7 % 101).collect();
+        for &vec_strategy in &[VecStrategy::Positional, VecStrategy::Myers] {
+            let config = DeltaConfig { vec_strategy, max_ops: Some(10) };
+            let delta = delta_with_config(&vec0, &vec1, &config)?;
+            assert!(matches!(
+                &delta.0[..],
+                [EltDelta::SetAll(items)] if items.len() == 100
+            ));
+            assert_eq!(vec0.apply(delta)?, vec1);
+            // NOTE: A diff within the budget is unaffected:
+            let mut vec2: Vec<u32> = vec0.clone();
+            vec2[50] = 1000;
+            let delta = delta_with_config(&vec0, &vec2, &config)?;
+            let unbounded = DeltaConfig { vec_strategy, max_ops: None };
+            assert_eq!(delta, delta_with_config(&vec0, &vec2, &unbounded)?);
+            assert!(delta.len() <= 10);
+            assert_eq!(vec0.apply(delta)?, vec2);
+        }
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta__max_ops__counts_operations() -> DeltaResult<()> {
+        let config = DeltaConfig { vec_strategy: VecStrategy::Myers, max_ops: Some(10) };
+        // NOTE: An edit distance of 20 within a single operation:
+        let vec0: Vec<u32> = (0 .. 100).collect();
+        let vec1: Vec<u32> = (0 .. 40).chain(60 .. 100).collect();
+        let delta = delta_with_config(&vec0, &vec1, &config)?;
+        assert_eq!(delta, VecDelta(vec![EltDelta::DeleteRange { index: 40, count: 20 }]));
+        // NOTE: 11 inserted elements are 11 operations:
+        let vec2: Vec<u32> = (0 .. 40).chain(1000 .. 1011).chain(40 .. 100).collect();
+        let delta = delta_with_config(&vec0, &vec2, &config)?;
+        assert!(matches!(&delta.0[..], [EltDelta::SetAll(_)]));
+        assert_eq!(vec0.apply(delta)?, vec2);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta__prepended_values() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 1000).collect();
+        let vec1: Vec<u32> = (1000 .. 1005).chain(0 .. 1000).collect();
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta, VecDelta(vec![EltDelta::Prepend(vec![
+            1000u32.into_delta()?,
+            1001u32.into_delta()?,
+            1002u32.into_delta()?,
+            1003u32.into_delta()?,
+            1004u32.into_delta()?,
+        ])]));
+        assert_eq!(delta.edit_distance(), 5);
+        let vec2 = vec0.apply(delta)?;
+        assert_eq!(vec1, vec2);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta__duplicate_values() -> DeltaResult<()> {
+        let positional = DeltaConfig::default();
+        let myers = DeltaConfig {
+            vec_strategy: VecStrategy::Myers,
+            ..Default::default()
+        };
+
+        let vec0: Vec<char> = vec!['a', 'a', 'a'];
+        let vec1: Vec<char> = vec!['a', 'a'];
+        let delta = delta_with_config(&vec0, &vec1, &positional)?;
+        assert_eq!(delta, VecDelta(vec![EltDelta::Remove { count: 1 }]));
+        assert_eq!(vec0.apply(delta)?, vec1);
+        let delta = delta_with_config(&vec0, &vec1, &myers)?;
+        assert_eq!(delta, VecDelta(vec![EltDelta::Delete { index: 2 }]));
+        assert_eq!(vec0.apply(delta)?, vec1);
+
+        let vec0: Vec<char> = vec!['a', 'b', 'a'];
+        let vec1: Vec<char> = vec!['a', 'a', 'b'];
+        let delta = delta_with_config(&vec0, &vec1, &positional)?;
+        assert_eq!(delta, VecDelta(vec![EltDelta::Swap { i: 1, j: 2 }]));
+        assert_eq!(vec0.apply(delta)?, vec1);
+        let delta = delta_with_config(&vec0, &vec1, &myers)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::Delete { index: 1 },
+            EltDelta::Insert { index: 2, item: 'b'.into_delta()? },
+        ]));
+        assert_eq!(vec0.apply(delta)?, vec1);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__delta__swapped_values() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![1, 3, 10, 30, 49, 500];
+        let vec1: Vec<i32> = vec![1, 49, 10, 30, 3, 500];
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta, VecDelta(vec![EltDelta::Swap { i: 1, j: 4 }]));
+        assert_eq!(delta.edit_distance(), 1);
+        let vec2 = vec0.apply(delta)?;
+        assert_eq!(vec1, vec2);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDelta__positional_paths_agree() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 40).collect();
+        let cases: Vec<Vec<u32>> = vec![
+            (0 .. 40).map(|n| if n % 3 == 0 { n * 10 } else { n }).collect(),
+            (0 .. 55).collect(),
+            (0 .. 25).map(|n| n + 1).collect(),
+            (0 .. 40).map(|n| match n { 5 => 30, 30 => 5, n => n }).collect(),
+            (100 .. 105).chain(0 .. 40).collect(),
+            vec0.clone(),
+            vec![],
+        ];
+        for vec1 in cases {
+            let delta: VecDelta<u32> = vec0.delta(&vec1)?;
+            let mut resumable = VecDelta::diff_resumable(&vec0, &vec1);
+            while resumable.step(7)?.is_pending() {}
+            assert_eq!(resumable.finish()?, delta);
+            assert_eq!(VecDelta::diff_indexed(&vec0[..], &vec1[..])?, delta);
+            // NOTE: The builder never recognizes prepended values:
+            if !delta.iter().any(|change| matches!(change, EltDelta::Prepend(_))) {
+                let mut builder = VecDelta::builder(&vec0);
+                builder.extend(&vec1)?;
+                assert_eq!(builder.finish()?, delta);
+            }
+            assert_eq!(vec0.apply(delta)?, vec1);
+        }
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDelta__is_empty() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![1, 3, 10, 30];
+        assert!(DeltaOps::is_empty(&vec0.delta(&vec0)?));
+        assert!(!DeltaOps::is_empty(&vec0.delta(&vec![1, 3, 10])?));
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDelta__identity_delta() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![1, 3, 10, 30];
+        assert_eq!(vec0.apply(Vec::<i32>::identity_delta())?, vec0);
+        assert_eq!(Vec::<i32>::new().apply(Vec::<i32>::identity_delta())?, Vec::<i32>::new());
+        assert!(DeltaOps::is_empty(&Vec::<i32>::identity_delta()));
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDelta__compose() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![1, 3, 10, 30];
+        let vec1: Vec<i32> = vec![1, 30, 10, 3, 5];
+        let vec2: Vec<i32> = vec![2, 30];
+        let delta = vec0.delta(&vec1)?.compose(&vec1.delta(&vec2)?)?;
+        assert_eq!(vec0.apply(delta)?, vec2);
+        assert_eq!(vec0.apply(vec0.delta(&vec2)?)?, vec2);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn VecDelta__invert() -> DeltaResult<()> {
+        let vec0: Vec<i32> = vec![1, 3, 10, 30];
+        let vec1: Vec<i32> = vec![1, 30, 10, 3, 5];
+        let delta = vec0.delta(&vec1)?;
+        let inverse = delta.invert(&vec0)?;
+        assert_eq!(vec0.apply(delta)?.apply(inverse)?, vec0);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__apply_mut__matches_apply() -> DeltaResult<()> {
+        let states: Vec<Vec<Vec<u32>>> = vec![
+            vec![vec![1, 2], vec![3], vec![4, 5, 6]],
+            vec![vec![1, 2], vec![3, 30], vec![4, 5, 6], vec![7]],
+            vec![vec![0], vec![1, 2], vec![4, 6]],
+            vec![],
+            vec![vec![8, 9]],
+        ];
+        let strategies = [VecStrategy::Positional, VecStrategy::Myers, VecStrategy::Auto];
+        for vec_strategy in strategies.iter().copied() {
+            let config = DeltaConfig { vec_strategy, ..Default::default() };
+            for lhs in &states { for rhs in &states {
+                let delta = delta_with_config(lhs, rhs, &config)?;
+                let mut vec0: Vec<Vec<u32>> = lhs.clone();
+                vec0.apply_mut(delta.clone())?;
+                assert_eq!(vec0, lhs.apply(delta)?);
+                assert_eq!(&vec0, rhs);
+            }}
+        }
+        Ok(())
+    }
+}
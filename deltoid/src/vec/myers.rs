@@ -0,0 +1,147 @@
+//! An implementation of Myers' `O(ND)` difference algorithm, as described
+//! in "An O(ND) Difference Algorithm and Its Variations" (E. Myers, 1986).
+//!
+//! The algorithm finds a shortest edit script i.e. one with a minimal
+//! number of element insertions and deletions, where `N` is the sum of
+//! the lengths of both sequences and `D` is the size of the edit script.
+
+/// A single step in an edit script that transforms a sequence `lhs`
+/// into a sequence `rhs`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum DiffOp {
+    /// `lhs[old]` and `rhs[new]` are equal.
+    Equal { old: usize, new: usize },
+    /// `lhs[old]` is deleted.
+    Delete { old: usize },
+    /// `rhs[new]` is inserted.
+    Insert { new: usize },
+}
+
+/// Calculate a shortest edit script transforming a sequence of length
+/// `lhs_len` into a sequence of length `rhs_len`, using `eq(i, j)` to
+/// determine whether `lhs[i]` equals `rhs[j]`.  The returned operations
+/// are ordered from the start of both sequences to their end.
+pub(crate) fn diff<F>(lhs_len: usize, rhs_len: usize, eq: F) -> Vec<DiffOp>
+where F: Fn(usize, usize) -> bool {
+    diff_bounded(lhs_len, rhs_len, lhs_len + rhs_len, eq)
+        .unwrap(/*No edit script is longer than lhs_len + rhs_len*/)
+}
+
+/// Like `diff()`, but give up and return `None` as soon as it's clear that
+/// the shortest edit script is longer than `max_d`.  Since the running time
+/// is `O(ND)`, this bounds it to `O(N * max_d)`.
+pub(crate) fn diff_bounded<F>(
+    lhs_len: usize,
+    rhs_len: usize,
+    max_d: usize,
+    eq: F,
+) -> Option<Vec<DiffOp>>
+where F: Fn(usize, usize) -> bool {
+    let (n, m) = (lhs_len as isize, rhs_len as isize);
+    let max: isize = n + m;
+    let offset = |k: isize| (k + max) as usize;
+    // NOTE: `v[offset(k)]` holds the furthest reaching x on diagonal `k`:
+    let mut v: Vec<isize> = vec![0; 2 * max as usize + 2];
+    // NOTE: `trace[d]` holds `v[offset(-d) ..= offset(d)]` as it was before
+    //       step `d`, which is all that backtracking from that step reads.
+    //       Keeping the whole of `v` instead would take `O(N * D)` memory:
+    let mut trace: Vec<Vec<isize>> = vec![];
+    'search: for d in 0 ..= max {
+        if d as usize > max_d { return None }
+        trace.push(v[offset(-d) ..= offset(d)].to_vec());
+        for k in (-d ..= d).step_by(2) {
+            let mut x = if k == -d || k != d && v[offset(k - 1)] < v[offset(k + 1)] {
+                v[offset(k + 1)]
+            } else {
+                v[offset(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && eq(x as usize, y as usize) {
+                x += 1;
+                y += 1;
+            }
+            v[offset(k)] = x;
+            if x >= n && y >= m { break 'search }
+        }
+    }
+    Some(backtrack(&trace, n, m))
+}
+
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> Vec<DiffOp> {
+    let mut ops: Vec<DiffOp> = vec![];
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let (d, k) = (d as isize, x - y);
+        // NOTE: Diagonals outside of `-d ..= d` weren't reached yet:
+        let furthest = |k: isize| if k.abs() <= d { v[(k + d) as usize] } else { 0 };
+        let prev_k = if k == -d || k != d && furthest(k - 1) < furthest(k + 1) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = furthest(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            ops.push(DiffOp::Equal { old: x as usize, new: y as usize });
+        }
+        if d > 0 {
+            if x == prev_x {
+                ops.push(DiffOp::Insert { new: prev_y as usize });
+            } else {
+                ops.push(DiffOp::Delete { old: prev_x as usize });
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    ops.reverse();
+    ops
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Replay `ops` to rebuild `rhs` from `lhs`, and count the edits.
+    fn replay(lhs: &[char], rhs: &[char], ops: &[DiffOp]) -> (Vec<char>, usize) {
+        let mut new: Vec<char> = vec![];
+        let mut edits: usize = 0;
+        for op in ops {
+            match *op {
+                DiffOp::Equal { old, .. } => new.push(lhs[old]),
+                DiffOp::Delete { .. } => edits += 1,
+                DiffOp::Insert { new: idx } => { new.push(rhs[idx]); edits += 1 },
+            }
+        }
+        (new, edits)
+    }
+
+    #[test]
+    fn diff__shortest_edit_script() {
+        for (lhs, rhs, d) in [
+            ("", "", 0),
+            ("abc", "", 3),
+            ("", "abc", 3),
+            ("abcabba", "cbabac", 5),
+            ("kitten", "sitting", 5),
+        ] {
+            let (lhs, rhs): (Vec<char>, Vec<char>) = (lhs.chars().collect(), rhs.chars().collect());
+            let ops: Vec<DiffOp> = diff(lhs.len(), rhs.len(), |i, j| lhs[i] == rhs[j]);
+            assert_eq!(replay(&lhs, &rhs, &ops), (rhs.clone(), d));
+        }
+    }
+
+    #[test]
+    fn diff__disjoint_sequences() {
+        let lhs: Vec<char> = vec!['a'; 2_000];
+        let rhs: Vec<char> = vec!['b'; 2_000];
+        let ops: Vec<DiffOp> = diff(lhs.len(), rhs.len(), |i, j| lhs[i] == rhs[j]);
+        assert_eq!(replay(&lhs, &rhs, &ops), (rhs.clone(), 4_000));
+        assert_eq!(diff_bounded(lhs.len(), rhs.len(), 100, |i, j| lhs[i] == rhs[j]), None);
+    }
+}
@@ -0,0 +1,186 @@
+//! Normalization of `VecDelta`s into a canonical order of operations.
+
+use crate::{Apply, Core, DeltaResult, FromDelta, IntoDelta};
+use crate::vec::{EltDelta, VecDelta};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+
+/// A value in a `Vec` that a run of index-based operations is applied to.
+enum Slot<T: Core> {
+    /// The value at `index` in the `Vec` before the run, along with
+    /// the edits that the run applies to it, in order.
+    Base { index: usize, edits: Vec<<T as Core>::Delta> },
+    /// A value that was inserted by the run.
+    Inserted(<T as Core>::Delta),
+}
+
+/// The net effect of a run of `EltDelta::Edit`s, `EltDelta::Insert`s,
+/// `EltDelta::Delete`s and `EltDelta::DeleteRange`s.  Only the front of
+/// the `Vec` that the run touches is modelled in `slots`, and any values
+/// past it are base values that the run leaves in place.
+struct Run<T: Core> {
+    slots: Vec<Slot<T>>,
+    /// The base index of the first value that isn't in `slots` yet.
+    next_base: usize,
+    /// The base indices of the deleted base values.
+    deleted: Vec<usize>,
+}
+
+impl<T> Run<T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn new() -> Self {
+        Self { slots: vec![], next_base: 0, deleted: vec![] }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.slots.is_empty() && self.deleted.is_empty()
+    }
+
+    /// Ensure that `slots` models at least the first `len` values.
+    fn reserve(&mut self, len: usize) {
+        while self.slots.len() < len {
+            self.slots.push(Slot::Base { index: self.next_base, edits: vec![] });
+            self.next_base += 1;
+        }
+    }
+
+    /// Add `change` to the run, or return it if it isn't index-based.
+    fn push(&mut self, change: EltDelta<T>) -> DeltaResult<Option<EltDelta<T>>> {
+        match change {
+            EltDelta::Edit { index, item } => {
+                self.reserve(index + 1);
+                match &mut self.slots[index] {
+                    Slot::Base { edits, .. } => edits.push(item),
+                    Slot::Inserted(value) => {
+                        let new: T = T::from_delta(value.clone())?.apply(item)?;
+                        *value = new.into_delta()?;
+                    },
+                }
+            },
+            EltDelta::Insert { index, item } => {
+                self.reserve(index);
+                self.slots.insert(index, Slot::Inserted(item));
+            },
+            EltDelta::Delete { index } => self.delete(index, 1),
+            EltDelta::DeleteRange { index, count } => self.delete(index, count),
+            change => return Ok(Some(change)),
+        }
+        Ok(None)
+    }
+
+    fn delete(&mut self, index: usize, count: usize) {
+        self.reserve(index + count);
+        for slot in self.slots.drain(index .. index + count) {
+            if let Slot::Base { index, .. } = slot { self.deleted.push(index); }
+        }
+    }
+
+    /// Emit the operations of the run in canonical order: first all
+    /// deletions, ascending by index and coalesced where adjacent, then
+    /// all insertions and edits, ascending by index.
+    fn flush(&mut self, changes: &mut Vec<EltDelta<T>>) {
+        let mut deleted: Vec<usize> = std::mem::take(&mut self.deleted);
+        deleted.sort_unstable();
+        // NOTE: Each deletion shifts the base values after it to the left:
+        for (shift, base) in deleted.into_iter().enumerate() {
+            let index: usize = base - shift;
+            match changes.last_mut() {
+                Some(last) if matches!(*last, EltDelta::Delete { index: i } if i == index) =>
+                    *last = EltDelta::DeleteRange { index, count: 2 },
+                Some(EltDelta::DeleteRange { index: i, count }) if *i == index =>
+                    *count += 1,
+                _ => changes.push(EltDelta::Delete { index }),
+            }
+        }
+        for (index, slot) in std::mem::take(&mut self.slots).into_iter().enumerate() {
+            match slot {
+                Slot::Base { edits, .. } => changes.extend(
+                    edits.into_iter().map(|item| EltDelta::Edit { index, item })
+                ),
+                Slot::Inserted(item) => changes.push(EltDelta::Insert { index, item }),
+            }
+        }
+        self.next_base = 0;
+    }
+}
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Reorder the operations of `self` into a canonical form, such that
+    /// equivalent deltas become equal, e.g. for content addressing.
+    /// Within each run of `Edit`s, `Insert`s, `Delete`s and `DeleteRange`s,
+    /// all deletions come first, ascending by index and coalesced where
+    /// adjacent, followed by all insertions and edits, ascending by index.
+    /// Deleting an inserted value cancels out both operations, and edits
+    /// of an inserted value are folded into its insertion.  Any other
+    /// operation, e.g. an `EltDelta::Add`, ends a run and stays in place.
+    ///
+    /// Applying the normalized delta has the same result as applying
+    /// `self`, for every `Vec` that `self` applies to successfully.
+    pub fn normalize(&mut self) -> DeltaResult<()> {
+        let mut changes: Vec<EltDelta<T>> = vec![];
+        let mut run: Run<T> = Run::new();
+        for change in std::mem::take(&mut self.0) {
+            if let Some(change) = run.push(change)? {
+                run.flush(&mut changes);
+                changes.push(change);
+            }
+        }
+        if !run.is_empty() { run.flush(&mut changes); }
+        self.0 = changes;
+        Ok(())
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn VecDelta__normalize() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 10).collect();
+        let mut delta0: VecDelta<u32> = VecDelta(vec![
+            EltDelta::Insert { index: 0, item: 100u32.into_delta()? },
+            EltDelta::Delete { index: 4 },
+            EltDelta::Edit { index: 5, item: 50u32.into_delta()? },
+            EltDelta::Delete { index: 4 },
+            EltDelta::Add(10u32.into_delta()?),
+        ]);
+        let mut delta1: VecDelta<u32> = VecDelta(vec![
+            EltDelta::Edit { index: 5, item: 50u32.into_delta()? },
+            EltDelta::Delete { index: 3 },
+            EltDelta::Insert { index: 1, item: 7u32.into_delta()? },
+            EltDelta::Delete { index: 4 },
+            EltDelta::Edit { index: 1, item: 70u32.into_delta()? },
+            EltDelta::Insert { index: 0, item: 100u32.into_delta()? },
+            EltDelta::Delete { index: 2 },
+            EltDelta::Add(10u32.into_delta()?),
+        ]);
+        let (vec1, vec2) = (vec0.apply(delta0.clone())?, vec0.apply(delta1.clone())?);
+        assert_eq!(vec1, vec2);
+        assert_ne!(delta0, delta1);
+
+        delta0.normalize()?;
+        delta1.normalize()?;
+        assert_eq!(delta0, delta1);
+        assert_eq!(delta0, VecDelta(vec![
+            EltDelta::DeleteRange { index: 3, count: 2 },
+            EltDelta::Insert { index: 0, item: 100u32.into_delta()? },
+            EltDelta::Edit { index: 4, item: 50u32.into_delta()? },
+            EltDelta::Add(10u32.into_delta()?),
+        ]));
+        assert_eq!(vec0.apply(delta0)?, vec1);
+        assert_eq!(vec0.apply(delta1)?, vec1);
+        Ok(())
+    }
+}
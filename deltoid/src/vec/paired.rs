@@ -0,0 +1,103 @@
+//! Diffing of 2 `Vec`s that are kept in lockstep, e.g. items and their
+//! metadata, with a single edit script.
+
+use crate::{Apply, Core, DeltaResult, FromDelta, IntoDelta};
+use crate::vec::{edit_script, myers, VecDelta};
+use crate::vec::myers::DiffOp;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+
+/// The deltas of 2 `Vec`s kept in lockstep, as returned by
+/// `delta_paired()`.  Both consist of the same sequence of
+/// `EltDelta::Insert`s, `EltDelta::Delete`s and `EltDelta::DeleteRange`s
+/// at the same indices, differing only in the inserted items.  Applying
+/// them therefore keeps the `Vec`s in lockstep.
+#[derive(Clone, Debug, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct PairedVecDelta<A: Core, B: Core> {
+    pub a: VecDelta<A>,
+    pub b: VecDelta<B>,
+}
+
+/// Calculate a single edit script that transforms both `old_a` into
+/// `new_a` and `old_b` into `new_b`, where the elements at the same index
+/// in each pair of `Vec`s belong together.  An element is only considered
+/// unchanged if both its `a` and `b` parts are, so e.g. reordering both
+/// `Vec`s in the same way moves the pairs as a whole.  The script is a
+/// minimal sequence of insertions and deletions, like that calculated
+/// by `VecStrategy::Myers`.
+pub fn delta_paired<A, B>(
+    old_a: &[A],
+    old_b: &[B],
+    new_a: &[A],
+    new_b: &[B],
+) -> DeltaResult<PairedVecDelta<A, B>>
+where A: Clone + Debug + PartialEq + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      B: Clone + Debug + PartialEq + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    ensure_eq!(old_a.len(), old_b.len())?;
+    ensure_eq!(new_a.len(), new_b.len())?;
+    let eq = |i: usize, j: usize| old_a[i] == new_a[j] && old_b[i] == new_b[j];
+    let ops: Vec<DiffOp> = myers::diff(old_a.len(), new_a.len(), eq);
+    Ok(PairedVecDelta {
+        a: VecDelta(edit_script(new_a, ops.iter().copied())?),
+        b: VecDelta(edit_script(new_b, ops)?),
+    })
+}
+
+impl<A, B> PairedVecDelta<A, B>
+where A: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      B: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    /// Apply `self` to both `Vec`s, which must be of the same length.
+    pub fn apply(self, a: &Vec<A>, b: &Vec<B>) -> DeltaResult<(Vec<A>, Vec<B>)> {
+        ensure_eq!(a.len(), b.len())?;
+        Ok((a.apply(self.a)?, b.apply(self.b)?))
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::EltDelta;
+
+    #[test]
+    fn delta_paired__reordered() -> DeltaResult<()> {
+        let items0: Vec<String> = vec!["a".into(), "b".into(), "c".into(), "d".into()];
+        let metas0: Vec<u32> = vec![1, 2, 3, 4];
+        let items1: Vec<String> = vec!["c".into(), "a".into(), "b".into(), "e".into()];
+        let metas1: Vec<u32> = vec![3, 1, 20, 5];
+        let delta = delta_paired(&items0, &metas0, &items1, &metas1)?;
+        fn shape<T: Core>(delta: &VecDelta<T>) -> Vec<String> {
+            delta.iter().map(|change| match change {
+                EltDelta::Insert { index, .. } => format!("+{}", index),
+                EltDelta::Delete { index } => format!("-{}", index),
+                EltDelta::DeleteRange { index, count } => format!("-{}x{}", index, count),
+                _ => unreachable!(),
+            }).collect()
+        }
+        assert_eq!(shape(&delta.a), shape(&delta.b));
+        let (items2, metas2) = delta.apply(&items0, &metas0)?;
+        assert_eq!(items2, items1);
+        assert_eq!(metas2, metas1);
+        Ok(())
+    }
+
+    #[test]
+    fn delta_paired__mismatched_lengths() {
+        let result = delta_paired(&[1u32, 2], &[1u32], &[], &[]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,129 @@
+//! Diffing of large `Vec`s in bounded steps, e.g. across async yield points.
+
+use crate::{Delta, DeltaResult, IntoDelta};
+use crate::vec::{compact_swap, push_positional, EltDelta, VecDelta};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::task::Poll;
+
+
+/// A positional diff of 2 `Vec`s that can be suspended and resumed.
+/// See `VecDelta::diff_resumable()`.
+#[derive(Clone, Debug)]
+pub struct ResumableDiff<'v, T: Delta> {
+    lhs: &'v [T],
+    rhs: &'v [T],
+    /// The next index to compare.
+    next: usize,
+    /// Whether `rhs` could still be `lhs` with some values prepended.
+    prepended: bool,
+    changes: Vec<EltDelta<T>>,
+}
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Start a resumable calculation of `lhs --[delta]--> rhs`.  Rather
+    /// than comparing all elements at once, `ResumableDiff::step()` does
+    /// a bounded number of comparisons per call, so that e.g. an async
+    /// task diffing huge `Vec`s can yield in between.  The final delta
+    /// is the same as that of `lhs.delta(rhs)`.
+    pub fn diff_resumable<'v>(lhs: &'v [T], rhs: &'v [T]) -> ResumableDiff<'v, T> {
+        ResumableDiff {
+            lhs,
+            rhs,
+            next: 0,
+            prepended: !lhs.is_empty() && rhs.len() > lhs.len(),
+            changes: vec![],
+        }
+    }
+}
+
+impl<'v, T> ResumableDiff<'v, T>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Compare up to `max_comparisons` more elements.  This returns
+    /// `Poll::Ready(())` once all elements have been compared, after
+    /// which `self.finish()` returns the delta without further work.
+    pub fn step(&mut self, max_comparisons: usize) -> DeltaResult<Poll<()>> {
+        let (lhs, rhs): (&[T], &[T]) = (self.lhs, self.rhs);
+        // NOTE: Transitions from and to an empty `Vec` need no comparisons:
+        if lhs.is_empty() || rhs.is_empty() { return Ok(Poll::Ready(())) }
+        let max_len: usize = usize::max(lhs.len(), rhs.len());
+        let end: usize = usize::min(self.next.saturating_add(max_comparisons), max_len);
+        let offset: usize = rhs.len().saturating_sub(lhs.len());
+        if self.prepended {
+            self.prepended = (self.next .. usize::min(end, lhs.len()))
+                .all(|index| lhs[index] == rhs[index + offset]);
+        }
+        push_positional(
+            &mut self.changes,
+            self.next .. end,
+            (lhs.len(), |index| Ok(Cow::Borrowed(&lhs[index]))),
+            (rhs.len(), |index| Ok(Cow::Borrowed(&rhs[index]))),
+        )?;
+        self.next = end;
+        Ok(if end == max_len { Poll::Ready(()) } else { Poll::Pending })
+    }
+
+    /// Return the delta, first comparing any elements
+    /// that haven't been compared by `self.step()` yet.
+    pub fn finish(mut self) -> DeltaResult<VecDelta<T>> {
+        while self.step(usize::MAX)?.is_pending() {}
+        let (lhs, rhs): (&[T], &[T]) = (self.lhs, self.rhs);
+        if lhs.is_empty() && !rhs.is_empty() {
+            let items = rhs.iter()
+                .map(|r| r.clone().into_delta())
+                .collect::<DeltaResult<_>>()?;
+            return Ok(VecDelta(vec![EltDelta::SetAll(items)]));
+        } else if !lhs.is_empty() && rhs.is_empty() {
+            return Ok(VecDelta(vec![EltDelta::Clear]));
+        } else if self.prepended {
+            let items = rhs[.. rhs.len() - lhs.len()].iter()
+                .map(|r| r.clone().into_delta())
+                .collect::<DeltaResult<_>>()?;
+            return Ok(VecDelta(vec![EltDelta::Prepend(items)]));
+        }
+        compact_swap(
+            &mut self.changes,
+            |index| Ok(Cow::Borrowed(&lhs[index])),
+            |index| Ok(Cow::Borrowed(&rhs[index])),
+        )?;
+        Ok(VecDelta(self.changes))
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn VecDelta__diff_resumable() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 1000).collect();
+        let cases: Vec<Vec<u32>> = vec![
+            (0 .. 1000).map(|n| if n % 7 == 0 { n + 1 } else { n }).collect(),
+            (0 .. 1200).collect(),
+            (0 .. 900).collect(),
+            (2000 .. 2010).chain(0 .. 1000).collect(),
+            vec![],
+        ];
+        for vec1 in cases {
+            let mut diff = VecDelta::diff_resumable(&vec0, &vec1);
+            let mut steps = 0;
+            while diff.step(64)?.is_pending() { steps += 1; }
+            assert!(vec1.is_empty() || steps >= 15, "{} steps", steps);
+            assert_eq!(diff.finish()?, vec0.delta(&vec1)?);
+        }
+        let diff = VecDelta::diff_resumable(&[], &vec0);
+        assert_eq!(diff.finish()?, Vec::<u32>::new().delta(&vec0)?);
+        Ok(())
+    }
+}
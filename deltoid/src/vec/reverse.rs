@@ -0,0 +1,173 @@
+//! `VecDelta`s that can be applied in reverse, e.g. to undo them.
+
+use crate::{Apply, Core, DeltaResult, FromDelta};
+use crate::vec::{apply_elt_delta, EltDelta, VecDelta};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+
+/// A `VecDelta` together with the values that each of its operations
+/// overwrites or removes, as calculated against the `Vec` the delta was
+/// made for.  See `VecDelta::apply_reverse()`.
+#[derive(Clone, Debug, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct ReversibleVecDelta<T: Core> {
+    pub delta: VecDelta<T>,
+    /// For each operation in `delta`, the values it overwrites or removes.
+    pub removed: Vec<Vec<T>>,
+}
+
+/// Return the values in `vec` that `change` overwrites or removes.
+/// `EltDelta::Add`, `EltDelta::Insert`, `EltDelta::Prepend` and
+/// `EltDelta::Swap` don't lose any values, while `EltDelta::SetAll` and
+/// `EltDelta::Clear` overwrite all of them.
+fn removed_values<T>(vec: &[T], change: &EltDelta<T>) -> DeltaResult<Vec<T>>
+where T: Clone + Core {
+    Ok(match change {
+        EltDelta::Edit { index, .. } | EltDelta::Delete { index } => {
+            ensure_lt![*index, vec.len()]?;
+            vec![vec[*index].clone()]
+        },
+        EltDelta::Remove { count } => {
+            ensure_le![*count, vec.len()]?;
+            vec[vec.len() - count ..].to_vec()
+        },
+        EltDelta::DeleteRange { index, count } => {
+            ensure_le![*index, vec.len()]?;
+            ensure_le![*count, vec.len() - index]?;
+            vec[*index .. index + count].to_vec()
+        },
+        EltDelta::SetAll(_) | EltDelta::Clear => vec.to_vec(),
+        EltDelta::Add(_) | EltDelta::Insert { .. } | EltDelta::Prepend(_)
+            | EltDelta::Swap { .. } => vec![],
+    })
+}
+
+/// Undo the effect of `change` on `vec`, given the values it `removed`.
+fn unapply_elt_delta<T>(
+    vec: &mut Vec<T>,
+    change: &EltDelta<T>,
+    removed: Vec<T>,
+) -> DeltaResult<()>
+where T: Core {
+    match change {
+        EltDelta::Edit { index, .. } => {
+            ensure_lt![*index, vec.len()]?;
+            ensure_eq![removed.len(), 1]?;
+            vec[*index] = removed.into_iter().next().unwrap(/*len 1*/);
+        },
+        EltDelta::Add(_) => {
+            ensure_gt![vec.len(), 0]?;
+            vec.pop();
+        },
+        EltDelta::Remove { count } => {
+            ensure_eq![removed.len(), *count]?;
+            vec.extend(removed);
+        },
+        EltDelta::Swap { i, j } => {
+            ensure_lt![*i, vec.len()]?;
+            ensure_lt![*j, vec.len()]?;
+            vec.swap(*i, *j);
+        },
+        EltDelta::Insert { index, .. } => {
+            ensure_lt![*index, vec.len()]?;
+            vec.remove(*index);
+        },
+        EltDelta::Delete { index } => {
+            ensure_le![*index, vec.len()]?;
+            ensure_eq![removed.len(), 1]?;
+            vec.insert(*index, removed.into_iter().next().unwrap(/*len 1*/));
+        },
+        EltDelta::DeleteRange { index, count } => {
+            ensure_le![*index, vec.len()]?;
+            ensure_eq![removed.len(), *count]?;
+            vec.splice(*index .. *index, removed);
+        },
+        EltDelta::Prepend(items) => {
+            ensure_le![items.len(), vec.len()]?;
+            vec.drain(.. items.len());
+        },
+        EltDelta::SetAll(_) | EltDelta::Clear => {
+            *vec = removed;
+        },
+    }
+    Ok(())
+}
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Attach to `self` the values that each of its operations overwrites
+    /// or removes, as seen when applying `self` to `base`.  This makes
+    /// it possible to reconstruct `base` from the result of applying
+    /// `self` to it, without having to store `base` itself.
+    pub fn with_removed_values(
+        self,
+        base: &[T],
+    ) -> DeltaResult<ReversibleVecDelta<T>> {
+        let mut vec: Vec<T> = base.to_vec();
+        let mut removed = vec![];
+        for change in self.iter() {
+            removed.push(removed_values(&vec, change)?);
+            apply_elt_delta(&mut vec, change.clone())?;
+        }
+        Ok(ReversibleVecDelta { delta: self, removed })
+    }
+
+    /// Reconstruct the `Vec` that `delta` was made for, given the state
+    /// `new_state` that resulted from applying `delta` to it.  This undoes
+    /// the operations of `delta` in reverse order, restoring the values
+    /// they overwrote or removed.
+    pub fn apply_reverse(
+        new_state: &[T],
+        delta: ReversibleVecDelta<T>,
+    ) -> DeltaResult<Vec<T>> {
+        let ReversibleVecDelta { delta, removed } = delta;
+        ensure_eq!(delta.len(), removed.len())?;
+        let mut vec: Vec<T> = new_state.to_vec();
+        for (change, removed) in delta.0.iter().zip(removed).rev() {
+            unapply_elt_delta(&mut vec, change, removed)?;
+        }
+        Ok(vec)
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Delta, IntoDelta};
+
+    #[test]
+    fn VecDelta__apply_reverse() -> DeltaResult<()> {
+        let vec0: Vec<u32> = vec![1, 2, 3, 4, 5];
+        let vec1: Vec<u32> = vec![1, 20, 3, 40];
+        let delta = vec0.delta(&vec1)?.with_removed_values(&vec0)?;
+        let vec2: Vec<u32> = vec0.apply(delta.delta.clone())?;
+        assert_eq!(vec2, vec1);
+        assert_eq!(VecDelta::apply_reverse(&vec2, delta)?, vec0);
+
+        // NOTE: Every kind of operation can be reversed:
+        let delta: VecDelta<u32> = VecDelta(vec![
+            EltDelta::Swap { i: 0, j: 4 },
+            EltDelta::Insert { index: 1, item: 6u32.into_delta()? },
+            EltDelta::Delete { index: 3 },
+            EltDelta::DeleteRange { index: 1, count: 2 },
+            EltDelta::Prepend(vec![7u32.into_delta()?, 8u32.into_delta()?]),
+            EltDelta::Add(9u32.into_delta()?),
+            EltDelta::Remove { count: 2 },
+            EltDelta::Edit { index: 0, item: 10u32.into_delta()? },
+            EltDelta::SetAll(vec![11u32.into_delta()?]),
+            EltDelta::Clear,
+        ]);
+        let delta = delta.with_removed_values(&vec0)?;
+        let vec3: Vec<u32> = vec0.apply(delta.delta.clone())?;
+        assert_eq!(vec3, Vec::<u32>::new());
+        assert_eq!(VecDelta::apply_reverse(&vec3, delta)?, vec0);
+        Ok(())
+    }
+}
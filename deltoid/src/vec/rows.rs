@@ -0,0 +1,128 @@
+//! A flattened, row-by-row view of `VecDelta`s for e.g. table UIs.
+
+use crate::{Apply, DeltaResult, FromDelta};
+use crate::vec::{apply_elt_delta, EltDelta, VecDelta};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+
+/// The change of a single row i.e. element of a `Vec`.
+/// See `VecDelta::as_row_changes()`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ElementChange<T> {
+    /// A row holding the given value was added.
+    Added(T),
+    /// The row was removed.
+    Removed,
+    /// The value of the row was changed to the given value.
+    Modified(T),
+}
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Describe the effect of applying `self` to `base` as a flat list
+    /// of `(row_index, change)` pairs.  Like the operations in `self`,
+    /// each row index refers to the `Vec` as it is after all previous
+    /// changes have been applied to it, so the list can be replayed
+    /// on e.g. a table one change at a time.  Operations affecting
+    /// multiple rows, such as `EltDelta::Swap`, are split into one
+    /// change per row.
+    pub fn as_row_changes(
+        &self,
+        base: &[T],
+    ) -> DeltaResult<Vec<(usize, ElementChange<T>)>> {
+        let mut vec: Vec<T> = base.to_vec();
+        let mut rows: Vec<(usize, ElementChange<T>)> = vec![];
+        for change in self.iter() {
+            let old_len: usize = vec.len();
+            apply_elt_delta(&mut vec, change.clone())?;
+            match change {
+                EltDelta::Edit { index, .. } => rows.push(
+                    (*index, ElementChange::Modified(vec[*index].clone()))
+                ),
+                EltDelta::Add(_) => rows.push(
+                    (old_len, ElementChange::Added(vec[old_len].clone()))
+                ),
+                EltDelta::Remove { count } => rows.extend(
+                    (old_len - count .. old_len).rev()
+                        .map(|index| (index, ElementChange::Removed))
+                ),
+                EltDelta::Swap { i, j } => rows.extend(vec![
+                    (*i, ElementChange::Modified(vec[*i].clone())),
+                    (*j, ElementChange::Modified(vec[*j].clone())),
+                ]),
+                EltDelta::Insert { index, .. } => rows.push(
+                    (*index, ElementChange::Added(vec[*index].clone()))
+                ),
+                EltDelta::Delete { index } => rows.push(
+                    (*index, ElementChange::Removed)
+                ),
+                EltDelta::DeleteRange { index, count } => rows.extend(
+                    (0 .. *count).map(|_| (*index, ElementChange::Removed))
+                ),
+                EltDelta::Prepend(items) => rows.extend(
+                    vec[.. items.len()].iter().enumerate().map(|(index, item)| {
+                        (index, ElementChange::Added(item.clone()))
+                    })
+                ),
+                EltDelta::SetAll(_) | EltDelta::Clear => {
+                    rows.extend((0 .. old_len).rev().map(|index| {
+                        (index, ElementChange::Removed)
+                    }));
+                    rows.extend(vec.iter().enumerate().map(|(index, item)| {
+                        (index, ElementChange::Added(item.clone()))
+                    }));
+                },
+            }
+        }
+        Ok(rows)
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IntoDelta;
+
+    #[test]
+    fn VecDelta__as_row_changes() -> DeltaResult<()> {
+        let vec0: Vec<u32> = vec![10, 20, 30];
+        let delta: VecDelta<u32> = VecDelta(vec![
+            EltDelta::Edit { index: 1, item: 25u32.into_delta()? },
+            EltDelta::Insert { index: 0, item: 5u32.into_delta()? },
+            EltDelta::Delete { index: 3 },
+            EltDelta::Swap { i: 0, j: 2 },
+            EltDelta::Add(40u32.into_delta()?),
+            EltDelta::Remove { count: 2 },
+        ]);
+        let rows = delta.as_row_changes(&vec0)?;
+        assert_eq!(rows, vec![
+            (1, ElementChange::Modified(25)),
+            (0, ElementChange::Added(5)),
+            (3, ElementChange::Removed),
+            (0, ElementChange::Modified(25)),
+            (2, ElementChange::Modified(5)),
+            (3, ElementChange::Added(40)),
+            (3, ElementChange::Removed),
+            (2, ElementChange::Removed),
+        ]);
+        // NOTE: Replaying the row changes one by one yields the same
+        //       result as applying the delta:
+        let mut table: Vec<u32> = vec0.clone();
+        for (index, change) in rows {
+            match change {
+                ElementChange::Added(value) => table.insert(index, value),
+                ElementChange::Removed => { table.remove(index); },
+                ElementChange::Modified(value) => table[index] = value,
+            }
+        }
+        assert_eq!(table, vec0.apply(delta)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+//! Application of `VecDelta`s while they are being deserialized.
+
+use crate::{Apply, FromDelta};
+use crate::vec::{apply_elt_delta, EltDelta, VecDelta};
+use serde::de::{self, DeserializeSeed, Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Debug};
+
+
+/// A `DeserializeSeed` that applies each operation of a serialized
+/// `VecDelta` to a base `Vec` as soon as it has been read, rather than
+/// first collecting all operations into a `VecDelta`.
+/// See `VecDelta::apply_seed()`.
+pub struct VecDeltaSeed<'b, T> {
+    base: &'b mut Vec<T>,
+}
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Return a `DeserializeSeed` that applies a serialized `VecDelta`
+    /// to `base` in place while deserializing it.  The result is the same
+    /// as deserializing the `VecDelta` and then applying it to `base`,
+    /// but no intermediate `VecDelta` is allocated.  If an operation
+    /// fails to apply, deserialization fails with a custom error, and
+    /// `base` is left with the operations before it applied.
+    pub fn apply_seed(base: &mut Vec<T>) -> VecDeltaSeed<'_, T> {
+        VecDeltaSeed { base }
+    }
+}
+
+impl<'b, 'de, T> DeserializeSeed<'de> for VecDeltaSeed<'b, T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'d> Deserialize<'d>
+    + Serialize
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where D: Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(
+            "VecDelta",
+            VecDeltaVisitor { base: self.base },
+        )
+    }
+}
+
+struct VecDeltaVisitor<'b, T> {
+    base: &'b mut Vec<T>,
+}
+
+impl<'b, 'de, T> Visitor<'de> for VecDeltaVisitor<'b, T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'d> Deserialize<'d>
+    + Serialize
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a VecDelta")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<(), D::Error>
+    where D: Deserializer<'de> {
+        deserializer.deserialize_seq(self)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+    where A: SeqAccess<'de> {
+        while let Some(change) = seq.next_element::<EltDelta<T>>()? {
+            apply_elt_delta(self.base, change)
+                .map_err(|err| de::Error::custom(format!("{:?}", err)))?;
+        }
+        Ok(())
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DeltaResult, Delta};
+
+    #[test]
+    fn VecDelta__apply_seed() -> DeltaResult<()> {
+        let vec0: Vec<u32> = vec![1, 2, 3, 4, 5];
+        let vec1: Vec<u32> = vec![1, 20, 3, 6, 7, 8];
+        let delta: VecDelta<u32> = vec0.delta(&vec1)?;
+        let json: String = serde_json::to_string(&delta)
+            .expect("failed to serialize");
+        let mut vec2: Vec<u32> = vec0.clone();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        VecDelta::apply_seed(&mut vec2).deserialize(&mut deserializer)
+            .expect("failed to deserialize");
+        let deserialized: VecDelta<u32> = serde_json::from_str(&json)
+            .expect("failed to deserialize");
+        assert_eq!(vec2, vec0.apply(deserialized)?);
+        assert_eq!(vec2, vec1);
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+//! Calculation of `VecDelta`s between index-addressable lazy sources,
+//! e.g. memory-mapped or paged storage that can't be held in RAM at once.
+
+use crate::{Delta, DeltaResult, IntoDelta};
+use crate::vec::{compact_swap, push_positional, EltDelta, VecDelta};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+
+/// A sequence of values that can be read one index at a time.
+pub trait IndexedSource<T> {
+    /// Return the number of values in `self`.
+    fn len(&self) -> usize;
+
+    /// Return the value at `index`, or `None` if it's out of bounds.
+    fn get(&self, index: usize) -> Option<T>;
+
+    /// Return true iff. `self` contains no values.
+    fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+impl<T: Clone> IndexedSource<T> for [T] {
+    fn len(&self) -> usize { <[T]>::len(self) }
+
+    fn get(&self, index: usize) -> Option<T> { <[T]>::get(self, index).cloned() }
+}
+
+/// Read the value at `index` of `source`, which must be within bounds.
+fn read<T, S>(source: &S, index: usize) -> DeltaResult<T>
+where S: IndexedSource<T> + ?Sized {
+    source.get(index).ok_or_else(|| ExpectedValue!("IndexedSource<T>"))
+}
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Calculate `lhs --[delta]--> rhs`, where both are read one value at
+    /// a time rather than being held in memory in their entirety.  The
+    /// delta is the same as that of `lhs.delta(rhs)` for the equivalent
+    /// `Vec`s, and each value is read at most a few times.
+    pub fn diff_indexed<L, R>(lhs: &L, rhs: &R) -> DeltaResult<Self>
+    where L: IndexedSource<T> + ?Sized,
+          R: IndexedSource<T> + ?Sized {
+        let (lhs_len, rhs_len): (usize, usize) = (lhs.len(), rhs.len());
+        let read_all = |count: usize| -> DeltaResult<Vec<<T as crate::Core>::Delta>> {
+            (0 .. count).map(|index| read(rhs, index)?.into_delta()).collect()
+        };
+        // NOTE: Transitions from and to an empty `Vec` are a single operation:
+        if lhs_len == 0 && rhs_len > 0 {
+            return Ok(VecDelta(vec![EltDelta::SetAll(read_all(rhs_len)?)]));
+        } else if lhs_len > 0 && rhs_len == 0 {
+            return Ok(VecDelta(vec![EltDelta::Clear]));
+        }
+        // NOTE: Fast path for when values were only prepended to `lhs`:
+        if lhs_len > 0 && rhs_len > lhs_len {
+            let offset: usize = rhs_len - lhs_len;
+            let mut prepended = true;
+            for index in 0 .. lhs_len {
+                if read(lhs, index)? != read(rhs, index + offset)? {
+                    prepended = false;
+                    break;
+                }
+            }
+            if prepended {
+                return Ok(VecDelta(vec![EltDelta::Prepend(read_all(offset)?)]));
+            }
+        }
+        let mut changes: Vec<EltDelta<T>> = vec![];
+        push_positional(
+            &mut changes,
+            0 .. usize::max(lhs_len, rhs_len),
+            (lhs_len, |index| read(lhs, index).map(Cow::Owned)),
+            (rhs_len, |index| read(rhs, index).map(Cow::Owned)),
+        )?;
+        compact_swap(
+            &mut changes,
+            |index| read(lhs, index).map(Cow::Owned),
+            |index| read(rhs, index).map(Cow::Owned),
+        )?;
+        Ok(VecDelta(changes))
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// A source that loads its values from fixed-size pages on demand,
+    /// and counts the number of values it loaded.
+    struct Paged {
+        pages: Vec<Vec<u32>>,
+        loads: Cell<usize>,
+    }
+
+    impl Paged {
+        fn new(values: &[u32]) -> Self {
+            let pages = values.chunks(16).map(|page| page.to_vec()).collect();
+            Self { pages, loads: Cell::new(0) }
+        }
+    }
+
+    impl IndexedSource<u32> for Paged {
+        fn len(&self) -> usize {
+            self.pages.iter().map(|page| page.len()).sum()
+        }
+
+        fn get(&self, index: usize) -> Option<u32> {
+            self.loads.set(self.loads.get() + 1);
+            self.pages.get(index / 16)?.get(index % 16).copied()
+        }
+    }
+
+    #[test]
+    fn VecDelta__diff_indexed() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 100).collect();
+        let cases: Vec<Vec<u32>> = vec![
+            (0 .. 100).map(|n| if n % 9 == 0 { n + 1 } else { n }).collect(),
+            (0 .. 120).collect(),
+            (0 .. 90).collect(),
+            (200 .. 210).chain(0 .. 100).collect(),
+            vec![1, 0].into_iter().chain(2 .. 100).collect(),
+            vec![],
+        ];
+        for vec1 in cases {
+            let (lhs, rhs) = (Paged::new(&vec0), Paged::new(&vec1));
+            let delta = VecDelta::diff_indexed(&lhs, &rhs)?;
+            assert_eq!(delta, vec0.delta(&vec1)?);
+            assert!(lhs.loads.get() <= 3 * vec0.len());
+            assert_eq!(VecDelta::diff_indexed(&vec0[..], &vec1[..])?, delta);
+        }
+        let delta = VecDelta::diff_indexed(&[][..], &Paged::new(&vec0))?;
+        assert_eq!(delta, Vec::<u32>::new().delta(&vec0)?);
+        Ok(())
+    }
+}
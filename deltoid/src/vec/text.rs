@@ -0,0 +1,207 @@
+//! A compact, line-based text format for `VecDelta`s, intended to be
+//! read and edited by hand. Each line encodes a single operation:
+//!
+//! ```text
+//! +3 value    Insert `value` at index 3
+//! -5          Delete the value at index 5
+//! -5..8       Delete the values at indices 5, 6 and 7
+//! ~2 value    Edit the value at index 2 to become `value`
+//! ```
+//!
+//! Values are rendered with `Display` and parsed with `FromStr`, and
+//! span the remainder of their line verbatim, so `+3 ` inserts an empty
+//! value and trailing whitespace is kept.  Values whose rendering spans
+//! more than one line can't be expressed.  Blank lines are ignored.
+
+use crate::{Core, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::vec::{EltDelta, VecDelta};
+use std::fmt::Display;
+use std::str::FromStr;
+
+
+impl<T> VecDelta<T>
+where T: Core + FromDelta + IntoDelta + Display + FromStr,
+      <T as FromStr>::Err: Display,
+{
+    /// Render `self` in the text format, one operation per line.
+    /// Only `Insert`, `Delete`, `DeleteRange` and `Edit` operations can
+    /// be expressed, which covers e.g. deltas calculated with
+    /// `VecStrategy::Myers`.
+    /// Any other operation, or a value whose rendering contains a line
+    /// break, results in an error.
+    pub fn to_text(&self) -> DeltaResult<String> {
+        let mut text = String::new();
+        for (index, change) in self.iter().enumerate() {
+            let render = |item: &T::Delta| -> DeltaResult<String> {
+                let value: String = T::from_delta(item.clone())?.to_string();
+                if value.contains(['\n', '\r']) {
+                    return Err(DeltaError::IllegalDelta { index });
+                }
+                Ok(value)
+            };
+            let line: String = match change {
+                EltDelta::Insert { index, item } =>
+                    format!("+{} {}", index, render(item)?),
+                EltDelta::Delete { index } =>
+                    format!("-{}", index),
+                EltDelta::DeleteRange { index, count } =>
+                    format!("-{}..{}", index, index + count),
+                EltDelta::Edit { index, item } =>
+                    format!("~{} {}", index, render(item)?),
+                _ => return Err(DeltaError::IllegalDelta { index }),
+            };
+            text.push_str(&line);
+            text.push('\n');
+        }
+        Ok(text)
+    }
+
+    /// Parse a `VecDelta` from `text`, as rendered by `Self::to_text()`.
+    pub fn from_text(text: &str) -> DeltaResult<Self> {
+        let mut changes: Vec<EltDelta<T>> = vec![];
+        for (lineno, line) in text.lines().enumerate() {
+            if line.trim().is_empty() { continue }
+            let parse_error = |reason: String| DeltaError::ParseError {
+                line: lineno + 1,
+                reason,
+            };
+            let (op, rest): (char, &str) = {
+                let mut chars = line.chars();
+                let op: char = chars.next().unwrap(/* line is non-empty */);
+                (op, chars.as_str())
+            };
+            let (index, value): (&str, Option<&str>) = match rest.find(' ') {
+                Some(pos) => (&rest[..pos], Some(&rest[pos + 1..])),
+                None => (rest, None),
+            };
+            let parse_index = |index: &str| -> DeltaResult<usize> {
+                index.parse().map_err(|_| {
+                    parse_error(format!("Expected an index, got {:?}", index))
+                })
+            };
+            if let (Some(pos), '-', None) = (index.find(".."), op, value) {
+                let start: usize = parse_index(&index[.. pos])?;
+                let end: usize = parse_index(&index[pos + 2 ..])?;
+                if start >= end { return Err(parse_error(
+                    format!("Expected a non-empty range, got {:?}", index)
+                )) }
+                changes.push(EltDelta::DeleteRange { index: start, count: end - start });
+                continue;
+            }
+            let index: usize = parse_index(index)?;
+            let parse_value = |value: Option<&str>| -> DeltaResult<T::Delta> {
+                let value: &str = value.ok_or_else(|| {
+                    parse_error(format!("Expected a value after '{}'", op))
+                })?;
+                let value: T = value.parse().map_err(|err| {
+                    parse_error(format!("Failed to parse value: {}", err))
+                })?;
+                value.into_delta()
+            };
+            changes.push(match (op, value) {
+                ('+', value) => EltDelta::Insert { index, item: parse_value(value)? },
+                ('-', None) => EltDelta::Delete { index },
+                ('-', Some(_)) => return Err(parse_error(
+                    "Unexpected value after '-'".to_string()
+                )),
+                ('~', value) => EltDelta::Edit { index, item: parse_value(value)? },
+                (op, _) => return Err(parse_error(
+                    format!("Unknown operation '{}'", op)
+                )),
+            });
+        }
+        Ok(VecDelta(changes))
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Apply, DeltaConfig, VecStrategy};
+    use crate::vec::delta_with_config;
+
+    #[test]
+    fn VecDelta__text__round_trip() -> DeltaResult<()> {
+        let v0: Vec<String> = vec!["a".into(), "b".into(), "c".into()];
+        let v1: Vec<String> = vec!["a".into(), "hello world".into(), "c".into()];
+        let config = DeltaConfig {
+            vec_strategy: VecStrategy::Myers,
+            ..Default::default()
+        };
+        let delta: VecDelta<String> = delta_with_config(&v0, &v1, &config)?;
+        let text: String = delta.to_text()?;
+        assert_eq!(text, "-1\n+1 hello world\n");
+        let parsed: VecDelta<String> = VecDelta::from_text(&text)?;
+        assert_eq!(parsed, delta);
+        assert_eq!(v0.apply(parsed)?, v1);
+
+        let delta: VecDelta<u32> = VecDelta::from_text("~0 7\n\n-2\n+1 9\n")?;
+        assert_eq!(delta.to_text()?, "~0 7\n-2\n+1 9\n");
+        assert_eq!(vec![1u32, 2, 3].apply(delta)?, vec![7u32, 9, 2]);
+
+        let delta: VecDelta<u32> = VecDelta::from_text("-1..3\n+1 9\n")?;
+        assert_eq!(delta.0[0], EltDelta::DeleteRange { index: 1, count: 2 });
+        assert_eq!(delta.to_text()?, "-1..3\n+1 9\n");
+        assert_eq!(vec![1u32, 2, 3, 4].apply(delta)?, vec![1u32, 9, 4]);
+        Ok(())
+    }
+
+    #[test]
+    fn VecDelta__text__round_trip__whitespace() -> DeltaResult<()> {
+        let v0: Vec<String> = vec!["a".into(), "b".into()];
+        let v1: Vec<String> = vec!["".into(), "b \t".into(), " ".into()];
+        let delta: VecDelta<String> = VecDelta(vec![
+            EltDelta::Edit { index: 0, item: "".to_string().into_delta()? },
+            EltDelta::Edit { index: 1, item: "b \t".to_string().into_delta()? },
+            EltDelta::Insert { index: 2, item: " ".to_string().into_delta()? },
+        ]);
+        let text: String = delta.to_text()?;
+        assert_eq!(text, "~0 \n~1 b \t\n+2  \n");
+        let parsed: VecDelta<String> = VecDelta::from_text(&text)?;
+        assert_eq!(parsed, delta);
+        assert_eq!(v0.apply(parsed)?, v1);
+
+        let parsed: VecDelta<String> = VecDelta::from_text("+0 \r\n~1 x \r\n")?;
+        assert_eq!(parsed, VecDelta(vec![
+            EltDelta::Insert { index: 0, item: "".to_string().into_delta()? },
+            EltDelta::Edit { index: 1, item: "x ".to_string().into_delta()? },
+        ]));
+
+        let delta: VecDelta<String> = VecDelta(vec![
+            EltDelta::Delete { index: 0 },
+            EltDelta::Insert { index: 0, item: "two\nlines".to_string().into_delta()? },
+        ]);
+        assert_eq!(delta.to_text(), Err(DeltaError::IllegalDelta { index: 1 }));
+        Ok(())
+    }
+
+    #[test]
+    fn VecDelta__text__malformed_input() -> DeltaResult<()> {
+        let result = VecDelta::<u32>::from_text("+0 1\n*3 4\n");
+        assert_eq!(result, Err(DeltaError::ParseError {
+            line: 2,
+            reason: "Unknown operation '*'".into(),
+        }));
+        let result = VecDelta::<u32>::from_text("~x 1\n");
+        assert_eq!(result, Err(DeltaError::ParseError {
+            line: 1,
+            reason: "Expected an index, got \"x\"".into(),
+        }));
+        let result = VecDelta::<u32>::from_text("-3..3\n");
+        assert_eq!(result, Err(DeltaError::ParseError {
+            line: 1,
+            reason: "Expected a non-empty range, got \"3..3\"".into(),
+        }));
+        let result = VecDelta::<u32>::from_text("+3\n");
+        assert_eq!(result, Err(DeltaError::ParseError {
+            line: 1,
+            reason: "Expected a value after '+'".into(),
+        }));
+        let result = VecDelta::<u32>::from_text("+3 foo\n");
+        assert!(matches!(result, Err(DeltaError::ParseError { line: 1, .. })));
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+//! Application of `VecDelta`s to `Vec`s whose order doesn't matter.
+
+use crate::{Apply, DeltaError, DeltaResult, FromDelta};
+use crate::vec::{apply_elt_delta, EltDelta, VecDelta};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+
+impl<T> VecDelta<T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Apply `self` to `base`, treating `base` as an unordered multiset.
+    /// Each `EltDelta::Delete` removes its target using `Vec::swap_remove()`,
+    /// which moves at most 1 other value into its place rather than shifting
+    /// every value after it.
+    /// This *reorders* the result: it contains the same values as
+    /// `base.apply(self)`, but not necessarily in the same order.
+    ///
+    /// Since the indices of later operations would no longer line up
+    /// with the reordered `Vec`, only `EltDelta::Add`s may follow the
+    /// first `Delete`, and no `Delete` may follow those `Add`s.  Deltas
+    /// calculated by `delta_as_set()` meet this requirement.  Any other
+    /// operation after the first `Delete` results in an error.
+    pub fn apply_unordered(&self, base: &[T]) -> DeltaResult<Vec<T>> {
+        let mut vec: Vec<T> = base.to_vec();
+        // NOTE: The indices to delete, relative to `vec` as it was
+        //       before the first `Delete`, in ascending order:
+        let mut deleted: Vec<usize> = vec![];
+        let mut appending = false;
+        for (index, change) in self.iter().enumerate() { match change {
+            EltDelta::Delete { index: target } if !appending => {
+                // NOTE: Translate `target` to an index into `vec` by
+                //       skipping over the values deleted so far:
+                let mut target: usize = *target;
+                for &idx in deleted.iter() {
+                    if idx <= target { target += 1 } else { break }
+                }
+                ensure_lt![target, vec.len()]?;
+                let pos = deleted.binary_search(&target).unwrap_err(/*new*/);
+                deleted.insert(pos, target);
+            },
+            EltDelta::Add(item) if !deleted.is_empty() => {
+                if !appending {
+                    // NOTE: Removing the values in descending order ensures
+                    //       that each value moved into place by a swap-remove
+                    //       is one that's kept:
+                    for &target in deleted.iter().rev() {
+                        vec.swap_remove(target);
+                    }
+                    appending = true;
+                }
+                vec.push(<T>::from_delta(item.clone())?);
+            },
+            _ if !deleted.is_empty() =>
+                return Err(DeltaError::IllegalDelta { index }),
+            change => apply_elt_delta(&mut vec, change.clone())?,
+        }}
+        if !appending {
+            for &target in deleted.iter().rev() {
+                vec.swap_remove(target);
+            }
+        }
+        Ok(vec)
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IntoDelta;
+    use crate::vec::delta_as_set;
+
+    #[test]
+    fn VecDelta__apply_unordered__large_vec() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 20_000).collect();
+        let vec1: Vec<u32> = (0 .. 20_000)
+            .filter(|n| n % 3 != 0)
+            .chain(30_000 .. 30_100)
+            .rev()
+            .collect();
+        let delta: VecDelta<u32> = delta_as_set(&vec0, &vec1)?;
+        let mut unordered: Vec<u32> = delta.apply_unordered(&vec0)?;
+        let mut ordered: Vec<u32> = vec0.apply(delta)?;
+        assert_ne!(unordered, ordered);
+        unordered.sort_unstable();
+        ordered.sort_unstable();
+        let mut expected: Vec<u32> = vec1;
+        expected.sort_unstable();
+        assert_eq!(unordered, expected);
+        assert_eq!(ordered, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn VecDelta__apply_unordered__moves() -> DeltaResult<()> {
+        let vec0: Vec<u32> = (0 .. 20_000).collect();
+        let vec1: Vec<u32> = (0 .. 20_000).filter(|n| n % 100 != 0).collect();
+        let delta: VecDelta<u32> = delta_as_set(&vec0, &vec1)?;
+        let deleted: usize = vec0.len() - vec1.len();
+        // NOTE: Count the values that no longer occur at their index in `vec0`:
+        let moved = |vec: &[u32]| -> usize {
+            vec.iter().zip(&vec0).filter(|(value, base)| value != base).count()
+        };
+        let unordered: Vec<u32> = delta.apply_unordered(&vec0)?;
+        assert!(moved(&unordered) <= deleted, "{} moved", moved(&unordered));
+        let ordered: Vec<u32> = vec0.apply(delta)?;
+        assert_eq!(moved(&ordered), vec1.len());
+        Ok(())
+    }
+
+    #[test]
+    fn VecDelta__apply_unordered__illegal_ops() -> DeltaResult<()> {
+        let vec0: Vec<u32> = vec![1, 2, 3, 4];
+        let delta: VecDelta<u32> = VecDelta(vec![
+            EltDelta::Edit { index: 0, item: 9u32.into_delta()? },
+            EltDelta::Delete { index: 0 },
+            EltDelta::Delete { index: 0 },
+            EltDelta::Add(5u32.into_delta()?),
+        ]);
+        assert_eq!(delta.apply_unordered(&vec0)?, vec![3, 4, 5]);
+        let delta: VecDelta<u32> = VecDelta(vec![
+            EltDelta::Delete { index: 0 },
+            EltDelta::Edit { index: 0, item: 9u32.into_delta()? },
+        ]);
+        assert_eq!(
+            delta.apply_unordered(&vec0),
+            Err(DeltaError::IllegalDelta { index: 1 })
+        );
+        let delta: VecDelta<u32> = VecDelta(vec![
+            EltDelta::Delete { index: 0 },
+            EltDelta::Add(5u32.into_delta()?),
+            EltDelta::Delete { index: 0 },
+        ]);
+        assert_eq!(
+            delta.apply_unordered(&vec0),
+            Err(DeltaError::IllegalDelta { index: 2 })
+        );
+        Ok(())
+    }
+}
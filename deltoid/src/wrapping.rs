@@ -0,0 +1,219 @@
+//! Delta support for [`Wrapping`] integers.
+//!
+//! Unlike the primitive integers, whose deltas replace the whole value,
+//! a `Wrapping<T>` delta is the offset from the old to the new value,
+//! modulo the range of `T`.  E.g. incrementing a `Wrapping<u32>` counter
+//! by one produces a `WrappingDelta(1)`, regardless of the value of the
+//! counter, and a `Wrapping<u8>` going from 255 to 0 does the same.  An
+//! unchanged value has an offset of zero.  As a consequence, applying a
+//! delta to a different value than the one it was calculated against
+//! shifts that value by the same offset, rather than replacing it.
+//!
+//! [`Wrapping`]: https://doc.rust-lang.org/std/num/struct.Wrapping.html
+
+use crate::{
+    Apply, Compose, Core, Delta, DeltaOps, DeltaResult, EmptyDelta,
+    FromDelta, IntoDelta, Invert,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::num::Wrapping;
+
+
+/// Integer types with wrapping addition and subtraction.
+pub trait WrappingOps: Copy {
+    const ZERO: Self;
+
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_wrapping_ops {
+    ( $($type:ty),* $(,)? ) => {
+        $(
+            impl WrappingOps for $type {
+                const ZERO: Self = 0;
+
+                #[inline(always)]
+                fn wrapping_add(self, rhs: Self) -> Self { self.wrapping_add(rhs) }
+
+                #[inline(always)]
+                fn wrapping_sub(self, rhs: Self) -> Self { self.wrapping_sub(rhs) }
+            }
+        )*
+    };
+}
+
+impl_wrapping_ops!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+
+impl<T> Core for Wrapping<T>
+where T: Clone + Debug + PartialEq + WrappingOps
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    type Delta = WrappingDelta<T>;
+}
+
+impl<T> Apply for Wrapping<T>
+where T: Clone + Debug + PartialEq + WrappingOps
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        Ok(Wrapping(self.0.wrapping_add(delta.0)))
+    }
+}
+
+impl<T> Delta for Wrapping<T>
+where T: Clone + Debug + PartialEq + WrappingOps
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        Ok(WrappingDelta(rhs.0.wrapping_sub(self.0)))
+    }
+}
+
+impl<T> FromDelta for Wrapping<T>
+where T: Clone + Debug + PartialEq + WrappingOps
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Interpret `delta` as an offset from zero.
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        Ok(Wrapping(T::ZERO.wrapping_add(delta.0)))
+    }
+}
+
+impl<T> IntoDelta for Wrapping<T>
+where T: Clone + Debug + PartialEq + WrappingOps
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    /// Return the offset of `self` from zero.
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        Ok(WrappingDelta(self.0.wrapping_sub(T::ZERO)))
+    }
+}
+
+impl<T> EmptyDelta for Wrapping<T>
+where T: Clone + Debug + PartialEq + WrappingOps
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn identity_delta() -> Self::Delta {
+        WrappingDelta(T::ZERO)
+    }
+}
+
+
+/// The wrapping offset from one `Wrapping<T>` value to another.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+#[serde(transparent)]
+pub struct WrappingDelta<T>(#[doc(hidden)] pub T);
+
+impl<T: WrappingOps + PartialEq> DeltaOps for WrappingDelta<T> {
+    /// Returns `true` if the offset is zero, i.e. the value is unchanged.
+    fn is_empty(&self) -> bool { self.0 == T::ZERO }
+}
+
+impl<T: WrappingOps> Compose for WrappingDelta<T> {
+    /// Offsets compose by wrapping addition, so this never overflows.
+    fn compose(&self, next: &Self) -> DeltaResult<Self> {
+        Ok(WrappingDelta(self.0.wrapping_add(next.0)))
+    }
+}
+
+impl<T: WrappingOps> Invert<Wrapping<T>> for WrappingDelta<T> {
+    /// The inverse of an offset doesn't depend on `base`.
+    fn invert(&self, _base: &Wrapping<T>) -> DeltaResult<Self> {
+        Ok(WrappingDelta(T::ZERO.wrapping_sub(self.0)))
+    }
+}
+
+impl<T: Debug> std::fmt::Debug for WrappingDelta<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "WrappingDelta({:?})", self.0)
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn Wrapping__delta__increment() -> DeltaResult<()> {
+        let counter0: Wrapping<u32> = Wrapping(41);
+        let counter1: Wrapping<u32> = counter0 + Wrapping(1);
+        let delta = counter0.delta(&counter1)?;
+        assert_eq!(delta, WrappingDelta(1));
+        assert_eq!(counter0.apply(delta)?, counter1);
+        // NOTE: The offset applies to any value:
+        assert_eq!(Wrapping(100u32).apply(delta)?, Wrapping(101));
+        assert!(counter0.delta(&counter0)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn Wrapping__delta__wrap_boundary() -> DeltaResult<()> {
+        let (max, zero): (Wrapping<u8>, Wrapping<u8>) = (Wrapping(255), Wrapping(0));
+        let delta = max.delta(&zero)?;
+        assert_eq!(delta, WrappingDelta(1));
+        assert_eq!(max.apply(delta)?, zero);
+        let delta = zero.delta(&max)?;
+        assert_eq!(delta, WrappingDelta(255));
+        assert_eq!(zero.apply(delta)?, max);
+
+        let (min, max): (Wrapping<i8>, Wrapping<i8>) = (Wrapping(-128), Wrapping(127));
+        let delta = max.delta(&min)?;
+        assert_eq!(delta, WrappingDelta(1));
+        assert_eq!(max.apply(delta)?, min);
+        assert_eq!(min.apply(min.delta(&max)?)?, max);
+        Ok(())
+    }
+
+    #[test]
+    fn Wrapping__into_delta__round_trip() -> DeltaResult<()> {
+        let value: Wrapping<u16> = Wrapping(65_000);
+        let delta = value.into_delta()?;
+        let json = serde_json::to_string(&delta).expect("failed to serialize");
+        assert_eq!(json, "65000");
+        let delta: WrappingDelta<u16> = serde_json::from_str(&json)
+            .expect("failed to deserialize");
+        assert_eq!(Wrapping::from_delta(delta)?, value);
+        Ok(())
+    }
+
+    #[test]
+    fn WrappingDelta__compose() -> DeltaResult<()> {
+        let (counter0, counter1, counter2): (Wrapping<u8>, _, _) =
+            (Wrapping(250), Wrapping(255), Wrapping(3));
+        let delta = counter0.delta(&counter1)?.compose(&counter1.delta(&counter2)?)?;
+        assert_eq!(delta, WrappingDelta(9));
+        assert_eq!(counter0.apply(delta)?, counter2);
+        Ok(())
+    }
+
+    #[test]
+    fn WrappingDelta__invert() -> DeltaResult<()> {
+        let (counter0, counter1): (Wrapping<u8>, Wrapping<u8>) = (Wrapping(255), Wrapping(2));
+        let delta = counter0.delta(&counter1)?;
+        let inverse = delta.invert(&counter0)?;
+        assert_eq!(inverse, WrappingDelta(253));
+        assert_eq!(counter0.apply(delta)?.apply(inverse)?, counter0);
+        Ok(())
+    }
+
+    #[test]
+    fn WrappingDelta__identity_delta() -> DeltaResult<()> {
+        let counter: Wrapping<u8> = Wrapping(255);
+        assert_eq!(counter.apply(Wrapping::<u8>::identity_delta())?, counter);
+        assert!(Wrapping::<u8>::identity_delta().is_empty());
+        Ok(())
+    }
+}